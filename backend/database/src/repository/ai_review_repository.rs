@@ -0,0 +1,116 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{dto::AIReviewResult, models::AIReviewTask, AIErrorCheckStatus, ProjectStatus};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AIReviewRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl AIReviewRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Queues the project for a fresh review, dropping earlier attempts
+    pub async fn queue_review(&self, project_id: Uuid) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE project SET ai_status = $1, ai_attempts = 0, ai_error = NULL, ai_next_attempt_at = now()
+            WHERE id = $2",
+        )
+        .bind(AIErrorCheckStatus::NotYet.to_i16())
+        .bind(project_id)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Marks due reviews of projects waiting for an editor as in process
+    /// until `lease_until` and returns them. Reviews whose lease ran out are
+    /// picked up again.
+    pub async fn claim_due_reviews(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<AIReviewTask>, SqlxError> {
+        let tasks = sqlx::query_as::<_, AIReviewTask>(
+            "UPDATE project SET ai_status = $1, ai_next_attempt_at = $2
+            WHERE id IN (
+                SELECT id FROM project
+                WHERE status IN ($3, $4) AND ai_status IN ($5, $1, $6) AND ai_next_attempt_at <= $7
+                ORDER BY ai_next_attempt_at LIMIT $8 FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, nerd_id, title, description, details, manuscript, funding_goal, duration, tags, ai_attempts",
+        )
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .bind(ProjectStatus::PendingReview.to_i16())
+        .bind(ProjectStatus::UnderReview.to_i16())
+        .bind(AIErrorCheckStatus::NotYet.to_i16())
+        .bind(AIErrorCheckStatus::Error.to_i16())
+        .bind(now)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(tasks)
+    }
+
+    /// Stores the scores if the review is still claimed under `lease_until`.
+    /// Returns false when the lease ran out and another run took it over.
+    pub async fn mark_review_done(
+        &self,
+        project_id: Uuid,
+        lease_until: DateTime<Utc>,
+        result: &AIReviewResult,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project SET ai_status = $1, ai_analysis = $2, ai_objectives = $3, ai_methodology = $4,
+                ai_budget = $5, ai_expertise = $6, ai_innovation = $7, ai_attempts = ai_attempts + 1,
+                ai_error = NULL, ai_next_attempt_at = NULL, ai_analyzed_at = now()
+            WHERE id = $8 AND ai_status = $9 AND ai_next_attempt_at = $10",
+        )
+        .bind(AIErrorCheckStatus::Done.to_i16())
+        .bind(&result.analysis)
+        .bind(result.objectives)
+        .bind(result.methodology)
+        .bind(result.budget)
+        .bind(result.expertise)
+        .bind(result.innovation)
+        .bind(project_id)
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() > 0)
+    }
+
+    /// Records a failed attempt if the review is still claimed under
+    /// `lease_until`. Without `next_attempt_at` the review is given up.
+    pub async fn mark_review_failed(
+        &self,
+        project_id: Uuid,
+        lease_until: DateTime<Utc>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project SET ai_status = $1, ai_attempts = ai_attempts + 1, ai_error = $2, ai_next_attempt_at = $3
+            WHERE id = $4 AND ai_status = $5 AND ai_next_attempt_at = $6",
+        )
+        .bind(AIErrorCheckStatus::Error.to_i16())
+        .bind(error)
+        .bind(next_attempt_at)
+        .bind(project_id)
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() > 0)
+    }
+}
@@ -1,8 +1,8 @@
-use crate::pool::DatabasePool;
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::{pool::DatabasePool, repository::SearchSql};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use sqlx::{self, Error as SqlxError};
 use std::sync::Arc;
-use types::{models::{Bid, BidMilestone, BidMilestoneStatus, BidMilestoneSubmission, BidMilestoneSubmissionStatus, BidStatus, Bounty, BountyChat, BountyComment, BountyDifficulty, BountyMilestone, BountyMilestoneSubmission, BountyStatus, BountySubmissionStatus, BountyWorkSubmission}};
+use types::{models::{Bid, BidMilestone, BidMilestoneStatus, BidMilestoneSubmission, BidMilestoneSubmissionStatus, BidStatus, Bounty, BountyChat, BountyDifficulty, BountyMilestone, BountyMilestoneSubmission, BountyStatus, BountySubmissionStatus, BountyWorkSubmission, Cursor, ListingSort, ViewEntityType, VIEW_DEDUPE_MINUTES}, UserRoleType};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -175,19 +175,29 @@ impl BountyRepository {
         status: Option<BountyStatus>,
         category_id: Option<Uuid>,
         difficulty: Option<BountyDifficulty>,
-        _role: Option<String>,
+        role: Option<String>,
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
+        sort: Option<ListingSort>,
+        after: Option<Cursor>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<Bounty>, SqlxError> {
         let mut filters = Vec::new();
         let mut index = 3;
-        let mut query = format!("SELECT b.* FROM bounty b");
-        if title.as_ref().map_or(false, |s| !s.is_empty()) {
-            filters.push(format!("b.title ILIKE ${index}"));
+        let mut query = "SELECT b.*".to_string();
+        let is_search = title.as_ref().is_some_and(|s| !s.is_empty());
+        if is_search {
+            let search = SearchSql {
+                alias: "b",
+                snippet_column: "description",
+                param: index,
+            };
+            query = format!("{}, {}", &query, search.columns());
+            filters.push(search.filter());
             index += 1;
         }
+        query = format!("{} FROM bounty b", &query);
         if status.is_some() {
             filters.push(format!("b.status = ${index}"));
             index += 1;
@@ -204,20 +214,32 @@ impl BountyRepository {
         if is_mine.unwrap_or_default() {
             if user_id.is_some() {
                 filters.push(format!("b.user_id = ${index}"));
+                index += 1;
             } else {
                 return Ok(Vec::new());
             }
+        } else if role != Some(UserRoleType::Admin.to_string()) {
+            filters.push("b.moderation_status = 0".to_string());
+        }
+        if after.is_some() {
+            filters.push(format!("(b.updated_at, b.id) < (${}, ${})", index, index + 1));
         }
     
         if !filters.is_empty() {
             query = format!("{} WHERE {}", &query, &filters.join(" AND "));
         }
-        query = format!("{} ORDER BY b.updated_at DESC LIMIT $1 OFFSET $2", &query);
+        let order = match sort {
+            Some(sort) => bounty_order(sort),
+            None if is_search => "search_rank DESC, b.updated_at DESC",
+            None => "b.updated_at DESC, b.id DESC",
+        };
+        query = format!("{} ORDER BY {}", &query, order);
+        query = format!("{} LIMIT $1 OFFSET $2", &query);
         let mut query = sqlx::query_as::<_, Bounty>(&query)
             .bind(limit.unwrap_or(5))
             .bind(offset.unwrap_or(0));
         if let Some(t) = title.as_ref().filter(|s| !s.is_empty()) {
-            query = query.bind(format!("%{}%", t));
+            query = query.bind(t.trim());
         }
         if let Some(s) = status {
             query = query.bind(s);
@@ -233,6 +255,9 @@ impl BountyRepository {
                 query = query.bind(user_id);
             }
         }
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
         
         let bounties = query.fetch_all(self.db_conn.get_pool()).await?;
         Ok(bounties)
@@ -453,40 +478,6 @@ impl BountyRepository {
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn get_bounty_comments(
-        &self,
-        id: Uuid,
-        offset: Option<i32>,
-        limit: Option<i32>,
-    ) -> Result<Vec<BountyComment>, SqlxError> {
-        let bounty_comments = sqlx::query_as::<_, BountyComment>(
-            "SELECT * FROM bounty_comment WHERE bounty_id = $1 ORDER BY created_at LIMIT $2 OFFSET $3",
-        )
-        .bind(id)
-        .bind(limit.unwrap_or(10))
-        .bind(offset.unwrap_or(0))
-        .fetch_all(self.db_conn.get_pool())
-        .await?;
-        Ok(bounty_comments)
-    }
-
-    pub async fn submit_bounty_comment(
-        &self,
-        user_id: Uuid,
-        bounty_id: Uuid,
-        nerd_id: &str,
-        comment: &str,
-    ) -> Result<bool, SqlxError> {
-        let row = sqlx::query("INSERT INTO bounty_comment (user_id, bounty_id, nerd_id, comment) VALUES ($1, $2, $3, $4)")
-            .bind(user_id)
-            .bind(bounty_id)
-            .bind(nerd_id)
-            .bind(comment)
-            .execute(self.db_conn.get_pool())
-            .await?;
-        Ok(row.rows_affected() == 1)
-    }
-
     pub async fn update_bounty_status(
         &self,
         id: Uuid,
@@ -549,17 +540,22 @@ impl BountyRepository {
     pub async fn get_bounty_chats(
         &self,
         chat_number: &str,
+        after: Option<Cursor>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<BountyChat>, SqlxError> {
-        let bounty_chats = sqlx::query_as::<_, BountyChat>(
-            "SELECT * FROM bounty_chat WHERE chat_number = $1 ORDER BY created_at LIMIT $2 OFFSET $3",
-        )
-        .bind(chat_number)
-        .bind(limit.unwrap_or(10))
-        .bind(offset.unwrap_or(0))
-        .fetch_all(self.db_conn.get_pool())
-        .await?;
+        let keyset = if after.is_some() { " AND (created_at, id) > ($4, $5)" } else { "" };
+        let query = format!(
+            "SELECT * FROM bounty_chat WHERE chat_number = $1 AND moderation_status = 0{keyset} ORDER BY created_at, id LIMIT $2 OFFSET $3"
+        );
+        let mut query = sqlx::query_as::<_, BountyChat>(&query)
+            .bind(chat_number)
+            .bind(limit.unwrap_or(10))
+            .bind(offset.unwrap_or(0));
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
+        let bounty_chats = query.fetch_all(self.db_conn.get_pool()).await?;
         Ok(bounty_chats)
     }
 
@@ -680,6 +676,7 @@ impl BountyRepository {
             SELECT b.* FROM bounty b
             WHERE b.id != $1 
             AND b.status = ANY($6)
+            AND b.moderation_status = 0
             AND (
                 EXISTS (
                     SELECT 1 FROM unnest($2) cat1
@@ -984,11 +981,30 @@ impl BountyRepository {
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn increment_view_count(&self, id: Uuid) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE bounty SET count_view = count_view + 1 WHERE id = $1")
-            .bind(id)
-            .execute(self.db_conn.get_pool())
-            .await?;
+    /// Counts a view unless `viewer` already viewed the bounty within the last
+    /// `VIEW_DEDUPE_MINUTES`. Views without a viewer always count.
+    pub async fn increment_view_count(
+        &self,
+        id: Uuid,
+        viewer: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "WITH viewed AS (
+                UPDATE bounty SET count_view = count_view + 1 WHERE id = $1
+                    AND ($3::TEXT IS NULL OR NOT EXISTS (
+                        SELECT 1 FROM view_events
+                        WHERE entity_type = $2 AND entity_id = $1 AND viewer = $3 AND created_at > $4
+                    ))
+                RETURNING id
+            )
+            INSERT INTO view_events (entity_type, entity_id, viewer) SELECT $2, id, $3 FROM viewed",
+        )
+        .bind(id)
+        .bind(ViewEntityType::Bounty)
+        .bind(viewer)
+        .bind(Utc::now() - Duration::minutes(VIEW_DEDUPE_MINUTES))
+        .execute(self.db_conn.get_pool())
+        .await?;
         Ok(row.rows_affected() == 1)
     }
 
@@ -1000,14 +1016,6 @@ impl BountyRepository {
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn increment_comment_count(&self, id: Uuid) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE bounty SET count_comment = count_comment + 1 WHERE id = $1")
-            .bind(id)
-            .execute(self.db_conn.get_pool())
-            .await?;
-        Ok(row.rows_affected() == 1)
-    }
-
     // Bid Milestone Submission Methods
     pub async fn create_bid_milestone_submission(
         &self,
@@ -1145,3 +1153,15 @@ impl BountyRepository {
         Ok(row.rows_affected() == 1)
     }
 }
+
+fn bounty_order(sort: ListingSort) -> &'static str {
+    match sort {
+        ListingSort::Newest => "b.created_at DESC",
+        ListingSort::MostFunded => "b.reward_amount DESC, b.updated_at DESC",
+        // Bounties still open first, by deadline
+        ListingSort::EndingSoonest => "b.deadline < CURRENT_DATE, b.deadline, b.updated_at DESC",
+        ListingSort::MostViewed => "b.count_view DESC, b.updated_at DESC",
+        ListingSort::Trending => "b.trending_score DESC, b.updated_at DESC",
+        ListingSort::Updated | ListingSort::ClosestToGoal => "b.updated_at DESC, b.id DESC",
+    }
+}
@@ -0,0 +1,130 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::models::{
+    CollaborationInvitation, CollaboratorRole, CollaboratorStatus, ProjectCollaborator,
+};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct CollaboratorRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl CollaboratorRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Invites `user_id`, or re-invites them after a decline. Returns `None`
+    /// when the user is already invited or collaborating.
+    pub async fn create_collaborator(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        role: CollaboratorRole,
+        invited_by: Uuid,
+    ) -> Result<Option<ProjectCollaborator>, SqlxError> {
+        let collaborator = sqlx::query_as::<_, ProjectCollaborator>(
+            "INSERT INTO project_collaborators (project_id, user_id, role, invited_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (project_id, user_id) DO UPDATE SET
+                role = $3, invited_by = $4, status = $5, responded_at = NULL, updated_at = now()
+            WHERE project_collaborators.status = $6
+            RETURNING *",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(role)
+        .bind(invited_by)
+        .bind(CollaboratorStatus::Invited)
+        .bind(CollaboratorStatus::Declined)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(collaborator)
+    }
+
+    pub async fn get_collaborator(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Option<ProjectCollaborator> {
+        sqlx::query_as::<_, ProjectCollaborator>(
+            "SELECT * FROM project_collaborators WHERE project_id = $1 AND user_id = $2",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    pub async fn get_collaborators(
+        &self,
+        project_id: Uuid,
+        status: Option<CollaboratorStatus>,
+    ) -> Result<Vec<ProjectCollaborator>, SqlxError> {
+        let collaborators = sqlx::query_as::<_, ProjectCollaborator>(
+            "SELECT * FROM project_collaborators
+            WHERE project_id = $1 AND ($2::smallint IS NULL OR status = $2)
+            ORDER BY created_at",
+        )
+        .bind(project_id)
+        .bind(status)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(collaborators)
+    }
+
+    pub async fn respond_invitation(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        status: CollaboratorStatus,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project_collaborators SET status = $1, responded_at = now(), updated_at = now()
+            WHERE project_id = $2 AND user_id = $3 AND status = $4",
+        )
+        .bind(status)
+        .bind(project_id)
+        .bind(user_id)
+        .bind(CollaboratorStatus::Invited)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    pub async fn delete_collaborator(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        let row =
+            sqlx::query("DELETE FROM project_collaborators WHERE project_id = $1 AND user_id = $2")
+                .bind(project_id)
+                .bind(user_id)
+                .execute(self.db_conn.get_pool())
+                .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    pub async fn get_invitations(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<CollaborationInvitation>, SqlxError> {
+        let invitations = sqlx::query_as::<_, CollaborationInvitation>(
+            "SELECT pc.id, pc.project_id, p.title AS project_title, pc.role, pc.invited_by, pc.created_at
+            FROM project_collaborators pc JOIN project p ON p.id = pc.project_id
+            WHERE pc.user_id = $1 AND pc.status = $2
+            ORDER BY pc.created_at DESC",
+        )
+        .bind(user_id)
+        .bind(CollaboratorStatus::Invited)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(invitations)
+    }
+}
@@ -0,0 +1,311 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::models::{
+    Comment, CommentEdit, CommentEntityType, CommentReaction, CommentTarget, Cursor,
+};
+use uuid::Uuid;
+
+/// The comment table and the column pointing at the commented entity
+fn comment_table(entity_type: CommentEntityType) -> (&'static str, &'static str) {
+    match entity_type {
+        CommentEntityType::Project => ("project_comment", "project_id"),
+        CommentEntityType::Bounty => ("bounty_comment", "bounty_id"),
+    }
+}
+
+fn comment_columns(fk: &str) -> String {
+    format!(
+        "id, user_id, {fk} AS entity_id, parent_id, comment, is_pinned, edited_at, deleted_at, created_at, updated_at"
+    )
+}
+
+/// Keeps `bounty.count_comment` in step with the live comments. Appended to
+/// a CTE whose `changed` step returns the affected `bounty_id`s.
+fn bounty_count_step(entity_type: CommentEntityType, delta: &str) -> String {
+    match entity_type {
+        CommentEntityType::Bounty => format!(
+            ", counted AS (UPDATE bounty SET count_comment = GREATEST(count_comment {delta}, 0) WHERE id IN (SELECT bounty_id FROM changed))"
+        ),
+        CommentEntityType::Project => String::new(),
+    }
+}
+
+#[derive(Clone)]
+pub struct CommentRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl CommentRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    pub async fn get_target(
+        &self,
+        entity_type: CommentEntityType,
+        entity_id: Uuid,
+    ) -> Option<CommentTarget> {
+        let table = match entity_type {
+            CommentEntityType::Project => "project",
+            CommentEntityType::Bounty => "bounty",
+        };
+        sqlx::query_as::<_, CommentTarget>(&format!(
+            "SELECT id, user_id, nerd_id, title FROM {table} WHERE id = $1"
+        ))
+        .bind(entity_id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    /// Top level comments that aren't pinned, oldest first. Deleted comments
+    /// are kept while they still have replies. Moderated comments are left
+    /// out along with their replies.
+    pub async fn get_comments(
+        &self,
+        entity_type: CommentEntityType,
+        entity_id: Uuid,
+        after: Option<Cursor>,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<Comment>, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        let keyset = if after.is_some() {
+            " AND (c.created_at, c.id) > ($4, $5)"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT {columns} FROM {table} c
+            WHERE c.{fk} = $1 AND c.parent_id IS NULL AND NOT c.is_pinned AND c.moderation_status = 0
+                AND (c.deleted_at IS NULL OR EXISTS (SELECT 1 FROM {table} r WHERE r.parent_id = c.id AND r.deleted_at IS NULL AND r.moderation_status = 0)){keyset}
+            ORDER BY c.created_at, c.id LIMIT $2 OFFSET $3"
+        );
+        let mut query = sqlx::query_as::<_, Comment>(&query)
+            .bind(entity_id)
+            .bind(limit.unwrap_or(10))
+            .bind(offset.unwrap_or(0));
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
+        let comments = query.fetch_all(self.db_conn.get_pool()).await?;
+        Ok(comments)
+    }
+
+    pub async fn get_pinned_comments(
+        &self,
+        entity_type: CommentEntityType,
+        entity_id: Uuid,
+    ) -> Result<Vec<Comment>, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        let comments = sqlx::query_as::<_, Comment>(&format!(
+            "SELECT {columns} FROM {table}
+            WHERE {fk} = $1 AND parent_id IS NULL AND is_pinned AND deleted_at IS NULL AND moderation_status = 0
+            ORDER BY created_at, id"
+        ))
+        .bind(entity_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(comments)
+    }
+
+    pub async fn get_replies(
+        &self,
+        entity_type: CommentEntityType,
+        parent_ids: &[Uuid],
+    ) -> Result<Vec<Comment>, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        let comments = sqlx::query_as::<_, Comment>(&format!(
+            "SELECT {columns} FROM {table}
+            WHERE parent_id = ANY($1) AND deleted_at IS NULL AND moderation_status = 0
+            ORDER BY created_at, id"
+        ))
+        .bind(parent_ids)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(comments)
+    }
+
+    pub async fn get_comment_by_id(
+        &self,
+        entity_type: CommentEntityType,
+        id: Uuid,
+    ) -> Option<Comment> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        sqlx::query_as::<_, Comment>(&format!("SELECT {columns} FROM {table} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+            .unwrap_or(None)
+    }
+
+    pub async fn create_comment(
+        &self,
+        entity_type: CommentEntityType,
+        target: &CommentTarget,
+        user_id: Uuid,
+        parent_id: Option<Uuid>,
+        comment: &str,
+    ) -> Result<Comment, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        let counted = bounty_count_step(entity_type, "+ 1");
+        let comment = sqlx::query_as::<_, Comment>(&format!(
+            "WITH changed AS (
+                INSERT INTO {table} (user_id, {fk}, nerd_id, parent_id, comment)
+                VALUES ($1, $2, $3, $4, $5) RETURNING *
+            ){counted}
+            SELECT {columns} FROM changed"
+        ))
+        .bind(user_id)
+        .bind(target.id)
+        .bind(&target.nerd_id)
+        .bind(parent_id)
+        .bind(comment)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(comment)
+    }
+
+    /// Saves the current text to the edit history and replaces it
+    pub async fn edit_comment(
+        &self,
+        entity_type: CommentEntityType,
+        id: Uuid,
+        comment: &str,
+    ) -> Result<Comment, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let columns = comment_columns(fk);
+        let comment = sqlx::query_as::<_, Comment>(&format!(
+            "WITH previous AS (
+                SELECT id, comment FROM {table} WHERE id = $1 AND deleted_at IS NULL
+            ), logged AS (
+                INSERT INTO {table}_edit (comment_id, comment) SELECT id, comment FROM previous
+            )
+            UPDATE {table} SET comment = $2, edited_at = now(), updated_at = now()
+            WHERE id IN (SELECT id FROM previous)
+            RETURNING {columns}"
+        ))
+        .bind(id)
+        .bind(comment)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(comment)
+    }
+
+    /// Clears the text, edit history and reactions but keeps the row so its
+    /// replies stay in place
+    pub async fn delete_comment(
+        &self,
+        entity_type: CommentEntityType,
+        id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        let (table, fk) = comment_table(entity_type);
+        let counted = bounty_count_step(entity_type, "- 1");
+        let deleted = sqlx::query_scalar::<_, i64>(&format!(
+            "WITH changed AS (
+                UPDATE {table} SET comment = '', is_pinned = FALSE, deleted_at = now(), updated_at = now()
+                WHERE id = $1 AND deleted_at IS NULL RETURNING id, {fk}
+            ), edits AS (
+                DELETE FROM {table}_edit WHERE comment_id IN (SELECT id FROM changed)
+            ), reactions AS (
+                DELETE FROM {table}_reaction WHERE comment_id IN (SELECT id FROM changed)
+            ){counted}
+            SELECT COUNT(*) FROM changed"
+        ))
+        .bind(id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(deleted == 1)
+    }
+
+    pub async fn set_pinned(
+        &self,
+        entity_type: CommentEntityType,
+        id: Uuid,
+        is_pinned: bool,
+    ) -> Result<bool, SqlxError> {
+        let (table, _) = comment_table(entity_type);
+        let row = sqlx::query(&format!(
+            "UPDATE {table} SET is_pinned = $2 WHERE id = $1 AND deleted_at IS NULL"
+        ))
+        .bind(id)
+        .bind(is_pinned)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    /// Adds the reaction, or removes it if the user already reacted with
+    /// that emoji. Returns whether the reaction exists afterwards.
+    pub async fn toggle_reaction(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: Uuid,
+        user_id: Uuid,
+        emoji: &str,
+    ) -> Result<bool, SqlxError> {
+        let (table, _) = comment_table(entity_type);
+        let reacted = sqlx::query_scalar::<_, bool>(&format!(
+            "WITH removed AS (
+                DELETE FROM {table}_reaction WHERE comment_id = $1 AND user_id = $2 AND emoji = $3 RETURNING 1
+            ), added AS (
+                INSERT INTO {table}_reaction (comment_id, user_id, emoji)
+                SELECT $1, $2, $3 WHERE NOT EXISTS (SELECT 1 FROM removed)
+                ON CONFLICT DO NOTHING RETURNING 1
+            )
+            SELECT EXISTS (SELECT 1 FROM added)"
+        ))
+        .bind(comment_id)
+        .bind(user_id)
+        .bind(emoji)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(reacted)
+    }
+
+    /// Reaction counts of the given comments, in the order each emoji was
+    /// first used
+    pub async fn get_reactions(
+        &self,
+        entity_type: CommentEntityType,
+        comment_ids: &[Uuid],
+        viewer_id: Option<Uuid>,
+    ) -> Result<Vec<CommentReaction>, SqlxError> {
+        let (table, _) = comment_table(entity_type);
+        let reactions = sqlx::query_as::<_, CommentReaction>(&format!(
+            "SELECT comment_id, emoji, COUNT(*) AS count, COALESCE(BOOL_OR(user_id = $2), FALSE) AS reacted
+            FROM {table}_reaction WHERE comment_id = ANY($1)
+            GROUP BY comment_id, emoji ORDER BY MIN(created_at)"
+        ))
+        .bind(comment_ids)
+        .bind(viewer_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reactions)
+    }
+
+    /// Earlier versions of a comment, most recent first
+    pub async fn get_edits(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: Uuid,
+    ) -> Result<Vec<CommentEdit>, SqlxError> {
+        let (table, _) = comment_table(entity_type);
+        let edits = sqlx::query_as::<_, CommentEdit>(&format!(
+            "SELECT id, comment_id, comment, edited_at FROM {table}_edit
+            WHERE comment_id = $1 ORDER BY edited_at DESC"
+        ))
+        .bind(comment_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(edits)
+    }
+}
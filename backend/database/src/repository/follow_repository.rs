@@ -0,0 +1,272 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError, PgConnection};
+use std::sync::Arc;
+use types::models::{FollowEntityType, SavedItem};
+use uuid::Uuid;
+
+const FOLLOWS: &str = "follows";
+const BOOKMARKS: &str = "bookmarks";
+
+#[derive(Clone)]
+pub struct FollowRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl FollowRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    pub async fn entity_exists(&self, entity_type: FollowEntityType, entity_id: Uuid) -> bool {
+        let table = match entity_type {
+            FollowEntityType::Project => "project",
+            FollowEntityType::Bounty => "bounty",
+            FollowEntityType::Dao => "dao",
+        };
+        sqlx::query_scalar::<_, bool>(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {table} WHERE id = $1)"
+        ))
+        .bind(entity_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or(false)
+    }
+
+    /// Removes the row if it exists and inserts it otherwise. Returns whether
+    /// the row exists afterwards.
+    async fn toggle(
+        &self,
+        table: &str,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        let saved = sqlx::query_scalar::<_, bool>(&format!(
+            "WITH removed AS (
+                DELETE FROM {table} WHERE user_id = $1 AND entity_type = $2 AND entity_id = $3 RETURNING 1
+            ), added AS (
+                INSERT INTO {table} (user_id, entity_type, entity_id)
+                SELECT $1, $2, $3 WHERE NOT EXISTS (SELECT 1 FROM removed)
+                ON CONFLICT DO NOTHING RETURNING 1
+            )
+            SELECT EXISTS (SELECT 1 FROM added)"
+        ))
+        .bind(user_id)
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(saved)
+    }
+
+    /// Saved items of a user, newest first. Items whose entity has been
+    /// deleted are left out.
+    async fn get_saved_items(
+        &self,
+        table: &str,
+        user_id: Uuid,
+        entity_type: Option<FollowEntityType>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<SavedItem>, SqlxError> {
+        let mut conn = self.db_conn.get_pool().acquire().await?;
+        Self::fetch_saved_items(&mut conn, table, user_id, entity_type, offset, limit).await
+    }
+
+    async fn fetch_saved_items(
+        conn: &mut PgConnection,
+        table: &str,
+        user_id: Uuid,
+        entity_type: Option<FollowEntityType>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<SavedItem>, SqlxError> {
+        let items = sqlx::query_as::<_, SavedItem>(&format!(
+            "SELECT s.entity_type, s.entity_id,
+                COALESCE(p.nerd_id, b.nerd_id, d.nerd_id) AS nerd_id,
+                COALESCE(p.title, b.title, d.title) AS title,
+                COALESCE(p.cover_photo, b.cover_photo, d.cover_photo) AS cover_photo,
+                COALESCE(p.status, b.status, d.status, 0)::SMALLINT AS status,
+                s.created_at
+            FROM {table} s
+            LEFT JOIN project p ON s.entity_type = $2 AND p.id = s.entity_id
+            LEFT JOIN bounty b ON s.entity_type = $3 AND b.id = s.entity_id
+            LEFT JOIN dao d ON s.entity_type = $4 AND d.id = s.entity_id
+            WHERE s.user_id = $1 AND ($5::SMALLINT IS NULL OR s.entity_type = $5)
+                AND COALESCE(p.id, b.id, d.id) IS NOT NULL
+                AND COALESCE(p.moderation_status, b.moderation_status, 0) = 0
+            ORDER BY s.created_at DESC LIMIT $6 OFFSET $7"
+        ))
+        .bind(user_id)
+        .bind(FollowEntityType::Project)
+        .bind(FollowEntityType::Bounty)
+        .bind(FollowEntityType::Dao)
+        .bind(entity_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(conn)
+        .await?;
+        Ok(items)
+    }
+
+    /// Which of `entity_ids` the user has saved
+    async fn get_saved_ids(
+        &self,
+        table: &str,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_ids: &[Uuid],
+    ) -> Vec<Uuid> {
+        sqlx::query_scalar::<_, Uuid>(&format!(
+            "SELECT entity_id FROM {table} WHERE user_id = $1 AND entity_type = $2 AND entity_id = ANY($3)"
+        ))
+        .bind(user_id)
+        .bind(entity_type)
+        .bind(entity_ids)
+        .fetch_all(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+
+    pub async fn toggle_follow(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        self.toggle(FOLLOWS, user_id, entity_type, entity_id).await
+    }
+
+    pub async fn toggle_bookmark(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        self.toggle(BOOKMARKS, user_id, entity_type, entity_id)
+            .await
+    }
+
+    pub async fn get_follows(
+        &self,
+        user_id: Uuid,
+        entity_type: Option<FollowEntityType>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<SavedItem>, SqlxError> {
+        self.get_saved_items(FOLLOWS, user_id, entity_type, offset, limit)
+            .await
+    }
+
+    pub async fn get_bookmarks(
+        &self,
+        user_id: Uuid,
+        entity_type: Option<FollowEntityType>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<SavedItem>, SqlxError> {
+        self.get_saved_items(BOOKMARKS, user_id, entity_type, offset, limit)
+            .await
+    }
+
+    pub async fn get_followed_ids(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_ids: &[Uuid],
+    ) -> Vec<Uuid> {
+        self.get_saved_ids(FOLLOWS, user_id, entity_type, entity_ids)
+            .await
+    }
+
+    pub async fn get_bookmarked_ids(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_ids: &[Uuid],
+    ) -> Vec<Uuid> {
+        self.get_saved_ids(BOOKMARKS, user_id, entity_type, entity_ids)
+            .await
+    }
+
+    /// Users following any of the given entities, each listed once
+    pub async fn get_follower_ids(
+        &self,
+        entities: &[(FollowEntityType, Uuid)],
+    ) -> Result<Vec<Uuid>, SqlxError> {
+        let (types, ids): (Vec<i16>, Vec<Uuid>) = entities
+            .iter()
+            .map(|(entity_type, id)| (i16::from(*entity_type), *id))
+            .unzip();
+        let user_ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT DISTINCT f.user_id FROM follows f
+            JOIN UNNEST($1::SMALLINT[], $2::UUID[]) AS e(entity_type, entity_id)
+                ON f.entity_type = e.entity_type AND f.entity_id = e.entity_id",
+        )
+        .bind(types)
+        .bind(ids)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(user_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+    use types::models::ModerationStatus;
+
+    #[tokio::test]
+    async fn decodes_saved_items() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let mut conn = PgConnection::connect(&url).await.unwrap();
+        let mut tx = conn.begin().await.unwrap();
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, tier) VALUES ('saved-items@test.local', 'free') RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO project (nerd_id, user_id, title, status) VALUES ('RP-SAVED-TEST', $1, 'Saved', 2) RETURNING id",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        for table in [FOLLOWS, BOOKMARKS] {
+            sqlx::query(&format!(
+                "INSERT INTO {table} (user_id, entity_type, entity_id) VALUES ($1, $2, $3)"
+            ))
+            .bind(user_id)
+            .bind(FollowEntityType::Project)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+            let items = FollowRepository::fetch_saved_items(&mut tx, table, user_id, None, 0, 10)
+                .await
+                .unwrap();
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0].entity_type, FollowEntityType::Project);
+            assert_eq!(items[0].entity_id, project_id);
+            assert_eq!(items[0].nerd_id, "RP-SAVED-TEST");
+            assert_eq!(items[0].status, 2);
+        }
+        sqlx::query("UPDATE project SET moderation_status = $2 WHERE id = $1")
+            .bind(project_id)
+            .bind(ModerationStatus::Hidden)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        for table in [FOLLOWS, BOOKMARKS] {
+            let items = FollowRepository::fetch_saved_items(&mut tx, table, user_id, None, 0, 10)
+                .await
+                .unwrap();
+            assert!(items.is_empty());
+        }
+    }
+}
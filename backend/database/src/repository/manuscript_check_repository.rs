@@ -0,0 +1,231 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{
+    dto::ManuscriptFindingResult,
+    models::{CollaboratorStatus, ManuscriptCheck, ManuscriptFinding},
+    AIErrorCheckStatus, AnalysisErrorType, ErrorStatistics,
+};
+use uuid::Uuid;
+
+/// Counts findings per `AnalysisErrorType` over the rows of `f`
+fn error_statistics_columns() -> String {
+    format!(
+        "COUNT(f.id) FILTER (WHERE f.error_type = {}) AS math_errors,
+        COUNT(f.id) FILTER (WHERE f.error_type = {}) AS methodology_errors,
+        COUNT(f.id) FILTER (WHERE f.error_type = {}) AS logical_framework_errors,
+        COUNT(f.id) FILTER (WHERE f.error_type = {}) AS data_analysis_errors,
+        COUNT(f.id) FILTER (WHERE f.error_type = {}) AS technical_presentation_errors,
+        COUNT(f.id) FILTER (WHERE f.error_type = {}) AS research_quality_errors,
+        COUNT(f.id) AS total_errors",
+        i16::from(AnalysisErrorType::MathError),
+        i16::from(AnalysisErrorType::MethodologyError),
+        i16::from(AnalysisErrorType::LogicalFrameworkError),
+        i16::from(AnalysisErrorType::DataAnalysisError),
+        i16::from(AnalysisErrorType::TechnicalPresentationError),
+        i16::from(AnalysisErrorType::ResearchQualityError),
+    )
+}
+
+#[derive(Clone)]
+pub struct ManuscriptCheckRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl ManuscriptCheckRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// The owner, accepted collaborators and handling editors of the project
+    pub async fn can_access(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM project WHERE id = $1 AND user_id = $2)
+                OR EXISTS (SELECT 1 FROM project_collaborators WHERE project_id = $1 AND user_id = $2 AND status = $3)
+                OR EXISTS (SELECT 1 FROM project_editor WHERE project_id = $1 AND user_id = $2)",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(i16::from(CollaboratorStatus::Accepted))
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+
+    pub async fn get_latest_check(&self, project_id: Uuid) -> Option<ManuscriptCheck> {
+        sqlx::query_as::<_, ManuscriptCheck>(
+            "SELECT * FROM manuscript_checks WHERE project_id = $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(project_id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    /// Queues a check of `manuscript`. Returns None while the project
+    /// already has a queued or running check.
+    pub async fn queue_check(
+        &self,
+        project_id: Uuid,
+        requested_by: Uuid,
+        manuscript: &str,
+    ) -> Result<Option<ManuscriptCheck>, SqlxError> {
+        let check = sqlx::query_as::<_, ManuscriptCheck>(
+            "INSERT INTO manuscript_checks (project_id, requested_by, manuscript, status)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            RETURNING *",
+        )
+        .bind(project_id)
+        .bind(requested_by)
+        .bind(manuscript)
+        .bind(AIErrorCheckStatus::NotYet.to_i16())
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(check)
+    }
+
+    /// Marks the oldest queued check as in process until `lease_until` and
+    /// returns it. Checks whose lease ran out are picked up again.
+    pub async fn claim_next_check(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<ManuscriptCheck>, SqlxError> {
+        let check = sqlx::query_as::<_, ManuscriptCheck>(
+            "UPDATE manuscript_checks SET status = $1, lease_until = $2
+            WHERE id = (
+                SELECT id FROM manuscript_checks
+                WHERE status = $3 OR (status = $1 AND lease_until <= $4)
+                ORDER BY created_at LIMIT 1 FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *",
+        )
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .bind(AIErrorCheckStatus::NotYet.to_i16())
+        .bind(now)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(check)
+    }
+
+    /// Stores the findings and marks the check as done, if it is still
+    /// claimed under `lease_until`
+    pub async fn complete_check(
+        &self,
+        check_id: Uuid,
+        lease_until: DateTime<Utc>,
+        findings: &[ManuscriptFindingResult],
+    ) -> Result<Option<ManuscriptCheck>, SqlxError> {
+        let mut error_types = Vec::with_capacity(findings.len());
+        let mut severities = Vec::with_capacity(findings.len());
+        let mut titles = Vec::with_capacity(findings.len());
+        let mut descriptions = Vec::with_capacity(findings.len());
+        let mut locations = Vec::with_capacity(findings.len());
+        for finding in findings {
+            error_types.push(i16::from(finding.error_type));
+            severities.push(i16::from(finding.severity));
+            titles.push(finding.title.clone());
+            descriptions.push(finding.description.clone());
+            locations.push(finding.location.clone());
+        }
+        let check = sqlx::query_as::<_, ManuscriptCheck>(
+            "WITH claimed AS (
+                SELECT id FROM manuscript_checks WHERE id = $1 AND status = $8 AND lease_until = $9
+            ), added AS (
+                INSERT INTO manuscript_findings (check_id, error_type, severity, title, description, location)
+                SELECT c.id, f.* FROM claimed c,
+                    UNNEST($2::SMALLINT[], $3::SMALLINT[], $4::TEXT[], $5::TEXT[], $6::TEXT[]) f
+                RETURNING 1
+            )
+            UPDATE manuscript_checks SET status = $7, error = NULL, total_errors = (SELECT COUNT(*) FROM added),
+                lease_until = NULL, completed_at = now()
+            WHERE id IN (SELECT id FROM claimed)
+            RETURNING *",
+        )
+        .bind(check_id)
+        .bind(error_types)
+        .bind(severities)
+        .bind(titles)
+        .bind(descriptions)
+        .bind(locations)
+        .bind(AIErrorCheckStatus::Done.to_i16())
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(check)
+    }
+
+    /// Keeps the error, if the check is still claimed under `lease_until`
+    pub async fn fail_check(
+        &self,
+        check_id: Uuid,
+        lease_until: DateTime<Utc>,
+        error: &str,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE manuscript_checks SET status = $1, error = $2, lease_until = NULL, completed_at = now()
+            WHERE id = $3 AND status = $4 AND lease_until = $5",
+        )
+        .bind(AIErrorCheckStatus::Error.to_i16())
+        .bind(error)
+        .bind(check_id)
+        .bind(AIErrorCheckStatus::InProcess.to_i16())
+        .bind(lease_until)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() > 0)
+    }
+
+    /// Most severe first
+    pub async fn get_findings(&self, check_id: Uuid) -> Result<Vec<ManuscriptFinding>, SqlxError> {
+        let findings = sqlx::query_as::<_, ManuscriptFinding>(
+            "SELECT * FROM manuscript_findings WHERE check_id = $1
+            ORDER BY severity DESC, error_type, created_at, id",
+        )
+        .bind(check_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(findings)
+    }
+
+    pub async fn get_check_statistics(&self, check_id: Uuid) -> Result<ErrorStatistics, SqlxError> {
+        let statistics = sqlx::query_as::<_, ErrorStatistics>(&format!(
+            "SELECT {} FROM manuscript_findings f WHERE f.check_id = $1",
+            error_statistics_columns()
+        ))
+        .bind(check_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(statistics)
+    }
+
+    /// Findings of every completed check across the platform
+    pub async fn get_error_statistics(&self) -> Result<ErrorStatistics, SqlxError> {
+        let statistics = sqlx::query_as::<_, ErrorStatistics>(&format!(
+            "SELECT {} FROM manuscript_findings f
+            JOIN manuscript_checks c ON c.id = f.check_id AND c.status = $1",
+            error_statistics_columns()
+        ))
+        .bind(AIErrorCheckStatus::Done.to_i16())
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(statistics)
+    }
+
+    /// The number of checked projects and of completed checks
+    pub async fn get_check_counts(&self) -> Result<(i64, i64), SqlxError> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COUNT(DISTINCT project_id), COUNT(*) FROM manuscript_checks WHERE status = $1",
+        )
+        .bind(AIErrorCheckStatus::Done.to_i16())
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(counts)
+    }
+}
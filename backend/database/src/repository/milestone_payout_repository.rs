@@ -0,0 +1,116 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::models::{MilestonePayoutInfo, MilestonePayoutStatus};
+use uuid::Uuid;
+
+const PAYOUT_INFO_QUERY: &str = "SELECT mp.milestone_id, mp.project_id,
+        p.nerd_id AS project_nerd_id, p.title AS project_title,
+        m.number, m.title AS milestone_title, m.funding_amount,
+        mp.status, mp.approval_tx_hash, mp.approved_at, mp.claimable_at,
+        mp.claim_tx_hash, mp.claimed_amount, mp.claimed_at, mp.updated_at
+    FROM milestone_payouts mp
+    JOIN milestone m ON m.id = mp.milestone_id
+    JOIN project p ON p.id = mp.project_id";
+
+#[derive(Clone)]
+pub struct MilestonePayoutRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl MilestonePayoutRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Moves the payout of milestone `number` to `status`. The status never
+    /// goes back and the first time each step was reached is kept. Returns
+    /// false when the project has no such milestone.
+    pub async fn record_payout(
+        &self,
+        project_id: Uuid,
+        number: i16,
+        status: MilestonePayoutStatus,
+        tx_hash: Option<&str>,
+        claimed_amount: Option<i32>,
+    ) -> Result<bool, SqlxError> {
+        let now = Utc::now();
+        let reached =
+            |s: MilestonePayoutStatus| -> Option<DateTime<Utc>> { (status == s).then_some(now) };
+        let (approval_tx_hash, claim_tx_hash) = match status {
+            MilestonePayoutStatus::ApprovedByAdmin => (tx_hash, None),
+            MilestonePayoutStatus::Claimable => (None, None),
+            MilestonePayoutStatus::Claimed => (None, tx_hash),
+        };
+        let result = sqlx::query(
+            "INSERT INTO milestone_payouts (milestone_id, project_id, status, approval_tx_hash,
+                approved_at, claimable_at, claim_tx_hash, claimed_amount, claimed_at)
+            SELECT id, project_id, $3, $4, $5, $6, $7, $8, $9
+            FROM milestone WHERE project_id = $1 AND number = $2
+            ON CONFLICT (milestone_id) DO UPDATE SET
+                status = GREATEST(milestone_payouts.status, EXCLUDED.status),
+                approval_tx_hash = COALESCE(EXCLUDED.approval_tx_hash, milestone_payouts.approval_tx_hash),
+                approved_at = COALESCE(milestone_payouts.approved_at, EXCLUDED.approved_at),
+                claimable_at = COALESCE(milestone_payouts.claimable_at, EXCLUDED.claimable_at),
+                claim_tx_hash = COALESCE(milestone_payouts.claim_tx_hash, EXCLUDED.claim_tx_hash),
+                claimed_amount = COALESCE(milestone_payouts.claimed_amount, EXCLUDED.claimed_amount),
+                claimed_at = COALESCE(milestone_payouts.claimed_at, EXCLUDED.claimed_at),
+                updated_at = now()",
+        )
+        .bind(project_id)
+        .bind(number)
+        .bind(status)
+        .bind(approval_tx_hash)
+        .bind(reached(MilestonePayoutStatus::ApprovedByAdmin))
+        .bind(reached(MilestonePayoutStatus::Claimable))
+        .bind(claim_tx_hash)
+        .bind(claimed_amount)
+        .bind(reached(MilestonePayoutStatus::Claimed))
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Payouts of the projects the user created, most recently updated first
+    pub async fn get_payouts_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MilestonePayoutInfo>, SqlxError> {
+        let payouts = sqlx::query_as::<_, MilestonePayoutInfo>(&format!(
+            "{} WHERE p.user_id = $1 ORDER BY mp.updated_at DESC, m.number",
+            PAYOUT_INFO_QUERY
+        ))
+        .bind(user_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(payouts)
+    }
+
+    pub async fn get_payouts(
+        &self,
+        status: Option<MilestonePayoutStatus>,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<MilestonePayoutInfo>, SqlxError> {
+        let filter = if status.is_some() {
+            " WHERE mp.status = $3"
+        } else {
+            ""
+        };
+        let query = format!(
+            "{}{} ORDER BY mp.updated_at DESC, mp.milestone_id LIMIT $1 OFFSET $2",
+            PAYOUT_INFO_QUERY, filter
+        );
+        let mut query = sqlx::query_as::<_, MilestonePayoutInfo>(&query)
+            .bind(limit.unwrap_or(10))
+            .bind(offset.unwrap_or(0));
+        if let Some(status) = status {
+            query = query.bind(status);
+        }
+        let payouts = query.fetch_all(self.db_conn.get_pool()).await?;
+        Ok(payouts)
+    }
+}
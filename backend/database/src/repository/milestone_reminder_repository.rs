@@ -0,0 +1,96 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{
+    models::{MilestoneReminder, MilestoneReminderKind},
+    FeedbackStatus, MileStoneStatus, ProjectStatus,
+};
+
+const DUE_AT: &str = "p.started_at + m.days_after_start * INTERVAL '1 day'";
+
+#[derive(Clone)]
+pub struct MilestoneReminderRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl MilestoneReminderRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Records a reminder for every milestone of a funded project that still
+    /// awaits proof and whose due date matches `window`, once per `days`
+    /// entry. Returns the milestones that weren't reminded yet, with the
+    /// smallest matching entry.
+    async fn claim_reminders(
+        &self,
+        kind: MilestoneReminderKind,
+        window: &str,
+        days: &[i32],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MilestoneReminder>, SqlxError> {
+        let reminders = sqlx::query_as::<_, MilestoneReminder>(&format!(
+            "WITH due AS (
+                SELECT m.id, d.days
+                FROM milestone m
+                JOIN project p ON p.id = m.project_id
+                CROSS JOIN UNNEST($1::INT[]) AS d(days)
+                WHERE p.status = $2 AND p.started_at IS NOT NULL
+                    AND m.status IN ($5, $6) AND m.proof_status IN ($7, $8)
+                    AND {window}
+            ),
+            sent AS (
+                INSERT INTO milestone_reminders (milestone_id, kind, days_before)
+                SELECT id, $3, days FROM due
+                ON CONFLICT DO NOTHING
+                RETURNING milestone_id, days_before
+            )
+            SELECT DISTINCT ON (m.id) m.id AS milestone_id, m.project_id, p.nerd_id AS project_nerd_id,
+                p.title AS project_title, p.user_id AS researcher_id, m.number, m.title AS milestone_title,
+                {due_at} AS due_at, s.days_before
+            FROM sent s
+            JOIN milestone m ON m.id = s.milestone_id
+            JOIN project p ON p.id = m.project_id
+            ORDER BY m.id, s.days_before",
+            window = window.replace("{due_at}", DUE_AT),
+            due_at = DUE_AT,
+        ))
+        .bind(days)
+        .bind(ProjectStatus::Funding.to_i16())
+        .bind(kind)
+        .bind(now)
+        .bind(MileStoneStatus::Pending.to_i16())
+        .bind(MileStoneStatus::InProgress.to_i16())
+        .bind(FeedbackStatus::Pending.to_i16())
+        .bind(FeedbackStatus::Rejected.to_i16())
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reminders)
+    }
+
+    /// Milestones due within one of `days` days from `now`
+    pub async fn claim_due_soon(
+        &self,
+        days: &[i32],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MilestoneReminder>, SqlxError> {
+        self.claim_reminders(
+            MilestoneReminderKind::DueSoon,
+            "{due_at} > $4 AND {due_at} <= $4 + d.days * INTERVAL '1 day'",
+            days,
+            now,
+        )
+        .await
+    }
+
+    pub async fn claim_overdue(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MilestoneReminder>, SqlxError> {
+        self.claim_reminders(MilestoneReminderKind::Overdue, "{due_at} <= $4", &[0], now)
+            .await
+    }
+}
@@ -1,15 +1,45 @@
+mod ai_review_repository;
 mod bounty_repository;
+mod collaborator_repository;
+mod comment_repository;
+mod follow_repository;
+mod manuscript_check_repository;
+mod milestone_payout_repository;
+mod milestone_reminder_repository;
 mod notification_repository;
 mod prediction_placement_repository;
 mod prediction_repository;
 mod project_repository;
+mod project_update_repository;
+mod report_repository;
+mod review_repository;
+mod search;
+mod search_repository;
+mod similarity_repository;
+mod trending_repository;
 mod user_repository;
 mod util_repository;
+mod webhook_repository;
 
+pub use ai_review_repository::*;
 pub use bounty_repository::*;
+pub use collaborator_repository::*;
+pub use comment_repository::*;
+pub use follow_repository::*;
+pub use manuscript_check_repository::*;
+pub use milestone_payout_repository::*;
+pub use milestone_reminder_repository::*;
 pub use notification_repository::*;
 pub use prediction_placement_repository::*;
 pub use prediction_repository::*;
 pub use project_repository::*;
+pub use project_update_repository::*;
+pub use report_repository::*;
+pub use review_repository::*;
+pub(crate) use search::*;
+pub use search_repository::*;
+pub use similarity_repository::*;
+pub use trending_repository::*;
 pub use user_repository::*;
 pub use util_repository::*;
+pub use webhook_repository::*;
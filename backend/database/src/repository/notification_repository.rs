@@ -1,8 +1,14 @@
 use std::sync::Arc;
 
 use crate::DatabasePool;
-use sqlx::Error as SqlxError;
-use types::models::{CreateNotification, Notification, NotificationCount, NotificationTab};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{Error as SqlxError, PgConnection};
+use types::models::{
+    Announcement, AnnouncementSegment, AnnouncementStatus, CreateNotification, Cursor,
+    Notification, NotificationCount, NotificationTab, NotificationType,
+    MAX_STORED_NOTIFICATION_ACTORS,
+};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -17,7 +23,26 @@ impl NotificationRepository {
         }
     }
 
-    /// Creates a new notification in the database
+    /// Gets the preferred language of each user, used to localize notifications
+    pub async fn get_user_languages(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, String)>, SqlxError> {
+        let languages = sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, COALESCE(language, 'English') FROM users WHERE id = ANY($1)",
+        )
+        .bind(user_ids)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(languages)
+    }
+
+    /// Creates a new notification in the database. When a collapse key is set
+    /// and the user already has an unread notification with the same key, the
+    /// actor is moved to the front of that notification's actors and counted
+    /// unless already listed. The merged row takes a new id so it is pushed
+    /// again and sorts as the newest entry. It keeps its text, see
+    /// `set_notification_text`.
     pub async fn create_notification(
         &self,
         notification: CreateNotification,
@@ -25,9 +50,19 @@ impl NotificationRepository {
         let notification_type: i32 = notification.notification_type.into();
         let notification = sqlx::query_as::<_, Notification>(
             "
-            INSERT INTO notifications (user_id, notification_type, title, message, data)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
+            INSERT INTO notifications (user_id, notification_type, title, message, data, collapse_key, actors)
+            VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $7::text IS NULL THEN '{}'::text[] ELSE ARRAY[$7::text] END)
+            ON CONFLICT (user_id, collapse_key) WHERE collapse_key IS NOT NULL AND is_read = false
+            DO UPDATE SET
+                id = nextval(pg_get_serial_sequence('notifications', 'id')),
+                notification_type = EXCLUDED.notification_type,
+                data = EXCLUDED.data,
+                group_count = CASE WHEN $7::text IS NOT NULL AND $7::text = ANY(notifications.actors)
+                    THEN notifications.group_count ELSE notifications.group_count + 1 END,
+                actors = (EXCLUDED.actors || array_remove(notifications.actors, $7::text))[1:$8],
+                created_at = now(),
+                updated_at = now()
+            RETURNING id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at
             ",
         )
         .bind(notification.user_id)
@@ -35,11 +70,36 @@ impl NotificationRepository {
         .bind(notification.title)
         .bind(notification.message)
         .bind(notification.data)
+        .bind(notification.collapse_key)
+        .bind(notification.actor)
+        .bind(MAX_STORED_NOTIFICATION_ACTORS)
         .fetch_one(self.db_conn.get_pool())
         .await?;
         Ok(notification)
     }
 
+    /// Sets the text rendered for `group_count` on a merged notification. Left
+    /// alone when another notification merged in meanwhile, that one sets its
+    /// own text.
+    pub async fn set_notification_text(
+        &self,
+        id: i64,
+        group_count: i32,
+        title: &str,
+        message: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE notifications SET title = $1, message = $2 WHERE id = $3 AND group_count = $4",
+        )
+        .bind(title)
+        .bind(message)
+        .bind(id)
+        .bind(group_count)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
     /// Gets all notifications for a user with pagination
     pub async fn get_user_notifications(
         &self,
@@ -49,7 +109,7 @@ impl NotificationRepository {
     ) -> Result<Vec<Notification>, SqlxError> {
         let notifications = sqlx::query_as::<_, Notification>(
             "
-            SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
+            SELECT id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at
             FROM notifications
             WHERE user_id = $1
             ORDER BY created_at DESC
@@ -72,97 +132,58 @@ impl NotificationRepository {
         user_id: Uuid,
         limit: i32,
         offset: i32,
+        after: Option<Cursor<i64>>,
         tab: Option<NotificationTab>,
     ) -> Result<Vec<Notification>, SqlxError> {
         let tab_enum = tab.unwrap_or(NotificationTab::All);
+        // Empty for All and Unread, which don't filter by type
         let notification_types = tab_enum.to_notification_types();
 
-        let notifications = match tab_enum {
-            NotificationTab::All => {
-                // All notifications, no filtering
-                sqlx::query_as::<_, Notification>(
-                    "
-                    SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
-                    FROM notifications
-                    WHERE user_id = $1
-                    ORDER BY created_at DESC
-                    LIMIT $2 OFFSET $3
-                    "
-                )
-                .bind(user_id)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(self.db_conn.get_pool())
-                .await?
-            }
-            NotificationTab::Unread => {
-                // Only unread notifications
-                sqlx::query_as::<_, Notification>(
-                    "
-                    SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
-                    FROM notifications
-                    WHERE user_id = $1 AND is_read = false
-                    ORDER BY created_at DESC
-                    LIMIT $2 OFFSET $3
-                    "
-                )
-                .bind(user_id)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(self.db_conn.get_pool())
-                .await?
-            }
-            _ => {
-                if notification_types.is_empty() {
-                    // No specific types to filter, return all
-                    sqlx::query_as::<_, Notification>(
-                        "
-                        SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
-                        FROM notifications
-                        WHERE user_id = $1
-                        ORDER BY created_at DESC
-                        LIMIT $2 OFFSET $3
-                        "
-                    )
-                    .bind(user_id)
-                    .bind(limit)
-                    .bind(offset)
-                    .fetch_all(self.db_conn.get_pool())
-                    .await?
-                } else {
-                    // Build the SQL for filtering by notification_type
-                    // We use a dynamic number of $N parameters for the IN clause
-                    let type_count = notification_types.len();
-                    let placeholders: Vec<String> =
-                        (0..type_count).map(|i| format!("${}", i + 2)).collect();
-
-                    let sql = format!(
-                        "SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
-                         FROM notifications
-                         WHERE user_id = $1 AND notification_type IN ({})
-                         ORDER BY created_at DESC
-                         LIMIT ${} OFFSET ${}",
-                        placeholders.join(", "),
-                        type_count + 2,
-                        type_count + 3
-                    );
-
-                    let mut query = sqlx::query_as::<_, Notification>(&sql).bind(user_id);
-
-                    // Bind notification type parameters
-                    for notification_type in &notification_types {
-                        let type_value: i32 = notification_type.clone().into();
-                        query = query.bind(type_value);
-                    }
-
-                    // Bind limit and offset
-                    query = query.bind(limit).bind(offset);
+        // $1 is the user, $2 and $3 the limit and offset
+        let mut filters = vec!["user_id = $1".to_string()];
+        let mut index = 4;
+        if matches!(tab_enum, NotificationTab::Unread) {
+            filters.push("is_read = false".to_string());
+        }
+        if !notification_types.is_empty() {
+            // We use a dynamic number of $N parameters for the IN clause
+            let placeholders: Vec<String> = (0..notification_types.len())
+                .map(|i| format!("${}", index + i))
+                .collect();
+            filters.push(format!(
+                "notification_type IN ({})",
+                placeholders.join(", ")
+            ));
+            index += notification_types.len();
+        }
+        if after.is_some() {
+            filters.push(format!("(created_at, id) < (${}, ${})", index, index + 1));
+        }
 
-                    query.fetch_all(self.db_conn.get_pool()).await?
-                }
-            }
-        };
+        let sql = format!(
+            "SELECT id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at
+             FROM notifications
+             WHERE {}
+             ORDER BY created_at DESC, id DESC
+             LIMIT $2 OFFSET $3",
+            filters.join(" AND ")
+        );
+
+        let mut query = sqlx::query_as::<_, Notification>(&sql)
+            .bind(user_id)
+            .bind(limit)
+            .bind(offset);
+
+        // Bind notification type parameters
+        for notification_type in &notification_types {
+            let type_value: i32 = notification_type.clone().into();
+            query = query.bind(type_value);
+        }
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
 
+        let notifications = query.fetch_all(self.db_conn.get_pool()).await?;
         Ok(notifications)
     }
 
@@ -172,7 +193,7 @@ impl NotificationRepository {
     ) -> Result<Vec<Notification>, SqlxError> {
         let notifications = sqlx::query_as::<_, Notification>(
             "
-            SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
+            SELECT id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at
             FROM notifications
             WHERE user_id = $1 AND is_read = false
             ORDER BY created_at DESC
@@ -445,7 +466,7 @@ impl NotificationRepository {
     ) -> Result<Vec<Notification>, SqlxError> {
         let notifications = sqlx::query_as::<_, Notification>(
             "
-            SELECT id, user_id, notification_type, title, message, data, is_read, created_at, updated_at
+            SELECT id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at
             FROM notifications
             WHERE id > $1
             ORDER BY id ASC
@@ -459,4 +480,221 @@ impl NotificationRepository {
 
         Ok(notifications)
     }
+
+    pub async fn create_announcement(
+        &self,
+        sender_id: Uuid,
+        title: &str,
+        message: &str,
+        segment: AnnouncementSegment,
+        segment_value: Option<String>,
+        scheduled_at: DateTime<Utc>,
+    ) -> Result<Announcement, SqlxError> {
+        let segment_type: i16 = segment.into();
+        let announcement = sqlx::query_as::<_, Announcement>(
+            "INSERT INTO announcements (sender_id, title, message, segment_type, segment_value, scheduled_at)
+            VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(sender_id)
+        .bind(title)
+        .bind(message)
+        .bind(segment_type)
+        .bind(segment_value)
+        .bind(scheduled_at)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(announcement)
+    }
+
+    pub async fn get_announcements(
+        &self,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<Announcement>, SqlxError> {
+        let announcements = sqlx::query_as::<_, Announcement>(
+            "SELECT * FROM announcements ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(announcements)
+    }
+
+    /// Cancels an announcement that has not started sending yet
+    pub async fn cancel_announcement(&self, id: Uuid) -> Result<Option<Announcement>, SqlxError> {
+        let scheduled: i16 = AnnouncementStatus::Scheduled.into();
+        let cancelled: i16 = AnnouncementStatus::Cancelled.into();
+        let announcement = sqlx::query_as::<_, Announcement>(
+            "UPDATE announcements SET status = $1, updated_at = now() WHERE id = $2 AND status = $3 RETURNING *",
+        )
+        .bind(cancelled)
+        .bind(id)
+        .bind(scheduled)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(announcement)
+    }
+
+    /// Delivers the oldest due scheduled announcement, if any, and returns it.
+    /// Claiming it, notifying its segment and marking it sent happen in one
+    /// transaction, so a failure leaves it scheduled for the next run and
+    /// concurrent senders skip it while it is locked.
+    pub async fn send_next_due_announcement(
+        &self,
+        now: DateTime<Utc>,
+        batch_size: usize,
+    ) -> Result<Option<Announcement>, SqlxError> {
+        let mut tx = self.db_conn.get_pool().begin().await?;
+        let Some(announcement) = sqlx::query_as::<_, Announcement>(
+            "SELECT * FROM announcements WHERE status = $1 AND scheduled_at <= $2
+            ORDER BY scheduled_at LIMIT 1 FOR UPDATE SKIP LOCKED",
+        )
+        .bind(i16::from(AnnouncementStatus::Scheduled))
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+        let segment = AnnouncementSegment::try_from(announcement.segment_type)
+            .map_err(|e| SqlxError::Decode(e.into()))?;
+        let user_ids =
+            Self::get_segment_user_ids(&mut tx, &segment, announcement.segment_value.as_deref())
+                .await?;
+        let notification_type: i32 = NotificationType::SystemMessage.into();
+        let data = json!({
+            "announcement_id": announcement.id,
+            "action": "announcement"
+        });
+        let mut recipient_count = 0;
+        for batch in user_ids.chunks(batch_size) {
+            recipient_count += sqlx::query(
+                "
+                INSERT INTO notifications (user_id, notification_type, title, message, data)
+                SELECT user_id, $2, $3, $4, $5 FROM UNNEST($1::uuid[]) AS user_id
+                ",
+            )
+            .bind(batch)
+            .bind(notification_type)
+            .bind(&announcement.title)
+            .bind(&announcement.message)
+            .bind(&data)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        let announcement = sqlx::query_as::<_, Announcement>(
+            "UPDATE announcements SET status = $1, recipient_count = $2, sent_at = now(), updated_at = now()
+            WHERE id = $3 RETURNING *",
+        )
+        .bind(i16::from(AnnouncementStatus::Sent))
+        .bind(recipient_count as i32)
+        .bind(announcement.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(announcement))
+    }
+
+    /// Resolves the users targeted by an announcement segment
+    async fn get_segment_user_ids(
+        conn: &mut PgConnection,
+        segment: &AnnouncementSegment,
+        segment_value: Option<&str>,
+    ) -> Result<Vec<Uuid>, SqlxError> {
+        let sql = match segment {
+            AnnouncementSegment::All => "SELECT id FROM users ORDER BY id",
+            AnnouncementSegment::Role => "SELECT id FROM users WHERE $1 = ANY(roles) ORDER BY id",
+            AnnouncementSegment::Tier => "SELECT id FROM users WHERE tier = $1 ORDER BY id",
+            AnnouncementSegment::Interest => {
+                "SELECT id FROM users WHERE $1 = ANY(interests)
+                OR EXISTS (SELECT 1 FROM category c WHERE c.id::text = $1 AND c.name = ANY(interests))
+                ORDER BY id"
+            }
+            AnnouncementSegment::ProjectFunders => {
+                "SELECT DISTINCT user_id FROM funding WHERE project_id::text = $1 AND user_id IS NOT NULL ORDER BY user_id"
+            }
+        };
+        let mut query = sqlx::query_scalar::<_, Uuid>(sql);
+        if *segment != AnnouncementSegment::All {
+            query = query.bind(segment_value.unwrap_or_default());
+        }
+        let user_ids = query.fetch_all(conn).await?;
+        Ok(user_ids)
+    }
+
+    /// Moves one batch of expired notifications out of the live table. Rows of
+    /// users who opted out of archived history are deleted, the rest are copied
+    /// to `notifications_archive`. Locked rows are skipped so the batch never
+    /// waits on concurrent writers. Returns (removed, archived).
+    pub async fn archive_expired_notifications(
+        &self,
+        read_before: DateTime<Utc>,
+        unread_before: DateTime<Utc>,
+        batch_size: i64,
+    ) -> Result<(i64, i64), SqlxError> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            "
+            WITH expired AS (
+                SELECT id FROM notifications
+                WHERE (is_read = true AND created_at < $1)
+                    OR (COALESCE(is_read, false) = false AND created_at < $2)
+                ORDER BY id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            ), removed AS (
+                DELETE FROM notifications n USING expired e WHERE n.id = e.id
+                RETURNING n.*
+            ), archived AS (
+                INSERT INTO notifications_archive (id, user_id, notification_type, title, message, data, is_read, collapse_key, group_count, actors, created_at, updated_at)
+                SELECT r.id, r.user_id, r.notification_type, r.title, r.message, r.data, COALESCE(r.is_read, false), r.collapse_key, r.group_count, r.actors, r.created_at, r.updated_at
+                FROM removed r JOIN users u ON u.id = r.user_id
+                WHERE u.archive_notifications = true
+                ON CONFLICT (id) DO NOTHING
+                RETURNING id
+            )
+            SELECT (SELECT COUNT(*) FROM removed), (SELECT COUNT(*) FROM archived)
+            ",
+        )
+        .bind(read_before)
+        .bind(unread_before)
+        .bind(batch_size)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(counts)
+    }
+
+    /// Gets archived notifications for a user with pagination
+    pub async fn get_archived_notifications(
+        &self,
+        user_id: Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<Notification>, SqlxError> {
+        let notifications = sqlx::query_as::<_, Notification>(
+            "
+            SELECT id, user_id, notification_type, title, message, data, COALESCE(is_read, false) AS is_read, collapse_key, group_count, actors, created_at, updated_at
+            FROM notifications_archive
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            ",
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(notifications)
+    }
+
+    /// Removes a user's archived history once they opt out of keeping it
+    pub async fn delete_archived_notifications(&self, user_id: Uuid) -> Result<u64, SqlxError> {
+        let result = sqlx::query("DELETE FROM notifications_archive WHERE user_id = $1")
+            .bind(user_id)
+            .execute(self.db_conn.get_pool())
+            .await?;
+        Ok(result.rows_affected())
+    }
 }
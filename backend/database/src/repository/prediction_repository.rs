@@ -1,7 +1,7 @@
 use crate::pool::DatabasePool;
 use sqlx::{self, Error as SqlxError};
 use std::sync::Arc;
-use types::models::{Prediction, PredictionStatus, User};
+use types::models::{ListingSort, Prediction, PredictionStatus, User};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -109,6 +109,7 @@ impl PredictionRepository {
         category_id: Option<Uuid>,
         user: Option<&User>,
         is_mine: Option<bool>,
+        sort: Option<ListingSort>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<Prediction>, SqlxError> {
@@ -136,10 +137,8 @@ impl PredictionRepository {
         if !filters.is_empty() {
             query = format!("{} WHERE {}", &query, &filters.join(" AND "));
         }
-        query = format!(
-            "{} ORDER BY p.started_at, p.ended_at LIMIT $1 OFFSET $2",
-            &query
-        );
+        let order = sort.map_or("p.started_at, p.ended_at", prediction_order);
+        query = format!("{} ORDER BY {} LIMIT $1 OFFSET $2", &query, order);
         let mut query = sqlx::query_as::<_, Prediction>(&query)
             .bind(limit.unwrap_or(5))
             .bind(offset.unwrap_or(0));
@@ -220,3 +219,16 @@ impl PredictionRepository {
         Ok(row.rows_affected() == 1)
     }
 }
+
+fn prediction_order(sort: ListingSort) -> &'static str {
+    match sort {
+        ListingSort::Updated => "p.updated_at DESC",
+        ListingSort::Newest => "p.created_at DESC",
+        ListingSort::MostFunded => "p.pool_amount DESC, p.started_at",
+        // Markets still open first, by closing date
+        ListingSort::EndingSoonest => "p.ended_at < CURRENT_DATE, p.ended_at, p.started_at",
+        ListingSort::MostViewed => "p.count_view DESC, p.started_at",
+        ListingSort::Trending => "p.trending_score DESC, p.started_at",
+        ListingSort::ClosestToGoal => "p.started_at, p.ended_at",
+    }
+}
@@ -1,14 +1,17 @@
-use crate::pool::DatabasePool;
+use crate::{pool::DatabasePool, repository::SearchSql};
 use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::Value;
 use sqlx::{self, Error as SqlxError};
 use std::sync::Arc;
 use types::{
     dto::ProjectStatusCount,
     models::{
-        CompletedDao, Dao, DaoVote, Funding, Milestone, Prediction, PredictionStatus, Project,
-        ProjectComment, ProjectIds, ProjectItem, TeamMember,
+        CollaboratorStatus, CompletedDao, Cursor, Dao, DaoVote, EditorCandidate, Funding,
+        FundingRefund, ListingSort, Milestone, Prediction, PredictionStatus, Project, ProjectIds,
+        ProjectItem, ProjectReviewStatus, ProjectRevision, ProjectStatusHistory, TeamMember,
+        ViewEntityType, VIEW_DEDUPE_MINUTES,
     },
-    FeedbackStatus, ProjectStatus, UserRoleType,
+    FeedbackStatus, MileStoneStatus, ProjectStatus, UserRoleType,
 };
 use uuid::Uuid;
 
@@ -242,15 +245,6 @@ impl ProjectRepository {
         Ok(true)
     }
 
-    pub async fn submit_project(&self, id: Uuid) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE project SET status = $1 WHERE id = $2")
-            .bind(ProjectStatus::PendingReview.to_i16())
-            .bind(id)
-            .execute(self.db_conn.get_pool())
-            .await?;
-        Ok(row.rows_affected() == 1)
-    }
-
     pub async fn get_project_ids(&self) -> Result<Vec<ProjectIds>, SqlxError> {
         let ids = sqlx::query_as::<_, ProjectIds>("SELECT id, nerd_id FROM project")
             .fetch_all(self.db_conn.get_pool())
@@ -267,19 +261,32 @@ impl ProjectRepository {
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
         is_public: Option<bool>,
+        sort: Option<ListingSort>,
+        after: Option<Cursor>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<ProjectItem>, SqlxError> {
         let mut filters = Vec::new();
         let mut index = 3;
-        let mut query = format!("SELECT p.id, p.nerd_id, p.proposal_id, p.user_id, p.title, p.description, p.cover_photo, p.category, p.status, p.funding_goal, p.duration, p.tags, p.arweave_tx_id, p.funding_amount, p.count_contributors, p.count_view, p.created_at, p.updated_at, p.dao_at, p.started_at FROM project p");
-        if title.as_ref().map_or(false, |s| !s.is_empty()) {
-            filters.push(format!("p.title ILIKE ${index}"));
+        let mut query = "SELECT p.id, p.nerd_id, p.proposal_id, p.user_id, p.title, p.description, p.cover_photo, p.category, p.status, p.funding_goal, p.duration, p.tags, p.arweave_tx_id, p.funding_amount, p.count_contributors, p.count_view, p.created_at, p.updated_at, p.dao_at, p.started_at".to_string();
+        let is_search = title.as_ref().is_some_and(|s| !s.is_empty());
+        if is_search {
+            let search = SearchSql {
+                alias: "p",
+                snippet_column: "description",
+                param: index,
+            };
+            query = format!("{}, {}", &query, search.columns());
+            filters.push(search.filter());
             index += 1;
         }
+        query = format!("{} FROM project p", &query);
         if is_mine.unwrap_or_default() {
             if user_id.is_some() {
-                filters.push(format!("p.user_id = ${index}"));
+                filters.push(format!(
+                    "(p.user_id = ${index} OR EXISTS (SELECT 1 FROM project_collaborators pc WHERE pc.project_id = p.id AND pc.user_id = ${index} AND pc.status = {}))",
+                    i16::from(CollaboratorStatus::Accepted)
+                ));
                 index += 1;
                 if status.is_some() {
                     filters.push(format!("p.status = ${index}"));
@@ -290,6 +297,7 @@ impl ProjectRepository {
             }
         } else {
             if is_public.unwrap_or_default() {
+                filters.push("p.moderation_status = 0".to_string());
                 if status.is_some() {
                     filters.push(format!("p.status = ${index}"));
                     index += 1;
@@ -341,16 +349,30 @@ impl ProjectRepository {
         }
         if category_id.is_some() {
             filters.push(format!("${index} = ANY(p.category)"));
+            index += 1;
+        }
+        if after.is_some() {
+            filters.push(format!(
+                "(p.updated_at, p.id) < (${}, ${})",
+                index,
+                index + 1
+            ));
         }
         if !filters.is_empty() {
             query = format!("{} WHERE {}", &query, &filters.join(" AND "));
         }
-        query = format!("{} ORDER BY p.updated_at DESC LIMIT $1 OFFSET $2", &query);
+        let order = match sort {
+            Some(sort) => project_order(sort),
+            None if is_search => "search_rank DESC, p.updated_at DESC",
+            None => "p.updated_at DESC, p.id DESC",
+        };
+        query = format!("{} ORDER BY {}", &query, order);
+        query = format!("{} LIMIT $1 OFFSET $2", &query);
         let mut query = sqlx::query_as::<_, ProjectItem>(&query)
             .bind(limit.unwrap_or(5))
             .bind(offset.unwrap_or(0));
         if let Some(title) = title.as_ref().filter(|s| !s.is_empty()) {
-            query = query.bind(format!("%{}%", title));
+            query = query.bind(title.trim());
         }
         if is_mine.unwrap_or_default() {
             if let Some(user_id) = user_id {
@@ -383,34 +405,174 @@ impl ProjectRepository {
         if let Some(category_id) = category_id {
             query = query.bind(category_id)
         }
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
         let projects = query.fetch_all(self.db_conn.get_pool()).await?;
         Ok(projects)
     }
 
+    /// Milestone due dates count from here. Keeps an earlier start.
+    pub async fn set_project_started_at(&self, id: Uuid) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project SET started_at = COALESCE(started_at, now()) WHERE id = $1",
+        )
+        .bind(id)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    /// Moves the project from `from` to `to` and records the change in
+    /// `project_status_history`. Returns false when the project is no longer
+    /// in `from`.
     pub async fn update_project_status(
         &self,
         id: Uuid,
-        status: &ProjectStatus,
+        from: &ProjectStatus,
+        to: &ProjectStatus,
+        actor_id: Option<Uuid>,
+        reason: Option<String>,
     ) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE project SET status = $1, updated_at = $2 WHERE id = $3")
-            .bind(status.to_i16())
-            .bind(Utc::now())
-            .bind(id)
-            .execute(self.db_conn.get_pool())
-            .await?;
+        let row = sqlx::query(
+            "WITH updated AS (
+                UPDATE project SET status = $1, updated_at = $2 WHERE id = $3 AND status = $4 RETURNING id
+            )
+            INSERT INTO project_status_history (project_id, actor_id, from_status, to_status, reason)
+            SELECT id, $5, $4, $1, $6 FROM updated",
+        )
+        .bind(to.to_i16())
+        .bind(Utc::now())
+        .bind(id)
+        .bind(from.to_i16())
+        .bind(actor_id)
+        .bind(reason)
+        .execute(self.db_conn.get_pool())
+        .await?;
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn create_project_editor(
+    pub async fn get_project_status_history(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectStatusHistory>, SqlxError> {
+        let history = sqlx::query_as::<_, ProjectStatusHistory>(
+            "SELECT * FROM project_status_history WHERE project_id = $1 ORDER BY created_at",
+        )
+        .bind(project_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(history)
+    }
+
+    /// Stores `snapshot` as the next version of the project
+    pub async fn create_project_revision(
+        &self,
+        project_id: Uuid,
+        from_status: &ProjectStatus,
+        submitted_by: Option<Uuid>,
+        snapshot: &Value,
+    ) -> Result<ProjectRevision, SqlxError> {
+        let revision = sqlx::query_as::<_, ProjectRevision>(
+            "INSERT INTO project_revisions (project_id, version, from_status, submitted_by, snapshot)
+            SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, $4 FROM project_revisions WHERE project_id = $1
+            RETURNING *",
+        )
+        .bind(project_id)
+        .bind(from_status.to_i16())
+        .bind(submitted_by)
+        .bind(snapshot)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(revision)
+    }
+
+    pub async fn get_project_revisions(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectRevision>, SqlxError> {
+        let revisions = sqlx::query_as::<_, ProjectRevision>(
+            "SELECT * FROM project_revisions WHERE project_id = $1 ORDER BY version",
+        )
+        .bind(project_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(revisions)
+    }
+
+    pub async fn get_project_revision(
+        &self,
+        project_id: Uuid,
+        version: i32,
+    ) -> Option<ProjectRevision> {
+        sqlx::query_as::<_, ProjectRevision>(
+            "SELECT * FROM project_revisions WHERE project_id = $1 AND version = $2",
+        )
+        .bind(project_id)
+        .bind(version)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    pub async fn get_status_history(
+        &self,
+        project_id: Option<Uuid>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<ProjectStatusHistory>, SqlxError> {
+        let history = sqlx::query_as::<_, ProjectStatusHistory>(
+            "SELECT * FROM project_status_history WHERE ($1::uuid IS NULL OR project_id = $1)
+            ORDER BY created_at DESC OFFSET $2 LIMIT $3",
+        )
+        .bind(project_id)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(history)
+    }
+
+    pub async fn is_project_editor(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM project_editor WHERE project_id = $1 AND user_id = $2)",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or(false)
+    }
+
+    /// Assigns the editor and moves the project from `from` to `to` in one
+    /// statement, so a status conflict leaves no editor row behind
+    #[allow(clippy::too_many_arguments)]
+    pub async fn assign_project_editor(
         &self,
         id: Uuid,
         nerd_id: &str,
         editor_id: Uuid,
+        from: &ProjectStatus,
+        to: &ProjectStatus,
+        actor_id: Option<Uuid>,
+        reason: Option<String>,
     ) -> Result<bool, SqlxError> {
         let row = sqlx::query(
-            "INSERT INTO project_editor (project_id, nerd_id, user_id) VALUES ($1, $2, $3)",
+            "WITH updated AS (
+                UPDATE project SET status = $1, updated_at = $2 WHERE id = $3 AND status = $4 RETURNING id
+            ), editor AS (
+                INSERT INTO project_editor (project_id, nerd_id, user_id)
+                SELECT id, $7, $8 FROM updated
+            )
+            INSERT INTO project_status_history (project_id, actor_id, from_status, to_status, reason)
+            SELECT id, $5, $4, $1, $6 FROM updated",
         )
+        .bind(to.to_i16())
+        .bind(Utc::now())
         .bind(id)
+        .bind(from.to_i16())
+        .bind(actor_id)
+        .bind(reason)
         .bind(nerd_id)
         .bind(editor_id)
         .execute(self.db_conn.get_pool())
@@ -418,34 +580,106 @@ impl ProjectRepository {
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn update_project_editor(
+    /// Editors not yet assigned to the project, with their category overlap,
+    /// workload, average turnaround and institution conflict with the researcher
+    pub async fn get_editor_candidates(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<EditorCandidate>, SqlxError> {
+        let candidates = sqlx::query_as::<_, EditorCandidate>(
+            "SELECT u.id AS user_id,
+                (SELECT COUNT(*) FROM category c WHERE c.id = ANY(p.category)
+                    AND (c.id::text = ANY(u.interests) OR c.name = ANY(u.interests))) AS category_overlap,
+                (SELECT COUNT(*) FROM project_editor pe
+                    WHERE pe.user_id = u.id AND COALESCE(pe.status, 0) = 0) AS open_assignments,
+                (SELECT COUNT(*) FROM project_editor pe
+                    WHERE pe.user_id = u.id AND COALESCE(pe.status, 0) <> 0) AS completed_assignments,
+                (SELECT (AVG(EXTRACT(EPOCH FROM pe.updated_at - pe.created_at)) / 3600)::float8
+                    FROM project_editor pe
+                    WHERE pe.user_id = u.id AND COALESCE(pe.status, 0) <> 0) AS avg_turnaround_hours,
+                COALESCE(NULLIF(TRIM(u.institution), '') IS NOT NULL
+                    AND LOWER(TRIM(u.institution)) = LOWER(TRIM(o.institution)), false) AS has_conflict
+            FROM project p
+            JOIN users o ON o.id = p.user_id
+            JOIN users u ON $2 = ANY(u.roles) AND u.id <> p.user_id
+            WHERE p.id = $1
+                AND NOT EXISTS (SELECT 1 FROM project_editor pe WHERE pe.project_id = p.id AND pe.user_id = u.id)",
+        )
+        .bind(project_id)
+        .bind(UserRoleType::Editor.to_string())
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(candidates)
+    }
+
+    /// Stores the decision of an assigned editor and moves the project from
+    /// `from` to `to`, recording the change in `project_status_history`.
+    /// Reviewer invitations still pending are cancelled with it. Nothing is
+    /// written unless the project is still in `from` and `editor_id` is
+    /// assigned to it.
+    pub async fn decide_editor(
         &self,
         id: Uuid,
         editor_id: Uuid,
         status: &FeedbackStatus,
         feedback: Option<String>,
+        from: &ProjectStatus,
+        to: &ProjectStatus,
     ) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE project_editor SET status = $1, feedback = $2, updated_at = $3 WHERE project_id = $4 AND user_id = $5")
-            .bind(status.to_i16())
-            .bind(feedback)
-            .bind(Utc::now())
-            .bind(id)
-            .bind(editor_id)
-            .execute(self.db_conn.get_pool())
-            .await?;
+        let row = sqlx::query(
+            "WITH updated AS (
+                UPDATE project SET status = $1, updated_at = now()
+                WHERE id = $2 AND status = $3
+                    AND EXISTS (SELECT 1 FROM project_editor WHERE project_id = $2 AND user_id = $4)
+                RETURNING id
+            ), editor AS (
+                UPDATE project_editor SET status = $5, feedback = $6, updated_at = now()
+                WHERE project_id IN (SELECT id FROM updated) AND user_id = $4
+            ), reviews AS (
+                UPDATE project_reviews SET status = $7, updated_at = now()
+                WHERE project_id IN (SELECT id FROM updated) AND status = $8
+            )
+            INSERT INTO project_status_history (project_id, actor_id, from_status, to_status, reason)
+            SELECT id, $4, $3, $1, $6 FROM updated",
+        )
+        .bind(to.to_i16())
+        .bind(id)
+        .bind(from.to_i16())
+        .bind(editor_id)
+        .bind(status.to_i16())
+        .bind(feedback)
+        .bind(ProjectReviewStatus::Cancelled)
+        .bind(ProjectReviewStatus::Pending)
+        .execute(self.db_conn.get_pool())
+        .await?;
         Ok(row.rows_affected() == 1)
     }
 
+    /// Applies the admin decision if the project is still in `from`, recording
+    /// the change in `project_status_history` with the feedback as reason and
+    /// cancelling reviewer invitations still pending.
     pub async fn decide_admin(
         &self,
         id: Uuid,
+        from: &ProjectStatus,
         status: &ProjectStatus,
         feedback: Option<String>,
         dao_at: Option<DateTime<Utc>>,
         started_at: Option<DateTime<Utc>>,
+        admin_id: Uuid,
     ) -> Result<Project, SqlxError> {
         let project = sqlx::query_as::<_, Project>(
-            "UPDATE project SET status = $1, feedback = $2, updated_at = $3, dao_at = $4, started_at = $5 WHERE id = $6 RETURNING *",
+            "WITH updated AS (
+                UPDATE project SET status = $1, feedback = $2, updated_at = $3, dao_at = $4, started_at = $5
+                WHERE id = $6 AND status = $7 RETURNING *
+            ), history AS (
+                INSERT INTO project_status_history (project_id, actor_id, from_status, to_status, reason)
+                SELECT id, $8, $7, $1, $2 FROM updated
+            ), reviews AS (
+                UPDATE project_reviews SET status = $9, updated_at = now()
+                WHERE project_id IN (SELECT id FROM updated) AND status = $10
+            )
+            SELECT * FROM updated",
         )
         .bind(status.to_i16())
         .bind(feedback)
@@ -453,6 +687,10 @@ impl ProjectRepository {
         .bind(dao_at)
         .bind(started_at)
         .bind(id)
+        .bind(from.to_i16())
+        .bind(admin_id)
+        .bind(ProjectReviewStatus::Cancelled)
+        .bind(ProjectReviewStatus::Pending)
         .fetch_one(self.db_conn.get_pool())
         .await?;
         Ok(project)
@@ -524,56 +762,31 @@ impl ProjectRepository {
         Ok(row.rows_affected() == 1)
     }
 
-    pub async fn get_project_comments(
-        &self,
-        id: Uuid,
-        offset: Option<i32>,
-        limit: Option<i32>,
-    ) -> Result<Vec<ProjectComment>, SqlxError> {
-        let project_comments = sqlx::query_as::<_, ProjectComment>(
-            "SELECT * FROM project_comment WHERE project_id = $1 ORDER BY updated_at LIMIT $2 OFFSET $3",
-        )
-        .bind(id)
-        .bind(limit.unwrap_or(10))
-        .bind(offset.unwrap_or(0))
-        .fetch_all(self.db_conn.get_pool())
-        .await?;
-        Ok(project_comments)
-    }
-
-    pub async fn submit_project_comment(
-        &self,
-        user_id: Uuid,
-        project_id: Uuid,
-        nerd_id: &str,
-        comment: &str,
-    ) -> Result<bool, SqlxError> {
-        let row = sqlx::query("INSERT INTO project_comment (user_id, project_id, nerd_id, comment) VALUES ($1, $2, $3, $4)")
-            .bind(user_id)
-            .bind(project_id)
-            .bind(nerd_id)
-            .bind(comment)
-            .execute(self.db_conn.get_pool())
-            .await?;
-        Ok(row.rows_affected() == 1)
-    }
-
     pub async fn get_daos(
         &self,
         title: Option<String>,
         status: Option<i16>,
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
+        sort: Option<ListingSort>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<Dao>, SqlxError> {
         let mut filters = Vec::new();
         let mut index = 3;
-        let mut query = format!("SELECT d.* FROM dao d");
-        if title.as_ref().map_or(false, |s| !s.is_empty()) {
-            filters.push(format!("d.title ILIKE ${index}"));
+        let mut query = "SELECT d.*".to_string();
+        let is_search = title.as_ref().is_some_and(|s| !s.is_empty());
+        if is_search {
+            let search = SearchSql {
+                alias: "d",
+                snippet_column: "description",
+                param: index,
+            };
+            query = format!("{}, {}", &query, search.columns());
+            filters.push(search.filter());
             index += 1;
         }
+        query = format!("{} FROM dao d", &query);
         if is_mine.unwrap_or_default() {
             if user_id.is_some() {
                 query = format!(
@@ -589,12 +802,18 @@ impl ProjectRepository {
         if !filters.is_empty() {
             query = format!("{} WHERE {}", &query, &filters.join(" AND "));
         }
-        query = format!("{} ORDER BY d.updated_at DESC LIMIT $1 OFFSET $2", &query);
+        let order = match sort {
+            Some(sort) => dao_order(sort),
+            None if is_search => "search_rank DESC, d.updated_at DESC",
+            None => "d.updated_at DESC",
+        };
+        query = format!("{} ORDER BY {}", &query, order);
+        query = format!("{} LIMIT $1 OFFSET $2", &query);
         let mut query = sqlx::query_as::<_, Dao>(&query)
             .bind(limit.unwrap_or(5))
             .bind(offset.unwrap_or(0));
         if let Some(title) = title.as_ref().filter(|s| !s.is_empty()) {
-            query = query.bind(format!("%{}%", title));
+            query = query.bind(title.trim());
         }
         if is_mine.unwrap_or_default() {
             if let Some(user_id) = user_id {
@@ -745,6 +964,84 @@ impl ProjectRepository {
         Ok(row.rows_affected() == 1)
     }
 
+    /// Whether the `Refunded` event at `tx_hash` and `log_index` was applied
+    pub async fn is_refund_recorded(&self, tx_hash: &str, log_index: i64) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM funding_refunds WHERE tx_hash = $1 AND log_index = $2)",
+        )
+        .bind(tx_hash)
+        .bind(log_index)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Spreads a refund of `amount` over the wallet's fundings of the
+    /// project, those of failed milestones first, and takes it off the
+    /// project and milestone totals. A funding refunded in full no longer
+    /// counts as a contribution. Nothing is applied when the event at
+    /// `tx_hash` and `log_index` was already recorded.
+    pub async fn refund_funding(
+        &self,
+        project_id: Uuid,
+        wallet: &str,
+        amount: i32,
+        tx_hash: &str,
+        log_index: i64,
+    ) -> Result<Vec<FundingRefund>, SqlxError> {
+        let refunds = sqlx::query_as::<_, FundingRefund>(
+            "WITH candidates AS (
+                SELECT f.id, f.number, f.amount - f.refunded_amount AS remaining,
+                    SUM(f.amount - f.refunded_amount) OVER (
+                        ORDER BY COALESCE(m.status IN ($6, $7), false) DESC, f.created_at, f.id
+                    ) AS running
+                FROM funding f
+                LEFT JOIN milestone m ON m.project_id = f.project_id AND m.number = f.number
+                WHERE f.project_id = $1 AND f.wallet = $2 AND f.refunded_amount < f.amount
+                    AND NOT EXISTS (SELECT 1 FROM funding_refunds WHERE tx_hash = $4 AND log_index = $5)
+            ),
+            applied AS (
+                SELECT id, number, LEAST(remaining, $3 - (running - remaining))::INT AS amount
+                FROM candidates
+                WHERE running - remaining < $3
+            ),
+            fundings AS (
+                UPDATE funding f SET refunded_amount = f.refunded_amount + a.amount, updated_at = now()
+                FROM applied a
+                WHERE f.id = a.id
+                RETURNING f.number, a.amount, f.refunded_amount >= f.amount AS closed
+            ),
+            milestones AS (
+                UPDATE milestone m SET amount = m.amount - s.amount,
+                    count_contributors = GREATEST(m.count_contributors - s.closed, 0), updated_at = now()
+                FROM (
+                    SELECT number, SUM(amount) AS amount, COUNT(*) FILTER (WHERE closed) AS closed
+                    FROM fundings GROUP BY number
+                ) s
+                WHERE m.project_id = $1 AND m.number = s.number
+            ),
+            projects AS (
+                UPDATE project SET funding_amount = funding_amount - (SELECT COALESCE(SUM(amount), 0) FROM fundings),
+                    count_contributors = GREATEST(count_contributors - (SELECT COUNT(*) FROM fundings WHERE closed), 0),
+                    updated_at = now()
+                WHERE id = $1 AND EXISTS (SELECT 1 FROM fundings)
+            )
+            INSERT INTO funding_refunds (funding_id, project_id, wallet, amount, tx_hash, log_index)
+            SELECT id, $1, $2, amount, $4, $5 FROM applied
+            RETURNING *",
+        )
+        .bind(project_id)
+        .bind(wallet)
+        .bind(amount)
+        .bind(tx_hash)
+        .bind(log_index)
+        .bind(MileStoneStatus::GiveUp.to_i16())
+        .bind(MileStoneStatus::Failed.to_i16())
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(refunds)
+    }
+
     pub async fn create_predictions(
         &self,
         nerd_id: &str,
@@ -802,6 +1099,7 @@ impl ProjectRepository {
             SELECT p.* FROM project p
             WHERE p.id != $1 
             AND p.status = ANY($6)
+            AND p.moderation_status = 0
             AND (
                 -- Check for category overlap
                 EXISTS (
@@ -913,11 +1211,30 @@ impl ProjectRepository {
         Ok(counts)
     }
 
-    pub async fn increment_view_count(&self, id: Uuid) -> Result<bool, SqlxError> {
-        let row = sqlx::query("UPDATE project SET count_view = count_view + 1 WHERE id = $1")
-            .bind(id)
-            .execute(self.db_conn.get_pool())
-            .await?;
+    /// Counts a view unless `viewer` already viewed the project within the last
+    /// `VIEW_DEDUPE_MINUTES`. Views without a viewer always count.
+    pub async fn increment_view_count(
+        &self,
+        id: Uuid,
+        viewer: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "WITH viewed AS (
+                UPDATE project SET count_view = count_view + 1 WHERE id = $1
+                    AND ($3::TEXT IS NULL OR NOT EXISTS (
+                        SELECT 1 FROM view_events
+                        WHERE entity_type = $2 AND entity_id = $1 AND viewer = $3 AND created_at > $4
+                    ))
+                RETURNING id
+            )
+            INSERT INTO view_events (entity_type, entity_id, viewer) SELECT $2, id, $3 FROM viewed",
+        )
+        .bind(id)
+        .bind(ViewEntityType::Project)
+        .bind(viewer)
+        .bind(Utc::now() - Duration::minutes(VIEW_DEDUPE_MINUTES))
+        .execute(self.db_conn.get_pool())
+        .await?;
         Ok(row.rows_affected() == 1)
     }
 
@@ -993,17 +1310,26 @@ impl ProjectRepository {
     pub async fn get_project_funding(
         &self,
         project_id: Uuid,
+        after: Option<Cursor>,
         limit: Option<i32>,
         offset: Option<i32>,
     ) -> Result<Vec<Funding>, SqlxError> {
-        let fundings = sqlx::query_as::<_, Funding>(
-            "SELECT * FROM funding WHERE project_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3",
-        )
-        .bind(project_id)
-        .bind(limit.unwrap_or(5))
-        .bind(offset.unwrap_or(0))
-        .fetch_all(self.db_conn.get_pool())
-        .await?;
+        let keyset = if after.is_some() {
+            " AND (created_at, id) < ($4, $5)"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT * FROM funding WHERE project_id = $1{keyset} ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3"
+        );
+        let mut query = sqlx::query_as::<_, Funding>(&query)
+            .bind(project_id)
+            .bind(limit.unwrap_or(5))
+            .bind(offset.unwrap_or(0));
+        if let Some(after) = after {
+            query = query.bind(after.at).bind(after.id);
+        }
+        let fundings = query.fetch_all(self.db_conn.get_pool()).await?;
         Ok(fundings)
     }
 
@@ -1165,3 +1491,37 @@ impl ProjectRepository {
         Ok(daos)
     }
 }
+
+fn project_order(sort: ListingSort) -> &'static str {
+    match sort {
+        ListingSort::Updated => "p.updated_at DESC, p.id DESC",
+        ListingSort::Newest => "p.created_at DESC",
+        ListingSort::MostFunded => "p.funding_amount DESC, p.updated_at DESC",
+        // Projects that haven't reached their goal first, closest one on top
+        ListingSort::ClosestToGoal => {
+            "p.funding_amount >= COALESCE(p.funding_goal, 0), p.funding_amount::real / NULLIF(p.funding_goal, 0) DESC NULLS LAST, p.updated_at DESC"
+        }
+        // Running projects first, by the end of their duration
+        ListingSort::EndingSoonest => {
+            "p.started_at + p.duration * INTERVAL '1 day' < NOW(), p.started_at + p.duration * INTERVAL '1 day' NULLS LAST, p.updated_at DESC"
+        }
+        ListingSort::MostViewed => "p.count_view DESC, p.updated_at DESC",
+        ListingSort::Trending => "p.trending_score DESC, p.updated_at DESC",
+    }
+}
+
+fn dao_order(sort: ListingSort) -> &'static str {
+    match sort {
+        ListingSort::Updated => "d.updated_at DESC",
+        ListingSort::Newest => "d.created_at DESC",
+        ListingSort::MostFunded => "d.amount_total DESC, d.updated_at DESC",
+        ListingSort::ClosestToGoal => {
+            "d.amount_total::real / NULLIF(d.funding_goal, 0) DESC NULLS LAST, d.updated_at DESC"
+        }
+        // Active votes first, the oldest one closes first
+        ListingSort::EndingSoonest => "d.status <> 0, d.created_at, d.updated_at DESC",
+        // DAOs have no view count; most votes instead
+        ListingSort::MostViewed => "d.count_total DESC, d.updated_at DESC",
+        ListingSort::Trending => "d.trending_score DESC, d.updated_at DESC",
+    }
+}
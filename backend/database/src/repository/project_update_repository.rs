@@ -0,0 +1,181 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError};
+use std::{collections::HashMap, sync::Arc};
+use types::{
+    dto::{CreateProjectUpdateRequest, EditProjectUpdateRequest},
+    models::{ProjectUpdate, ProjectUpdateComment, ProjectUpdateVisibility},
+};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ProjectUpdateRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl ProjectUpdateRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    pub async fn create_update(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        payload: &CreateProjectUpdateRequest,
+    ) -> Result<ProjectUpdate, SqlxError> {
+        let update = sqlx::query_as::<_, ProjectUpdate>(
+            "INSERT INTO project_updates (project_id, user_id, title, content, attachments, visibility)
+            VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(&payload.title)
+        .bind(&payload.content)
+        .bind(payload.attachments.clone().unwrap_or_default())
+        .bind(payload.visibility)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(update)
+    }
+
+    pub async fn edit_update(
+        &self,
+        id: Uuid,
+        payload: &EditProjectUpdateRequest,
+    ) -> Result<ProjectUpdate, SqlxError> {
+        let update = sqlx::query_as::<_, ProjectUpdate>(
+            "UPDATE project_updates SET title = COALESCE($1, title), content = COALESCE($2, content),
+                attachments = COALESCE($3, attachments), visibility = COALESCE($4, visibility),
+                updated_at = now()
+            WHERE id = $5 RETURNING *",
+        )
+        .bind(&payload.title)
+        .bind(&payload.content)
+        .bind(&payload.attachments)
+        .bind(payload.visibility)
+        .bind(id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(update)
+    }
+
+    pub async fn delete_update(&self, id: Uuid) -> Result<bool, SqlxError> {
+        let row = sqlx::query("DELETE FROM project_updates WHERE id = $1")
+            .bind(id)
+            .execute(self.db_conn.get_pool())
+            .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    pub async fn get_update_by_id(&self, id: Uuid) -> Option<ProjectUpdate> {
+        sqlx::query_as::<_, ProjectUpdate>("SELECT * FROM project_updates WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+            .unwrap_or(None)
+    }
+
+    /// Newest first; funders-only updates are left out unless `include_funders_only`
+    pub async fn get_updates(
+        &self,
+        project_id: Uuid,
+        include_funders_only: bool,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<ProjectUpdate>, SqlxError> {
+        let updates = sqlx::query_as::<_, ProjectUpdate>(
+            "SELECT * FROM project_updates WHERE project_id = $1 AND ($2 OR visibility = $3)
+            ORDER BY created_at DESC OFFSET $4 LIMIT $5",
+        )
+        .bind(project_id)
+        .bind(include_funders_only)
+        .bind(ProjectUpdateVisibility::Public)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(updates)
+    }
+
+    pub async fn get_comment_count(&self, update_id: Uuid) -> i64 {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM project_update_comments WHERE update_id = $1",
+        )
+        .bind(update_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Comment counts of several updates; updates without comments are absent
+    pub async fn get_comment_counts(&self, update_ids: &[Uuid]) -> HashMap<Uuid, i64> {
+        sqlx::query_as::<_, (Uuid, i64)>(
+            "SELECT update_id, COUNT(*) FROM project_update_comments
+            WHERE update_id = ANY($1) GROUP BY update_id",
+        )
+        .bind(update_ids)
+        .fetch_all(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+    }
+
+    pub async fn get_comments(
+        &self,
+        update_id: Uuid,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<ProjectUpdateComment>, SqlxError> {
+        let comments = sqlx::query_as::<_, ProjectUpdateComment>(
+            "SELECT * FROM project_update_comments WHERE update_id = $1
+            ORDER BY created_at OFFSET $2 LIMIT $3",
+        )
+        .bind(update_id)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(comments)
+    }
+
+    pub async fn create_comment(
+        &self,
+        update_id: Uuid,
+        user_id: Uuid,
+        comment: &str,
+    ) -> Result<ProjectUpdateComment, SqlxError> {
+        let comment = sqlx::query_as::<_, ProjectUpdateComment>(
+            "INSERT INTO project_update_comments (update_id, user_id, comment) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(update_id)
+        .bind(user_id)
+        .bind(comment)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(comment)
+    }
+
+    pub async fn get_funder_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>, SqlxError> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT DISTINCT user_id FROM funding WHERE project_id = $1 AND user_id IS NOT NULL",
+        )
+        .bind(project_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(ids)
+    }
+
+    pub async fn is_funder(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM funding WHERE project_id = $1 AND user_id = $2)",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,323 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError, PgConnection};
+use std::sync::Arc;
+use types::models::{
+    ModerationAction, ModerationQueueItem, ModerationStatus, Report, ReportReason, ReportStatus,
+    ReportTarget, ReportTargetType,
+};
+use uuid::Uuid;
+
+const TARGET_TYPES: [ReportTargetType; 5] = [
+    ReportTargetType::ProjectComment,
+    ReportTargetType::BountyComment,
+    ReportTargetType::BountyChat,
+    ReportTargetType::Project,
+    ReportTargetType::Bounty,
+];
+
+/// The table of the reported content with its author and preview columns
+fn target_table(target_type: ReportTargetType) -> (&'static str, &'static str, &'static str) {
+    match target_type {
+        ReportTargetType::ProjectComment => ("project_comment", "user_id", "comment"),
+        ReportTargetType::BountyComment => ("bounty_comment", "user_id", "comment"),
+        ReportTargetType::BountyChat => ("bounty_chat", "sender_id", "message"),
+        ReportTargetType::Project => ("project", "user_id", "title"),
+        ReportTargetType::Bounty => ("bounty", "user_id", "title"),
+    }
+}
+
+#[derive(Clone)]
+pub struct ReportRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl ReportRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Deleted comments can't be reported
+    pub async fn get_target(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+    ) -> Option<ReportTarget> {
+        let (table, author, preview) = target_table(target_type);
+        let (recipient, live) = match target_type {
+            ReportTargetType::ProjectComment | ReportTargetType::BountyComment => {
+                ("NULL::UUID", " AND deleted_at IS NULL")
+            }
+            ReportTargetType::BountyChat => ("receiver_id", ""),
+            ReportTargetType::Project | ReportTargetType::Bounty => ("NULL::UUID", ""),
+        };
+        sqlx::query_as::<_, ReportTarget>(&format!(
+            "SELECT id, {author} AS author_id, {recipient} AS recipient_id, {preview} AS preview, moderation_status
+            FROM {table} WHERE id = $1{live}"
+        ))
+        .bind(target_id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    /// Returns `None` when the user has already reported the content
+    pub async fn create_report(
+        &self,
+        reporter_id: Uuid,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        reason: ReportReason,
+        details: Option<String>,
+    ) -> Result<Option<Report>, SqlxError> {
+        let report = sqlx::query_as::<_, Report>(
+            "INSERT INTO reports (reporter_id, target_type, target_id, reason, details)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (reporter_id, target_type, target_id) DO NOTHING
+            RETURNING *",
+        )
+        .bind(reporter_id)
+        .bind(target_type)
+        .bind(target_id)
+        .bind(reason)
+        .bind(details)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(report)
+    }
+
+    /// Hides visible content once it has `threshold` open reports. Returns
+    /// whether it was hidden by this call.
+    pub async fn auto_hide(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        threshold: i64,
+    ) -> Result<bool, SqlxError> {
+        let mut conn = self.db_conn.get_pool().acquire().await?;
+        Self::hide_over_threshold(&mut conn, target_type, target_id, threshold).await
+    }
+
+    async fn hide_over_threshold(
+        conn: &mut PgConnection,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        threshold: i64,
+    ) -> Result<bool, SqlxError> {
+        let (table, _, _) = target_table(target_type);
+        let hidden = sqlx::query_scalar::<_, bool>(&format!(
+            "WITH hidden AS (
+                UPDATE {table} SET moderation_status = $3
+                WHERE id = $2 AND moderation_status = $4
+                    AND (SELECT COUNT(*) FROM reports WHERE target_type = $1 AND target_id = $2 AND status = $5) >= $6
+                RETURNING id
+            ), logged AS (
+                INSERT INTO moderation_actions (target_type, target_id, action)
+                SELECT $1, id, $7 FROM hidden
+            )
+            SELECT EXISTS (SELECT 1 FROM hidden)"
+        ))
+        .bind(target_type)
+        .bind(target_id)
+        .bind(ModerationStatus::Hidden)
+        .bind(ModerationStatus::Visible)
+        .bind(ReportStatus::Open)
+        .bind(threshold)
+        .bind(ModerationAction::AutoHide)
+        .fetch_one(conn)
+        .await?;
+        Ok(hidden)
+    }
+
+    /// Sets the moderation status of the content, logs the action and closes
+    /// its open reports with `report_status`. Returns false when the content
+    /// doesn't exist.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn moderate(
+        &self,
+        admin_id: Uuid,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        moderation_status: ModerationStatus,
+        action: ModerationAction,
+        note: Option<String>,
+        report_status: ReportStatus,
+    ) -> Result<bool, SqlxError> {
+        let (table, _, _) = target_table(target_type);
+        let changed = sqlx::query_scalar::<_, bool>(&format!(
+            "WITH changed AS (
+                UPDATE {table} SET moderation_status = $3 WHERE id = $2 RETURNING id
+            ), logged AS (
+                INSERT INTO moderation_actions (target_type, target_id, admin_id, action, note)
+                SELECT $1, id, $4, $5, $6 FROM changed
+            ), closed AS (
+                UPDATE reports SET status = $7, resolved_at = now(), resolved_by = $4
+                WHERE target_type = $1 AND target_id IN (SELECT id FROM changed) AND status = $8
+            )
+            SELECT EXISTS (SELECT 1 FROM changed)"
+        ))
+        .bind(target_type)
+        .bind(target_id)
+        .bind(moderation_status)
+        .bind(admin_id)
+        .bind(action)
+        .bind(note)
+        .bind(report_status)
+        .bind(ReportStatus::Open)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(changed)
+    }
+
+    /// Reported content grouped by target, most recently reported first.
+    /// Only reports in `status` are counted.
+    pub async fn get_moderation_queue(
+        &self,
+        status: ReportStatus,
+        target_type: Option<ReportTargetType>,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ModerationQueueItem>, SqlxError> {
+        let mut joins = String::new();
+        let mut authors = Vec::new();
+        let mut previews = Vec::new();
+        let mut statuses = Vec::new();
+        for (i, t) in TARGET_TYPES.iter().enumerate() {
+            let (table, author, preview) = target_table(*t);
+            joins.push_str(&format!(
+                " LEFT JOIN {table} t{i} ON r.target_type = {} AND t{i}.id = r.target_id",
+                i16::from(*t)
+            ));
+            authors.push(format!("t{i}.{author}"));
+            previews.push(format!("t{i}.{preview}"));
+            statuses.push(format!("t{i}.moderation_status"));
+        }
+        let filter = if target_type.is_some() {
+            " AND r.target_type = $4"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT r.target_type, r.target_id,
+                COALESCE({}) AS author_id,
+                LEFT(COALESCE({}), 200) AS preview,
+                COALESCE({}) AS moderation_status,
+                COUNT(*) AS count_report,
+                MIN(r.created_at) AS first_reported_at,
+                MAX(r.created_at) AS last_reported_at
+            FROM reports r{joins}
+            WHERE r.status = $1{filter}
+            GROUP BY r.target_type, r.target_id, 3, 4, 5
+            ORDER BY last_reported_at DESC, r.target_id
+            LIMIT $2 OFFSET $3",
+            authors.join(", "),
+            previews.join(", "),
+            statuses.join(", "),
+        );
+        let mut query = sqlx::query_as::<_, ModerationQueueItem>(&query)
+            .bind(status)
+            .bind(limit.unwrap_or(10))
+            .bind(offset.unwrap_or(0));
+        if let Some(target_type) = target_type {
+            query = query.bind(target_type);
+        }
+        let items = query.fetch_all(self.db_conn.get_pool()).await?;
+        Ok(items)
+    }
+
+    pub async fn get_reports(
+        &self,
+        targets: &[(ReportTargetType, Uuid)],
+        status: ReportStatus,
+    ) -> Result<Vec<Report>, SqlxError> {
+        let (types, ids): (Vec<i16>, Vec<Uuid>) = targets
+            .iter()
+            .map(|(target_type, id)| (i16::from(*target_type), *id))
+            .unzip();
+        let reports = sqlx::query_as::<_, Report>(
+            "SELECT r.* FROM reports r
+            JOIN UNNEST($1::SMALLINT[], $2::UUID[]) AS t(target_type, target_id)
+                ON r.target_type = t.target_type AND r.target_id = t.target_id
+            WHERE r.status = $3
+            ORDER BY r.created_at",
+        )
+        .bind(types)
+        .bind(ids)
+        .bind(status)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+
+    #[tokio::test]
+    async fn hides_at_report_threshold() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let mut conn = PgConnection::connect(&url).await.unwrap();
+        let mut tx = conn.begin().await.unwrap();
+        let user_ids: Vec<Uuid> = sqlx::query_scalar(
+            "INSERT INTO users (email, tier)
+            SELECT 'auto-hide-' || n || '@test.local', 'free' FROM generate_series(0, 2) n
+            RETURNING id",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+        let project_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO project (nerd_id, user_id, title) VALUES ('RP-HIDE-TEST', $1, 'Hide') RETURNING id",
+        )
+        .bind(user_ids[0])
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        let target_type = ReportTargetType::Project;
+        for reporter_id in &user_ids[1..] {
+            let hidden = ReportRepository::hide_over_threshold(&mut tx, target_type, project_id, 2)
+                .await
+                .unwrap();
+            assert!(!hidden);
+            sqlx::query(
+                "INSERT INTO reports (reporter_id, target_type, target_id, reason) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(reporter_id)
+            .bind(target_type)
+            .bind(project_id)
+            .bind(ReportReason::Spam)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        }
+        let hidden = ReportRepository::hide_over_threshold(&mut tx, target_type, project_id, 2)
+            .await
+            .unwrap();
+        assert!(hidden);
+        let status: ModerationStatus =
+            sqlx::query_scalar("SELECT moderation_status FROM project WHERE id = $1")
+                .bind(project_id)
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+        assert_eq!(status, ModerationStatus::Hidden);
+        // Already hidden content is left alone and not logged twice
+        let hidden = ReportRepository::hide_over_threshold(&mut tx, target_type, project_id, 2)
+            .await
+            .unwrap();
+        assert!(!hidden);
+        let actions: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM moderation_actions WHERE target_type = $1 AND target_id = $2 AND action = $3",
+        )
+        .bind(target_type)
+        .bind(project_id)
+        .bind(ModerationAction::AutoHide)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        assert_eq!(actions, 1);
+    }
+}
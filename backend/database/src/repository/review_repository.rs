@@ -0,0 +1,202 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{
+    dto::SubmitProjectReviewRequest,
+    models::{ProjectReview, ProjectReviewReminder, ProjectReviewStatus},
+};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ReviewRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl ReviewRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Invites `reviewer_id`, reopening a previously cancelled invitation.
+    /// Returns `None` when the reviewer already has a pending or submitted review.
+    pub async fn create_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        invited_by: Uuid,
+        due_at: DateTime<Utc>,
+    ) -> Result<Option<ProjectReview>, SqlxError> {
+        let review = sqlx::query_as::<_, ProjectReview>(
+            "INSERT INTO project_reviews (project_id, reviewer_id, invited_by, due_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (project_id, reviewer_id) DO UPDATE SET
+                status = $5, invited_by = $3, due_at = $4, reminded_at = NULL,
+                overdue_notified_at = NULL, updated_at = now()
+            WHERE project_reviews.status = $6
+            RETURNING *",
+        )
+        .bind(project_id)
+        .bind(reviewer_id)
+        .bind(invited_by)
+        .bind(due_at)
+        .bind(ProjectReviewStatus::Pending)
+        .bind(ProjectReviewStatus::Cancelled)
+        .fetch_optional(self.db_conn.get_pool())
+        .await?;
+        Ok(review)
+    }
+
+    pub async fn cancel_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project_reviews SET status = $1, updated_at = now()
+            WHERE project_id = $2 AND reviewer_id = $3 AND status = $4",
+        )
+        .bind(ProjectReviewStatus::Cancelled)
+        .bind(project_id)
+        .bind(reviewer_id)
+        .bind(ProjectReviewStatus::Pending)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    pub async fn submit_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        payload: &SubmitProjectReviewRequest,
+    ) -> Result<bool, SqlxError> {
+        let row = sqlx::query(
+            "UPDATE project_reviews SET status = $1, score_objectives = $2, score_methodology = $3,
+                score_budget = $4, score_expertise = $5, score_innovation = $6, recommendation = $7,
+                comments = $8, confidential_comments = $9, submitted_at = now(), updated_at = now()
+            WHERE project_id = $10 AND reviewer_id = $11 AND status = $12",
+        )
+        .bind(ProjectReviewStatus::Submitted)
+        .bind(payload.score_objectives)
+        .bind(payload.score_methodology)
+        .bind(payload.score_budget)
+        .bind(payload.score_expertise)
+        .bind(payload.score_innovation)
+        .bind(payload.recommendation)
+        .bind(&payload.comments)
+        .bind(&payload.confidential_comments)
+        .bind(project_id)
+        .bind(reviewer_id)
+        .bind(ProjectReviewStatus::Pending)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(row.rows_affected() == 1)
+    }
+
+    pub async fn get_review(&self, project_id: Uuid, reviewer_id: Uuid) -> Option<ProjectReview> {
+        sqlx::query_as::<_, ProjectReview>(
+            "SELECT * FROM project_reviews WHERE project_id = $1 AND reviewer_id = $2",
+        )
+        .bind(project_id)
+        .bind(reviewer_id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    pub async fn get_reviews(&self, project_id: Uuid) -> Result<Vec<ProjectReview>, SqlxError> {
+        let reviews = sqlx::query_as::<_, ProjectReview>(
+            "SELECT * FROM project_reviews WHERE project_id = $1 ORDER BY created_at",
+        )
+        .bind(project_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reviews)
+    }
+
+    pub async fn get_reviews_by_reviewer(
+        &self,
+        reviewer_id: Uuid,
+        status: Option<ProjectReviewStatus>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<ProjectReview>, SqlxError> {
+        let reviews = sqlx::query_as::<_, ProjectReview>(
+            "SELECT * FROM project_reviews WHERE reviewer_id = $1 AND ($2::smallint IS NULL OR status = $2)
+            ORDER BY due_at OFFSET $3 LIMIT $4",
+        )
+        .bind(reviewer_id)
+        .bind(status)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reviews)
+    }
+
+    pub async fn is_handling_editor(&self, project_id: Uuid, user_id: Uuid) -> bool {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM project_editor WHERE project_id = $1 AND user_id = $2)",
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(self.db_conn.get_pool())
+        .await
+        .unwrap_or_default()
+    }
+
+    pub async fn get_handling_editor_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>, SqlxError> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT user_id FROM project_editor WHERE project_id = $1",
+        )
+        .bind(project_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(ids)
+    }
+
+    /// Marks pending reviews due before `remind_until` as reminded and returns them
+    pub async fn claim_review_reminders(
+        &self,
+        now: DateTime<Utc>,
+        remind_until: DateTime<Utc>,
+    ) -> Result<Vec<ProjectReviewReminder>, SqlxError> {
+        let reminders = sqlx::query_as::<_, ProjectReviewReminder>(
+            "UPDATE project_reviews r SET reminded_at = $1
+            FROM users u, project p
+            WHERE r.reviewer_id = u.id AND r.project_id = p.id AND r.status = $3
+                AND r.reminded_at IS NULL AND r.due_at > $1 AND r.due_at <= $2
+            RETURNING r.id, r.project_id, r.reviewer_id, u.name AS reviewer_name,
+                p.title AS project_title, r.due_at",
+        )
+        .bind(now)
+        .bind(remind_until)
+        .bind(ProjectReviewStatus::Pending)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reminders)
+    }
+
+    /// Marks pending reviews past their deadline as notified and returns them
+    pub async fn claim_overdue_reviews(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ProjectReviewReminder>, SqlxError> {
+        let reminders = sqlx::query_as::<_, ProjectReviewReminder>(
+            "UPDATE project_reviews r SET overdue_notified_at = $1
+            FROM users u, project p
+            WHERE r.reviewer_id = u.id AND r.project_id = p.id AND r.status = $2
+                AND r.overdue_notified_at IS NULL AND r.due_at <= $1
+            RETURNING r.id, r.project_id, r.reviewer_id, u.name AS reviewer_name,
+                p.title AS project_title, r.due_at",
+        )
+        .bind(now)
+        .bind(ProjectReviewStatus::Pending)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(reminders)
+    }
+}
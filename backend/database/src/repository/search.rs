@@ -0,0 +1,38 @@
+/// SQL fragments for ranked full-text search over a table with a weighted
+/// `search_vector` column and a trigram index on `title`. `param` is the
+/// placeholder the raw search text is bound to.
+pub(crate) struct SearchSql<'a> {
+    pub alias: &'a str,
+    pub snippet_column: &'a str,
+    pub param: usize,
+}
+
+impl SearchSql<'_> {
+    fn query(&self) -> String {
+        format!("websearch_to_tsquery('english', ${})", self.param)
+    }
+
+    /// Matches on the text search vector, on a typo-tolerant trigram match
+    /// against the title, or on a plain substring of the title
+    pub fn filter(&self) -> String {
+        format!(
+            "({a}.search_vector @@ {q} OR ${p} <% {a}.title OR {a}.title ILIKE '%' || ${p} || '%')",
+            a = self.alias,
+            q = self.query(),
+            p = self.param
+        )
+    }
+
+    /// `search_rank` and `search_snippet` columns, with matches wrapped in `<mark>`
+    pub fn columns(&self) -> String {
+        format!(
+            "ts_rank({a}.search_vector, {q}) + word_similarity(${p}, {a}.title) AS search_rank, \
+            ts_headline('english', COALESCE({a}.{s}, ''), {q}, \
+            'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2') AS search_snippet",
+            a = self.alias,
+            q = self.query(),
+            p = self.param,
+            s = self.snippet_column
+        )
+    }
+}
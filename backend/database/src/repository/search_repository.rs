@@ -0,0 +1,172 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{
+    models::{BountyStatus, SearchResult},
+    ProjectStatus,
+};
+
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxWords=25, MinWords=10, MaxFragments=1";
+
+#[derive(Clone)]
+pub struct SearchRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl SearchRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Runs a `document_query`, binding the raw search text to `$1`, the
+    /// tsquery (or NULL) to `$2`, the limit to `$3` and whether to build
+    /// snippets to `$4`
+    async fn search_documents(
+        &self,
+        query: &str,
+        text: &str,
+        tsquery: Option<&str>,
+        limit: i32,
+        with_snippet: bool,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let results = sqlx::query_as::<_, SearchResult>(query)
+            .bind(text)
+            .bind(tsquery)
+            .bind(limit)
+            .bind(with_snippet)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+        Ok(results)
+    }
+
+    /// Ranked search over a table with a `search_vector` column
+    fn document_query(table: &str, subtitle: &str, image: &str, visible: &str) -> String {
+        format!(
+            "SELECT t.id, COALESCE(t.title, '') AS title, {subtitle} AS subtitle, {image} AS image,
+                CASE WHEN $4 THEN ts_headline('english', COALESCE(t.description, ''), to_tsquery('english', $2), '{HEADLINE_OPTIONS}') END AS snippet,
+                (COALESCE(ts_rank(t.search_vector, to_tsquery('english', $2)), 0) + word_similarity($1, COALESCE(t.title, '')))::real AS rank
+            FROM {table} t
+            WHERE {visible} AND (t.search_vector @@ to_tsquery('english', $2) OR $1 <% t.title OR t.title ILIKE '%' || $1 || '%')
+            ORDER BY rank DESC, t.updated_at DESC LIMIT $3"
+        )
+    }
+
+    pub async fn search_projects(
+        &self,
+        text: &str,
+        tsquery: Option<&str>,
+        limit: i32,
+        with_snippet: bool,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let visible = format!(
+            "t.status IN ({}, {}, {}) AND t.moderation_status = 0",
+            ProjectStatus::DaoVoting.to_i16(),
+            ProjectStatus::Funding.to_i16(),
+            ProjectStatus::Completed.to_i16()
+        );
+        let query = Self::document_query("project", "t.nerd_id", "t.cover_photo", &visible);
+        self.search_documents(&query, text, tsquery, limit, with_snippet)
+            .await
+    }
+
+    pub async fn search_bounties(
+        &self,
+        text: &str,
+        tsquery: Option<&str>,
+        limit: i32,
+        with_snippet: bool,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let visible = format!(
+            "t.status NOT IN ({}, {}, {}) AND t.moderation_status = 0",
+            i16::from(BountyStatus::PendingApproval),
+            i16::from(BountyStatus::Rejected),
+            i16::from(BountyStatus::Cancelled)
+        );
+        let query = Self::document_query("bounty", "t.nerd_id", "t.cover_photo", &visible);
+        self.search_documents(&query, text, tsquery, limit, with_snippet)
+            .await
+    }
+
+    pub async fn search_daos(
+        &self,
+        text: &str,
+        tsquery: Option<&str>,
+        limit: i32,
+        with_snippet: bool,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let query = Self::document_query("dao", "t.nerd_id", "t.cover_photo", "TRUE");
+        self.search_documents(&query, text, tsquery, limit, with_snippet)
+            .await
+    }
+
+    pub async fn search_predictions(
+        &self,
+        text: &str,
+        limit: i32,
+        with_snippet: bool,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let results = sqlx::query_as::<_, SearchResult>(
+            "SELECT p.id, p.title, p.project_title AS subtitle, p.cover_photo AS image,
+                CASE WHEN $3 THEN LEFT(p.description, 200) END AS snippet,
+                GREATEST(word_similarity($1, p.title), word_similarity($1, p.project_title))::real AS rank
+            FROM prediction p
+            WHERE p.title ILIKE '%' || $1 || '%' OR p.project_title ILIKE '%' || $1 || '%'
+                OR $1 <% p.title OR $1 <% p.project_title
+            ORDER BY rank DESC, p.updated_at DESC LIMIT $2",
+        )
+        .bind(text)
+        .bind(limit)
+        .bind(with_snippet)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(results)
+    }
+
+    /// Only users with a public profile. The snippet is their institution.
+    pub async fn search_users(
+        &self,
+        text: &str,
+        limit: i32,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let results = sqlx::query_as::<_, SearchResult>(
+            "SELECT u.id, COALESCE(u.name, u.username) AS title, u.username AS subtitle,
+                u.avatar_url AS image, u.institution AS snippet,
+                (GREATEST(word_similarity($1, COALESCE(u.name, '')), word_similarity($1, u.username))
+                    + CASE WHEN u.username ILIKE $1 || '%' OR u.name ILIKE $1 || '%' THEN 0.5 ELSE 0 END)::real AS rank
+            FROM users u
+            WHERE u.profile_visibility AND u.username IS NOT NULL
+                AND (u.name ILIKE '%' || $1 || '%' OR u.username ILIKE '%' || $1 || '%'
+                    OR $1 <% u.name OR $1 <% u.username)
+            ORDER BY rank DESC, u.created_at DESC LIMIT $2",
+        )
+        .bind(text)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(results)
+    }
+
+    pub async fn search_categories(
+        &self,
+        text: &str,
+        limit: i32,
+    ) -> Result<Vec<SearchResult>, SqlxError> {
+        let results = sqlx::query_as::<_, SearchResult>(
+            "SELECT c.id, COALESCE(c.name, '') AS title, NULL::text AS subtitle,
+                NULL::text AS image, NULL::text AS snippet,
+                (word_similarity($1, COALESCE(c.name, ''))
+                    + CASE WHEN c.name ILIKE $1 || '%' THEN 0.5 ELSE 0 END)::real AS rank
+            FROM category c
+            WHERE c.is_available AND (c.name ILIKE '%' || $1 || '%' OR $1 <% c.name)
+            ORDER BY rank DESC, c.name LIMIT $2",
+        )
+        .bind(text)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(results)
+    }
+}
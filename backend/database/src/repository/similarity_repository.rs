@@ -0,0 +1,196 @@
+use crate::pool::DatabasePool;
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::{
+    models::{
+        FingerprintSource, MinHash, SimilarityEntityType, SimilarityMatchInfo, TextFingerprint,
+    },
+    ProjectStatus,
+};
+use uuid::Uuid;
+
+/// Bounties have no details, their requirements and deliverables stand in
+const BOUNTY_SOURCE_COLUMNS: &str = "id, title, description,
+    array_to_string(requirements || deliverables, E'\\n') AS details";
+
+#[derive(Clone)]
+pub struct SimilarityRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl SimilarityRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    pub async fn get_project_source(&self, id: Uuid) -> Option<FingerprintSource> {
+        sqlx::query_as::<_, FingerprintSource>(
+            "SELECT id, title, description, details FROM project WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    pub async fn get_bounty_source(&self, id: Uuid) -> Option<FingerprintSource> {
+        sqlx::query_as::<_, FingerprintSource>(&format!(
+            "SELECT {} FROM bounty WHERE id = $1",
+            BOUNTY_SOURCE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.db_conn.get_pool())
+        .await
+        .unwrap_or(None)
+    }
+
+    /// Projects that have been submitted at least once, by id after `after`
+    pub async fn get_project_sources(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<FingerprintSource>, SqlxError> {
+        let sources = sqlx::query_as::<_, FingerprintSource>(
+            "SELECT id, title, description, details FROM project
+            WHERE status != $1 AND ($2::UUID IS NULL OR id > $2)
+            ORDER BY id LIMIT $3",
+        )
+        .bind(ProjectStatus::Creating.to_i16())
+        .bind(after)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(sources)
+    }
+
+    /// Bounties by id after `after`
+    pub async fn get_bounty_sources(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<FingerprintSource>, SqlxError> {
+        let sources = sqlx::query_as::<_, FingerprintSource>(&format!(
+            "SELECT {} FROM bounty WHERE ($1::UUID IS NULL OR id > $1) ORDER BY id LIMIT $2",
+            BOUNTY_SOURCE_COLUMNS
+        ))
+        .bind(after)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(sources)
+    }
+
+    /// Projects an editor still has to decide on, by id after `after`
+    pub async fn get_project_ids_in_review(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, SqlxError> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM project
+            WHERE status IN ($1, $2) AND ($3::UUID IS NULL OR id > $3)
+            ORDER BY id LIMIT $4",
+        )
+        .bind(ProjectStatus::PendingReview.to_i16())
+        .bind(ProjectStatus::UnderReview.to_i16())
+        .bind(after)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(ids)
+    }
+
+    pub async fn upsert_fingerprint(
+        &self,
+        entity_type: SimilarityEntityType,
+        entity_id: Uuid,
+        minhash: &MinHash,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO text_fingerprints (entity_type, entity_id, signature, bands)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (entity_type, entity_id)
+            DO UPDATE SET signature = EXCLUDED.signature, bands = EXCLUDED.bands, updated_at = now()",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(&minhash.signature)
+        .bind(minhash.bands())
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Fingerprints sharing at least one band with `minhash`, other than the
+    /// entity's own
+    pub async fn get_candidates(
+        &self,
+        entity_type: SimilarityEntityType,
+        entity_id: Uuid,
+        minhash: &MinHash,
+    ) -> Result<Vec<TextFingerprint>, SqlxError> {
+        let candidates = sqlx::query_as::<_, TextFingerprint>(
+            "SELECT entity_type, entity_id, signature FROM text_fingerprints
+            WHERE bands && $1 AND NOT (entity_type = $2 AND entity_id = $3)",
+        )
+        .bind(minhash.bands())
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(candidates)
+    }
+
+    /// Replaces the stored matches of the project
+    pub async fn replace_matches(
+        &self,
+        project_id: Uuid,
+        matches: &[(SimilarityEntityType, Uuid, f64)],
+    ) -> Result<(), SqlxError> {
+        let matched_types: Vec<i16> = matches.iter().map(|m| i16::from(m.0)).collect();
+        let matched_ids: Vec<Uuid> = matches.iter().map(|m| m.1).collect();
+        let scores: Vec<f64> = matches.iter().map(|m| m.2).collect();
+        sqlx::query(
+            "WITH removed AS (
+                DELETE FROM project_similarity_matches
+                WHERE project_id = $1
+                    AND (matched_type, matched_id) NOT IN (SELECT * FROM UNNEST($2::SMALLINT[], $3::UUID[]))
+            )
+            INSERT INTO project_similarity_matches (project_id, matched_type, matched_id, score)
+            SELECT $1, * FROM UNNEST($2::SMALLINT[], $3::UUID[], $4::FLOAT8[])
+            ON CONFLICT (project_id, matched_type, matched_id) DO UPDATE SET score = EXCLUDED.score",
+        )
+        .bind(project_id)
+        .bind(matched_types)
+        .bind(matched_ids)
+        .bind(scores)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Highest score first. Matches whose project or bounty is gone or
+    /// moderated are left out.
+    pub async fn get_matches(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<SimilarityMatchInfo>, SqlxError> {
+        let matches = sqlx::query_as::<_, SimilarityMatchInfo>(
+            "SELECT m.matched_type, m.matched_id, COALESCE(p.nerd_id, b.nerd_id) AS nerd_id,
+                COALESCE(p.title, b.title) AS title, m.score, m.created_at
+            FROM project_similarity_matches m
+            LEFT JOIN project p ON m.matched_type = $2 AND p.id = m.matched_id
+            LEFT JOIN bounty b ON m.matched_type = $3 AND b.id = m.matched_id
+            WHERE m.project_id = $1 AND COALESCE(p.moderation_status, b.moderation_status) = 0
+            ORDER BY m.score DESC, m.created_at DESC",
+        )
+        .bind(project_id)
+        .bind(SimilarityEntityType::Project)
+        .bind(SimilarityEntityType::Bounty)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(matches)
+    }
+}
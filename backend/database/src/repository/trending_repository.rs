@@ -0,0 +1,137 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::models::ViewEntityType;
+
+/// Weight of each kind of activity in the trending score
+const VIEW_WEIGHT: f64 = 1.0;
+const COMMENT_WEIGHT: f64 = 3.0;
+const BID_WEIGHT: f64 = 5.0;
+const VOTE_WEIGHT: f64 = 5.0;
+const PLACEMENT_WEIGHT: f64 = 5.0;
+const FUNDING_WEIGHT: f64 = 10.0;
+
+#[derive(Clone)]
+pub struct TrendingRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl TrendingRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Sets `trending_score` on every row of `table` from `events`, a query
+    /// returning `(entity_id, weight, created_at)` rows. Each event decays
+    /// exponentially with its age, `$1` is the start of the window and `$2`
+    /// the half life in hours. The exponent is clamped so that old events
+    /// with a short half life round to zero instead of underflowing. Rows
+    /// without recent events, or not matching `visible` (a condition on
+    /// `t2`), drop back to zero.
+    async fn refresh(
+        &self,
+        table: &str,
+        events: &str,
+        visible: &str,
+        since: DateTime<Utc>,
+        half_life_hours: f64,
+    ) -> Result<u64, SqlxError> {
+        let query = format!(
+            "WITH events AS ({events}),
+            scores AS (
+                SELECT entity_id,
+                    SUM(weight * EXP(GREATEST(LN(0.5) * EXTRACT(EPOCH FROM NOW() - created_at) / 3600 / $2, -700))) AS score
+                FROM events WHERE created_at >= $1 GROUP BY entity_id
+            )
+            UPDATE {table} t SET trending_score = COALESCE(s.score, 0)::real
+            FROM {table} t2 LEFT JOIN scores s ON s.entity_id = t2.id AND {visible}
+            WHERE t.id = t2.id AND t.trending_score <> COALESCE(s.score, 0)::real"
+        );
+        let row = sqlx::query(&query)
+            .bind(since)
+            .bind(half_life_hours)
+            .execute(self.db_conn.get_pool())
+            .await?;
+        Ok(row.rows_affected())
+    }
+
+    pub async fn refresh_projects(
+        &self,
+        since: DateTime<Utc>,
+        half_life_hours: f64,
+    ) -> Result<u64, SqlxError> {
+        let events = format!(
+            "SELECT entity_id, {VIEW_WEIGHT}::float8 AS weight, created_at FROM view_events WHERE entity_type = {}
+            UNION ALL SELECT project_id, {FUNDING_WEIGHT}, created_at FROM funding
+            UNION ALL SELECT project_id, {COMMENT_WEIGHT}, created_at FROM project_comment",
+            i16::from(ViewEntityType::Project)
+        );
+        self.refresh(
+            "project",
+            &events,
+            "t2.moderation_status = 0",
+            since,
+            half_life_hours,
+        )
+        .await
+    }
+
+    pub async fn refresh_bounties(
+        &self,
+        since: DateTime<Utc>,
+        half_life_hours: f64,
+    ) -> Result<u64, SqlxError> {
+        let events = format!(
+            "SELECT entity_id, {VIEW_WEIGHT}::float8 AS weight, created_at FROM view_events WHERE entity_type = {}
+            UNION ALL SELECT bounty_id, {BID_WEIGHT}, created_at FROM bid
+            UNION ALL SELECT bounty_id, {COMMENT_WEIGHT}, created_at FROM bounty_comment",
+            i16::from(ViewEntityType::Bounty)
+        );
+        self.refresh(
+            "bounty",
+            &events,
+            "t2.moderation_status = 0",
+            since,
+            half_life_hours,
+        )
+        .await
+    }
+
+    pub async fn refresh_daos(
+        &self,
+        since: DateTime<Utc>,
+        half_life_hours: f64,
+    ) -> Result<u64, SqlxError> {
+        let events = format!(
+            "SELECT dao_id AS entity_id, {VOTE_WEIGHT}::float8 AS weight, created_at FROM dao_vote"
+        );
+        self.refresh("dao", &events, "TRUE", since, half_life_hours)
+            .await
+    }
+
+    pub async fn refresh_predictions(
+        &self,
+        since: DateTime<Utc>,
+        half_life_hours: f64,
+    ) -> Result<u64, SqlxError> {
+        let events = format!(
+            "SELECT p.id AS entity_id, {PLACEMENT_WEIGHT}::float8 AS weight, pp.created_at
+            FROM prediction_placement pp
+            JOIN prediction p ON p.proposal_id = pp.proposal_id AND p.number = pp.milestone_index"
+        );
+        self.refresh("prediction", &events, "TRUE", since, half_life_hours)
+            .await
+    }
+
+    /// Views older than the trending window no longer count towards any score
+    pub async fn prune_view_events(&self, before: DateTime<Utc>) -> Result<u64, SqlxError> {
+        let row = sqlx::query("DELETE FROM view_events WHERE created_at < $1")
+            .bind(before)
+            .execute(self.db_conn.get_pool())
+            .await?;
+        Ok(row.rows_affected())
+    }
+}
@@ -84,6 +84,15 @@ impl UserRepository {
             .unwrap_or(None)
     }
 
+    /// Users among `ids`, in no particular order
+    pub async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>, SqlxError> {
+        let users = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+        Ok(users)
+    }
+
     pub async fn get_user_by_username(&self, username: &str) -> Option<User> {
         sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = $1")
             .bind(username)
@@ -92,6 +101,18 @@ impl UserRepository {
             .unwrap_or(None)
     }
 
+    pub async fn get_user_ids_by_usernames(
+        &self,
+        usernames: &[String],
+    ) -> Result<Vec<Uuid>, SqlxError> {
+        let user_ids =
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE username = ANY($1)")
+                .bind(usernames)
+                .fetch_all(self.db_conn.get_pool())
+                .await?;
+        Ok(user_ids)
+    }
+
     pub async fn get_all_usernames(&self) -> Result<Vec<String>, SqlxError> {
         let usernames = sqlx::query_scalar::<_, String>(
             "SELECT username FROM users WHERE username IS NOT NULL",
@@ -276,6 +297,14 @@ impl UserRepository {
         Ok(users)
     }
 
+    pub async fn get_user_ids_by_role(&self, role: UserRoleType) -> Result<Vec<Uuid>, SqlxError> {
+        let ids = sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE $1 = ANY(roles)")
+            .bind(role.to_string())
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+        Ok(ids)
+    }
+
     pub async fn update_user_onboarding(
         &self,
         id: Uuid,
@@ -478,9 +507,10 @@ impl UserRepository {
         funding_updates: bool,
         dao_proposals: bool,
         prediction_markets: bool,
+        archive_notifications: Option<bool>,
     ) -> Result<User, SqlxError> {
         let user = sqlx::query_as::<_, User>(
-            "UPDATE users SET email_notifications = $1, push_notifications = $2, milestone_updates = $3, funding_updates = $4, dao_proposals = $5, prediction_markets = $6, updated_at = $7 WHERE id = $8 RETURNING *"
+            "UPDATE users SET email_notifications = $1, push_notifications = $2, milestone_updates = $3, funding_updates = $4, dao_proposals = $5, prediction_markets = $6, archive_notifications = COALESCE($7, archive_notifications), updated_at = $8 WHERE id = $9 RETURNING *"
         )
         .bind(email_notifications)
         .bind(push_notifications)
@@ -488,6 +518,7 @@ impl UserRepository {
         .bind(funding_updates)
         .bind(dao_proposals)
         .bind(prediction_markets)
+        .bind(archive_notifications)
         .bind(Utc::now())
         .bind(id)
         .fetch_one(self.db_conn.get_pool())
@@ -0,0 +1,267 @@
+use crate::pool::DatabasePool;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{self, Error as SqlxError};
+use std::sync::Arc;
+use types::models::{
+    WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEvent, WebhookOutboxItem,
+};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WebhookRepository {
+    pub(crate) db_conn: Arc<DatabasePool>,
+}
+
+impl WebhookRepository {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    pub async fn create_endpoint(
+        &self,
+        user_id: Uuid,
+        url: &str,
+        secret: &str,
+        events: &[i16],
+        is_global: bool,
+        description: Option<String>,
+    ) -> Result<WebhookEndpoint, SqlxError> {
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(
+            "INSERT INTO webhook_endpoints (user_id, url, secret, events, is_global, description)
+            VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(user_id)
+        .bind(url)
+        .bind(secret)
+        .bind(events)
+        .bind(is_global)
+        .bind(description)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(endpoint)
+    }
+
+    pub async fn get_endpoint_by_id(&self, id: Uuid) -> Option<WebhookEndpoint> {
+        sqlx::query_as::<_, WebhookEndpoint>("SELECT * FROM webhook_endpoints WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+            .unwrap_or(None)
+    }
+
+    pub async fn get_endpoints(
+        &self,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<WebhookEndpoint>, SqlxError> {
+        let endpoints = sqlx::query_as::<_, WebhookEndpoint>(
+            "SELECT * FROM webhook_endpoints WHERE ($1::uuid IS NULL OR user_id = $1) ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(endpoints)
+    }
+
+    pub async fn update_endpoint(
+        &self,
+        id: Uuid,
+        url: Option<String>,
+        events: Option<Vec<i16>>,
+        is_active: Option<bool>,
+        description: Option<String>,
+    ) -> Result<WebhookEndpoint, SqlxError> {
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(
+            "UPDATE webhook_endpoints SET
+                url = COALESCE($1, url),
+                events = COALESCE($2, events),
+                is_active = COALESCE($3, is_active),
+                description = COALESCE($4, description),
+                updated_at = now()
+            WHERE id = $5 RETURNING *",
+        )
+        .bind(url)
+        .bind(events)
+        .bind(is_active)
+        .bind(description)
+        .bind(id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(endpoint)
+    }
+
+    pub async fn rotate_endpoint_secret(
+        &self,
+        id: Uuid,
+        secret: &str,
+    ) -> Result<WebhookEndpoint, SqlxError> {
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(
+            "UPDATE webhook_endpoints SET secret = $1, updated_at = now() WHERE id = $2 RETURNING *",
+        )
+        .bind(secret)
+        .bind(id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(endpoint)
+    }
+
+    pub async fn delete_endpoint(&self, id: Uuid) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+            .bind(id)
+            .execute(self.db_conn.get_pool())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queues one delivery per active endpoint subscribed to `event`. Global
+    /// (admin) endpoints receive every matching event; user endpoints only
+    /// receive events about resources owned by one of `owner_ids`.
+    pub async fn enqueue_deliveries(
+        &self,
+        event: WebhookEvent,
+        owner_ids: &[Uuid],
+        payload: &Value,
+    ) -> Result<u64, SqlxError> {
+        let result = sqlx::query(
+            "INSERT INTO webhook_deliveries (endpoint_id, event, payload)
+            SELECT id, $1, $2 FROM webhook_endpoints
+            WHERE is_active = true
+                AND (is_global = true OR user_id = ANY($3))
+                AND (cardinality(events) = 0 OR $1 = ANY(events))",
+        )
+        .bind(event)
+        .bind(payload)
+        .bind(owner_ids)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Claims pending deliveries that are due by pushing their next attempt to
+    /// `lease_until`, so a crashed sender leaves them to be retried later
+    /// instead of losing them.
+    pub async fn claim_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookOutboxItem>, SqlxError> {
+        let items = sqlx::query_as::<_, WebhookOutboxItem>(
+            "UPDATE webhook_deliveries d SET next_attempt_at = $1, updated_at = now()
+            FROM webhook_endpoints e
+            WHERE e.id = d.endpoint_id AND d.id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = $2 AND next_attempt_at <= $3
+                ORDER BY next_attempt_at LIMIT $4 FOR UPDATE SKIP LOCKED
+            )
+            RETURNING d.id, d.endpoint_id, d.event, d.payload, d.attempts, e.url, e.secret",
+        )
+        .bind(lease_until)
+        .bind(WebhookDeliveryStatus::Pending)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(items)
+    }
+
+    pub async fn mark_delivery_succeeded(
+        &self,
+        id: Uuid,
+        status_code: i32,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = $1, attempts = attempts + 1, last_status_code = $2,
+                last_error = NULL, delivered_at = now(), updated_at = now()
+            WHERE id = $3",
+        )
+        .bind(WebhookDeliveryStatus::Succeeded)
+        .bind(status_code)
+        .bind(id)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed attempt. The delivery is retried at `next_attempt_at`
+    /// or marked as failed for good when it is `None`.
+    pub async fn mark_delivery_failed(
+        &self,
+        id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<(), SqlxError> {
+        let status = if next_attempt_at.is_some() {
+            WebhookDeliveryStatus::Pending
+        } else {
+            WebhookDeliveryStatus::Failed
+        };
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = $1, attempts = attempts + 1, last_status_code = $2,
+                last_error = $3, next_attempt_at = COALESCE($4, next_attempt_at), updated_at = now()
+            WHERE id = $5",
+        )
+        .bind(status)
+        .bind(status_code)
+        .bind(error)
+        .bind(next_attempt_at)
+        .bind(id)
+        .execute(self.db_conn.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_delivery_by_id(&self, id: Uuid) -> Option<WebhookDelivery> {
+        sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+            .unwrap_or(None)
+    }
+
+    pub async fn get_deliveries(
+        &self,
+        user_id: Option<Uuid>,
+        endpoint_id: Option<Uuid>,
+        event: Option<i16>,
+        status: Option<i16>,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, SqlxError> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT d.* FROM webhook_deliveries d
+            JOIN webhook_endpoints e ON e.id = d.endpoint_id
+            WHERE ($1::uuid IS NULL OR e.user_id = $1)
+                AND ($2::uuid IS NULL OR d.endpoint_id = $2)
+                AND ($3::smallint IS NULL OR d.event = $3)
+                AND ($4::smallint IS NULL OR d.status = $4)
+            ORDER BY d.created_at DESC
+            OFFSET $5 LIMIT $6",
+        )
+        .bind(user_id)
+        .bind(endpoint_id)
+        .bind(event)
+        .bind(status)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.db_conn.get_pool())
+        .await?;
+        Ok(deliveries)
+    }
+
+    /// Puts a delivery back in the outbox to be sent on the next run
+    pub async fn retry_delivery(&self, id: Uuid) -> Result<WebhookDelivery, SqlxError> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            "UPDATE webhook_deliveries SET status = $1, next_attempt_at = now(), updated_at = now()
+            WHERE id = $2 RETURNING *",
+        )
+        .bind(WebhookDeliveryStatus::Pending)
+        .bind(id)
+        .fetch_one(self.db_conn.get_pool())
+        .await?;
+        Ok(delivery)
+    }
+}
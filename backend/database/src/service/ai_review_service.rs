@@ -0,0 +1,276 @@
+use crate::{pool::DatabasePool, AIReviewRepository, NotificationService, ReviewRepository};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use third_party_api::ai_review::{AIReviewClient, HttpAIReviewClient, REVIEW_TIMEOUT_SECS};
+use types::{
+    dto::{AIReviewRequest, AIReviewResult},
+    error::{ApiError, DbError},
+    models::AIReviewTask,
+};
+use utils::env::Env;
+
+/// Slack on top of the client timeouts before a claimed batch is picked up
+/// by another run
+const AI_REVIEW_LEASE_MARGIN_MINUTES: i64 = 1;
+const AI_REVIEW_MAX_RETRY_DELAY_HOURS: i64 = 6;
+
+/// How long a claimed batch stays invisible to other runs. The reviews run
+/// one after another, so the lease covers every one of them timing out.
+fn lease_duration(batch_size: i64) -> Duration {
+    Duration::seconds(REVIEW_TIMEOUT_SECS as i64 * batch_size.max(1))
+        + Duration::minutes(AI_REVIEW_LEASE_MARGIN_MINUTES)
+}
+
+/// When failed reviews are tried again
+#[derive(Clone, Copy, Debug)]
+struct RetryPolicy {
+    max_attempts: i32,
+    base: Duration,
+}
+
+impl RetryPolicy {
+    /// `base * 2^(attempts - 1)`, capped at a few hours
+    fn delay(&self, attempts: i32) -> Duration {
+        let factor = 2i32.saturating_pow(attempts.saturating_sub(1).max(0) as u32);
+        let max = Duration::hours(AI_REVIEW_MAX_RETRY_DELAY_HOURS);
+        self.base
+            .checked_mul(factor)
+            .map_or(max, |delay| delay.min(max))
+    }
+
+    /// None once `attempts` reached `max_attempts`
+    fn next_attempt_at(&self, attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (attempts < self.max_attempts).then(|| now + self.delay(attempts))
+    }
+}
+
+/// How one attempt at a review ended
+#[derive(Debug, PartialEq)]
+enum ReviewOutcome {
+    Done(AIReviewResult),
+    /// Tried again at `next_attempt_at`, or given up without one
+    Failed {
+        error: String,
+        next_attempt_at: Option<DateTime<Utc>>,
+    },
+}
+
+async fn attempt_review(
+    client: &dyn AIReviewClient,
+    retry: RetryPolicy,
+    task: &AIReviewTask,
+    now: DateTime<Utc>,
+) -> ReviewOutcome {
+    let request = AIReviewRequest {
+        project_id: task.id,
+        title: task.title.clone().unwrap_or_default(),
+        description: task.description.clone().unwrap_or_default(),
+        details: task.details.clone().unwrap_or_default(),
+        manuscript: task.manuscript.clone(),
+        funding_goal: task.funding_goal,
+        duration: task.duration,
+        tags: task.tags.clone(),
+    };
+    match client.review_project(&request).await {
+        Ok(result) => ReviewOutcome::Done(result),
+        Err(e) => ReviewOutcome::Failed {
+            error: e.to_string(),
+            next_attempt_at: retry.next_attempt_at(task.ai_attempts + 1, now),
+        },
+    }
+}
+
+/// Scores submitted projects with the AI backend and fills the `ai_*`
+/// fields of the project
+#[derive(Clone)]
+pub struct AIReviewService {
+    ai_review_repo: AIReviewRepository,
+    review_repo: ReviewRepository,
+    notification: NotificationService,
+    client: Arc<dyn AIReviewClient>,
+    retry: RetryPolicy,
+    batch_size: i64,
+}
+
+impl AIReviewService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env, notification: &NotificationService) -> Self {
+        Self::with_client(
+            db_conn,
+            env,
+            notification,
+            Arc::new(HttpAIReviewClient::new(&env.ai_backend_url)),
+        )
+    }
+
+    pub fn with_client(
+        db_conn: &Arc<DatabasePool>,
+        env: &Env,
+        notification: &NotificationService,
+        client: Arc<dyn AIReviewClient>,
+    ) -> Self {
+        Self {
+            ai_review_repo: AIReviewRepository::new(db_conn),
+            review_repo: ReviewRepository::new(db_conn),
+            notification: notification.clone(),
+            client,
+            retry: RetryPolicy {
+                max_attempts: env.ai_review_max_attempts,
+                base: env.ai_review_retry_base,
+            },
+            batch_size: env.ai_review_batch_size,
+        }
+    }
+
+    /// Reviews the projects that are due and returns how many were attempted
+    pub async fn run_due_reviews(&self) -> Result<usize, ApiError> {
+        let now = Utc::now();
+        let lease_until = now + lease_duration(self.batch_size);
+        let tasks = self
+            .ai_review_repo
+            .claim_due_reviews(now, lease_until, self.batch_size)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let count = tasks.len();
+        for task in tasks {
+            self.review(task, lease_until).await?;
+        }
+        Ok(count)
+    }
+
+    /// Stores the scores and tells the handling editors, or schedules a
+    /// retry until `max_attempts` is reached. Nothing is stored once the
+    /// lease ran out, as another run owns the review by then.
+    async fn review(&self, task: AIReviewTask, lease_until: DateTime<Utc>) -> Result<(), ApiError> {
+        match attempt_review(self.client.as_ref(), self.retry, &task, Utc::now()).await {
+            ReviewOutcome::Done(result) => {
+                let stored = self
+                    .ai_review_repo
+                    .mark_review_done(task.id, lease_until, &result)
+                    .await
+                    .map_err(|e| DbError::Str(e.to_string()))?;
+                if !stored {
+                    println!("Dropping AI review of project {}: lease expired", task.id);
+                    return Ok(());
+                }
+                let editor_ids = self
+                    .review_repo
+                    .get_handling_editor_ids(task.id)
+                    .await
+                    .unwrap_or_default();
+                let _ = self
+                    .notification
+                    .notify_ai_analysis_ready(
+                        editor_ids,
+                        task.id,
+                        &task.title.unwrap_or(task.nerd_id),
+                    )
+                    .await;
+                Ok(())
+            }
+            ReviewOutcome::Failed {
+                error,
+                next_attempt_at,
+            } => {
+                if next_attempt_at.is_none() {
+                    println!("Giving up AI review of project {}: {}", task.id, error);
+                }
+                self.ai_review_repo
+                    .mark_review_failed(task.id, lease_until, &error, next_attempt_at)
+                    .await
+                    .map_err(|e| DbError::Str(e.to_string()))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use third_party_api::ai_review::MockAIReviewClient;
+
+    fn retry() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base: Duration::minutes(5),
+        }
+    }
+
+    fn task(ai_attempts: i32) -> AIReviewTask {
+        AIReviewTask {
+            title: Some("Deep Sea Survey".to_string()),
+            ai_attempts,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn stores_successful_review() {
+        let result = AIReviewResult {
+            analysis: "Solid plan".to_string(),
+            objectives: 80,
+            ..Default::default()
+        };
+        let client = MockAIReviewClient::new(result.clone());
+        let outcome = attempt_review(&client, retry(), &task(0), Utc::now()).await;
+        assert_eq!(outcome, ReviewOutcome::Done(result));
+        assert_eq!(client.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn schedules_retry_after_failure() {
+        let client = MockAIReviewClient::default().failing_first(1);
+        let now = Utc::now();
+        let outcome = attempt_review(&client, retry(), &task(1), now).await;
+        let ReviewOutcome::Failed {
+            error,
+            next_attempt_at,
+        } = outcome
+        else {
+            panic!("expected a failed review");
+        };
+        assert!(error.contains("failed"));
+        assert_eq!(next_attempt_at, Some(now + Duration::minutes(10)));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let client = MockAIReviewClient::default().failing_first(1);
+        let outcome = attempt_review(&client, retry(), &task(2), Utc::now()).await;
+        assert!(matches!(
+            outcome,
+            ReviewOutcome::Failed {
+                next_attempt_at: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn retry_delay_backs_off() {
+        let retry = retry();
+        assert_eq!(retry.delay(0), Duration::minutes(5));
+        assert_eq!(retry.delay(1), Duration::minutes(5));
+        assert_eq!(retry.delay(2), Duration::minutes(10));
+        assert_eq!(retry.delay(4), Duration::minutes(40));
+        assert_eq!(
+            retry.delay(10),
+            Duration::hours(AI_REVIEW_MAX_RETRY_DELAY_HOURS)
+        );
+        assert_eq!(
+            retry.delay(i32::MAX),
+            Duration::hours(AI_REVIEW_MAX_RETRY_DELAY_HOURS)
+        );
+    }
+
+    #[test]
+    fn lease_covers_whole_batch() {
+        for batch_size in [1, 5, 20] {
+            assert!(
+                lease_duration(batch_size)
+                    >= Duration::seconds(REVIEW_TIMEOUT_SECS as i64 * batch_size)
+            );
+        }
+        assert!(lease_duration(0) >= Duration::seconds(REVIEW_TIMEOUT_SECS as i64));
+    }
+}
@@ -1,18 +1,19 @@
-use crate::{pool::DatabasePool, BountyRepository, UserRepository, UtilRepository};
+use crate::{pool::DatabasePool, BountyRepository, UserRepository, UtilRepository, WebhookService};
 use chrono::{Datelike, NaiveDate, Utc};
+use serde_json::json;
 use std::sync::Arc;
 use types::{
     dto::{
         BountyAction, BountyChatBountyInfo, BountyChatUserInfo, BountyCreateRequest,
-        BountyUpdateRequest, ChatNumberInfo, SubmitBidMilestoneWorkRequest, SubmitBidRequest,
-        SubmitBountyWorkRequest,
+        BountyUpdateRequest, ChatNumberInfo, Paginated, SubmitBidMilestoneWorkRequest,
+        SubmitBidRequest, SubmitBountyWorkRequest,
     },
     error::{ApiError, DbError, UserError},
     models::{
         BidInfo, BidMilestoneStatus, BidMilestoneSubmission, BidSmallInfo, BidStatus, Bounty,
-        BountyChatInfo, BountyCommentInfo, BountyDifficulty, BountyInfo,
-        BountyMilestoneSubmissionInfo, BountyReviewType, BountyStatus, BountyWorkSubmissionInfo,
-        User,
+        BountyChatInfo, BountyDifficulty, BountyInfo, BountyMilestoneSubmissionInfo,
+        BountyReviewType, BountyStatus, BountyWorkSubmissionInfo, Cursor, ListingSort, PageRequest,
+        User, WebhookEvent,
     },
 };
 use utils::commons::{generate_random_number, uuid_from_str};
@@ -23,14 +24,16 @@ pub struct BountyService {
     bounty_repo: BountyRepository,
     user_repo: UserRepository,
     util_repo: UtilRepository,
+    webhook: WebhookService,
 }
 
 impl BountyService {
-    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+    pub fn new(db_conn: &Arc<DatabasePool>, webhook: &WebhookService) -> Self {
         Self {
             bounty_repo: BountyRepository::new(db_conn),
             user_repo: UserRepository::new(db_conn),
             util_repo: UtilRepository::new(db_conn),
+            webhook: webhook.clone(),
         }
     }
 
@@ -86,13 +89,27 @@ impl BountyService {
         Ok(bounty.to_info(user.to_info(), categories, milestones, bidsmallinfos))
     }
 
-    pub async fn get_bounty_info_by_id(&self, id: &str) -> Result<BountyInfo, ApiError> {
+    /// Loads the bounty by id or nerd id and counts the view of `viewer`
+    pub async fn get_bounty_info_by_id(
+        &self,
+        id: &str,
+        viewer: Option<&str>,
+    ) -> Result<BountyInfo, ApiError> {
         let bounty = self.get_bounty_by_id_or_nerd_id(id).await?;
         // Increment view count
-        let _ = self.bounty_repo.increment_view_count(bounty.id).await;
+        let _ = self
+            .bounty_repo
+            .increment_view_count(bounty.id, viewer)
+            .await;
         self.bounty_to_info(&bounty).await
     }
 
+    /// Counts the view of `viewer`. Call it once the bounty is known to be
+    /// visible to them, so hidden bounties don't collect views.
+    pub async fn record_view(&self, id: Uuid, viewer: Option<&str>) {
+        let _ = self.bounty_repo.increment_view_count(id, viewer).await;
+    }
+
     pub async fn get_bounty_info_by_id_without_increment(
         &self,
         id: &str,
@@ -240,9 +257,13 @@ impl BountyService {
         role: Option<String>,
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
-        offset: Option<i32>,
+        sort: Option<ListingSort>,
+        page: PageRequest,
         limit: Option<i32>,
-    ) -> Result<Vec<BountyInfo>, ApiError> {
+    ) -> Result<Paginated<BountyInfo>, ApiError> {
+        let is_search = title.as_ref().is_some_and(|s| !s.is_empty());
+        page.ensure_default_order(!is_search && sort.is_none_or(|s| s == ListingSort::Updated))?;
+        let limit = limit.unwrap_or(5);
         let bounties = self
             .bounty_repo
             .get_bounties(
@@ -253,18 +274,21 @@ impl BountyService {
                 role,
                 user_id,
                 is_mine,
-                offset,
-                limit,
+                sort,
+                page.after(),
+                page.offset(),
+                Some(limit),
             )
             .await
             .map_err(|_| DbError::Str("Get bounties failed".to_string()))?;
+        let next_cursor = page.next_cursor(&bounties, limit, |b| Cursor::new(b.updated_at, b.id));
         let mut bounty_infos = Vec::new();
         for bounty in bounties {
             if let Ok(bounty_info) = self.bounty_to_info(&bounty).await {
                 bounty_infos.push(bounty_info);
             }
         }
-        Ok(bounty_infos)
+        Ok(Paginated::new(page, bounty_infos, next_cursor))
     }
 
     pub async fn get_bids(
@@ -390,6 +414,20 @@ impl BountyService {
                 number += 1;
             }
         }
+        let _ = self
+            .webhook
+            .enqueue(
+                WebhookEvent::BountyBidSubmitted,
+                &[bounty.user_id],
+                json!({
+                    "bountyId": bounty.id,
+                    "bountyNerdId": bounty.nerd_id,
+                    "bidId": bid.id,
+                    "bidderId": user.id,
+                    "bidAmount": bid.bid_amount,
+                }),
+            )
+            .await;
         Ok(bid.to_info(user.to_info(), milestones))
     }
 
@@ -446,52 +484,6 @@ impl BountyService {
         Ok(true)
     }
 
-    pub async fn get_bounty_comments(
-        &self,
-        id: &str,
-        offset: Option<i32>,
-        limit: Option<i32>,
-    ) -> Result<Vec<BountyCommentInfo>, ApiError> {
-        let bounty_comments = self
-            .bounty_repo
-            .get_bounty_comments(uuid_from_str(id)?, offset, limit)
-            .await
-            .unwrap_or_default();
-        let mut pc_infos = Vec::new();
-        for pc in bounty_comments {
-            if let Some(user) = self.user_repo.get_user_by_id(pc.user_id).await {
-                pc_infos.push(pc.to_info(user.to_info()));
-            }
-        }
-        Ok(pc_infos)
-    }
-
-    pub async fn submit_bounty_comment(
-        &self,
-        id: &str,
-        user_id: Uuid,
-        comment: &str,
-    ) -> Result<bool, ApiError> {
-        let res = if let Some(bounty) = self.bounty_repo.get_bounty_by_id(uuid_from_str(id)?).await
-        {
-            let comment_result = self
-                .bounty_repo
-                .submit_bounty_comment(user_id, bounty.id, &bounty.nerd_id, comment)
-                .await
-                .unwrap_or_default();
-
-            if comment_result {
-                // Increment comment count
-                let _ = self.bounty_repo.increment_comment_count(bounty.id).await;
-            }
-
-            comment_result
-        } else {
-            false
-        };
-        Ok(res)
-    }
-
     pub async fn review_bounty(
         &self,
         id: &str,
@@ -535,14 +527,17 @@ impl BountyService {
     pub async fn get_bounty_chats(
         &self,
         chat_number: &str,
-        offset: Option<i32>,
+        page: PageRequest,
         limit: Option<i32>,
-    ) -> Result<Vec<BountyChatInfo>, ApiError> {
+    ) -> Result<Paginated<BountyChatInfo>, ApiError> {
+        let limit = limit.unwrap_or(10);
         let bounty_chats = self
             .bounty_repo
-            .get_bounty_chats(chat_number, offset, limit)
+            .get_bounty_chats(chat_number, page.after(), page.offset(), Some(limit))
             .await
             .unwrap_or_default();
+        let next_cursor =
+            page.next_cursor(&bounty_chats, limit, |c| Cursor::new(c.created_at, c.id));
         let mut pc_infos = Vec::new();
         for pc in bounty_chats {
             if let Some(sender) = self.user_repo.get_user_by_id(pc.sender_id).await {
@@ -551,7 +546,7 @@ impl BountyService {
                 }
             }
         }
-        Ok(pc_infos)
+        Ok(Paginated::new(page, pc_infos, next_cursor))
     }
 
     pub async fn send_bounty_chat(
@@ -681,7 +676,7 @@ impl BountyService {
         // Check if this chat number already exists
         let existing_chats = self
             .bounty_repo
-            .get_bounty_chats(&chat_number, None, Some(1))
+            .get_bounty_chats(&chat_number, None, None, Some(1))
             .await
             .unwrap_or_default();
 
@@ -0,0 +1,346 @@
+use crate::{pool::DatabasePool, CommentRepository, UserRepository};
+use std::{collections::HashMap, sync::Arc};
+use types::{
+    dto::Paginated,
+    error::{ApiError, DbError},
+    models::{
+        Comment, CommentEdit, CommentEntityType, CommentInfo, CommentReaction, CommentTarget,
+        Cursor, PageRequest, PostedComment, UserInfo,
+    },
+};
+use utils::commons::{extract_mentions, is_emoji, uuid_from_str};
+use uuid::Uuid;
+
+/// Mentions past this many in one comment don't notify anyone
+const MAX_MENTIONS: usize = 20;
+
+#[derive(Clone)]
+pub struct CommentService {
+    comment_repo: CommentRepository,
+    user_repo: UserRepository,
+}
+
+impl CommentService {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            comment_repo: CommentRepository::new(db_conn),
+            user_repo: UserRepository::new(db_conn),
+        }
+    }
+
+    async fn get_target(
+        &self,
+        entity_type: CommentEntityType,
+        id: &str,
+    ) -> Result<CommentTarget, ApiError> {
+        let target = self
+            .comment_repo
+            .get_target(entity_type, uuid_from_str(id)?)
+            .await
+            .ok_or(DbError::Str(format!("{:?} not found", entity_type)))?;
+        Ok(target)
+    }
+
+    async fn get_comment(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+    ) -> Result<Comment, ApiError> {
+        let comment = self
+            .comment_repo
+            .get_comment_by_id(entity_type, uuid_from_str(comment_id)?)
+            .await
+            .filter(|c| c.deleted_at.is_none())
+            .ok_or(DbError::Str("Comment not found".to_string()))?;
+        Ok(comment)
+    }
+
+    /// Users mentioned in `text` but not in `previous`, leaving out the author
+    async fn new_mentions(&self, text: &str, previous: Option<&str>, author_id: Uuid) -> Vec<Uuid> {
+        let before = previous.map(extract_mentions).unwrap_or_default();
+        let usernames: Vec<String> = extract_mentions(text)
+            .into_iter()
+            .filter(|m| !before.contains(m))
+            .take(MAX_MENTIONS)
+            .collect();
+        if usernames.is_empty() {
+            return Vec::new();
+        }
+        self.user_repo
+            .get_user_ids_by_usernames(&usernames)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| *id != author_id)
+            .collect()
+    }
+
+    async fn to_infos(
+        &self,
+        entity_type: CommentEntityType,
+        comments: Vec<Comment>,
+        viewer_id: Option<Uuid>,
+    ) -> Result<Vec<CommentInfo>, ApiError> {
+        let parent_ids: Vec<Uuid> = comments.iter().map(|c| c.id).collect();
+        let replies = if parent_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.comment_repo
+                .get_replies(entity_type, &parent_ids)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?
+        };
+        let comment_ids: Vec<Uuid> = parent_ids
+            .iter()
+            .copied()
+            .chain(replies.iter().map(|r| r.id))
+            .collect();
+        let mut reactions: HashMap<Uuid, Vec<CommentReaction>> = HashMap::new();
+        if !comment_ids.is_empty() {
+            for reaction in self
+                .comment_repo
+                .get_reactions(entity_type, &comment_ids, viewer_id)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?
+            {
+                reactions
+                    .entry(reaction.comment_id)
+                    .or_default()
+                    .push(reaction);
+            }
+        }
+
+        let user_ids: Vec<Uuid> = comments
+            .iter()
+            .chain(replies.iter())
+            .map(|c| c.user_id)
+            .collect();
+        let users: HashMap<Uuid, UserInfo> = self
+            .user_repo
+            .get_users_by_ids(&user_ids)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .iter()
+            .map(|u| (u.id, u.to_info()))
+            .collect();
+        let mut infos: Vec<CommentInfo> = Vec::with_capacity(comments.len());
+        let mut positions: HashMap<Uuid, usize> = HashMap::new();
+        for comment in comments.iter().chain(replies.iter()) {
+            let Some(user) = users.get(&comment.user_id) else {
+                continue;
+            };
+            let mut info = comment.to_info(user.clone());
+            info.reactions = reactions.remove(&comment.id).unwrap_or_default();
+            match comment.parent_id {
+                Some(parent_id) => {
+                    if let Some(&i) = positions.get(&parent_id) {
+                        infos[i].replies.push(info);
+                    }
+                }
+                None => {
+                    positions.insert(comment.id, infos.len());
+                    infos.push(info);
+                }
+            }
+        }
+        Ok(infos)
+    }
+
+    /// Top level comments with their replies. The first page starts with the
+    /// pinned comments, which the cursor and offset don't count.
+    pub async fn get_comments(
+        &self,
+        entity_type: CommentEntityType,
+        id: &str,
+        viewer_id: Option<Uuid>,
+        page: PageRequest,
+        limit: Option<i32>,
+    ) -> Result<Paginated<CommentInfo>, ApiError> {
+        let entity_id = uuid_from_str(id)?;
+        let limit = limit.unwrap_or(10);
+        let mut comments = self
+            .comment_repo
+            .get_comments(
+                entity_type,
+                entity_id,
+                page.after(),
+                page.offset(),
+                Some(limit),
+            )
+            .await
+            .unwrap_or_default();
+        let next_cursor = page.next_cursor(&comments, limit, |c| Cursor::new(c.created_at, c.id));
+        if page.after().is_none() && page.offset().unwrap_or(0) == 0 {
+            let mut pinned = self
+                .comment_repo
+                .get_pinned_comments(entity_type, entity_id)
+                .await
+                .unwrap_or_default();
+            pinned.append(&mut comments);
+            comments = pinned;
+        }
+        let infos = self.to_infos(entity_type, comments, viewer_id).await?;
+        Ok(Paginated::new(page, infos, next_cursor))
+    }
+
+    pub async fn create_comment(
+        &self,
+        entity_type: CommentEntityType,
+        id: &str,
+        user_id: Uuid,
+        comment: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<PostedComment, ApiError> {
+        let target = self.get_target(entity_type, id).await?;
+        let parent = match parent_id {
+            Some(parent_id) => {
+                let parent = self
+                    .get_comment(entity_type, &parent_id.to_string())
+                    .await?;
+                if parent.entity_id != target.id {
+                    return Err(DbError::Str("Comment not found".to_string()).into());
+                }
+                Some(parent)
+            }
+            None => None,
+        };
+        // Replies stay one level deep
+        let thread_id = parent.as_ref().map(|p| p.parent_id.unwrap_or(p.id));
+        let created = self
+            .comment_repo
+            .create_comment(entity_type, &target, user_id, thread_id, comment)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let mentioned_user_ids = self.new_mentions(comment, None, user_id).await;
+        Ok(PostedComment {
+            comment: created,
+            target,
+            parent_user_id: parent.map(|p| p.user_id).filter(|id| *id != user_id),
+            mentioned_user_ids,
+        })
+    }
+
+    pub async fn edit_comment(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+        user_id: Uuid,
+        comment: &str,
+    ) -> Result<PostedComment, ApiError> {
+        let current = self.get_comment(entity_type, comment_id).await?;
+        if current.user_id != user_id {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        let target = self
+            .get_target(entity_type, &current.entity_id.to_string())
+            .await?;
+        if current.comment == comment {
+            return Ok(PostedComment {
+                comment: current,
+                target,
+                parent_user_id: None,
+                mentioned_user_ids: Vec::new(),
+            });
+        }
+        let edited = self
+            .comment_repo
+            .edit_comment(entity_type, current.id, comment)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let mentioned_user_ids = self
+            .new_mentions(comment, Some(&current.comment), user_id)
+            .await;
+        Ok(PostedComment {
+            comment: edited,
+            target,
+            parent_user_id: None,
+            mentioned_user_ids,
+        })
+    }
+
+    pub async fn delete_comment(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+        user_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let comment = self.get_comment(entity_type, comment_id).await?;
+        if comment.user_id != user_id {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        self.comment_repo
+            .delete_comment(entity_type, comment.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Only the owner of the project or bounty and admins can pin, and only
+    /// top level comments
+    pub async fn pin_comment(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+        is_pinned: bool,
+    ) -> Result<bool, ApiError> {
+        let comment = self.get_comment(entity_type, comment_id).await?;
+        let target = self
+            .get_target(entity_type, &comment.entity_id.to_string())
+            .await?;
+        if target.user_id != user_id && !is_admin {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        if comment.parent_id.is_some() {
+            return Err(DbError::Str("Replies can't be pinned".to_string()).into());
+        }
+        self.comment_repo
+            .set_pinned(entity_type, comment.id, is_pinned)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Adds the reaction or takes it back. Returns whether the user has the
+    /// reaction now.
+    pub async fn toggle_reaction(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+        user_id: Uuid,
+        emoji: &str,
+    ) -> Result<bool, ApiError> {
+        let emoji = emoji.trim();
+        if !is_emoji(emoji) {
+            return Err(DbError::Str("Invalid emoji".to_string()).into());
+        }
+        let comment = self.get_comment(entity_type, comment_id).await?;
+        self.comment_repo
+            .toggle_reaction(entity_type, comment.id, user_id, emoji)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Earlier versions of a comment, visible to its author, admins and the
+    /// owner of the commented item, who moderates its thread
+    pub async fn get_comment_history(
+        &self,
+        entity_type: CommentEntityType,
+        comment_id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<CommentEdit>, ApiError> {
+        let comment = self.get_comment(entity_type, comment_id).await?;
+        if comment.user_id != user_id && !is_admin {
+            let target = self
+                .get_target(entity_type, &comment.entity_id.to_string())
+                .await?;
+            if target.user_id != user_id {
+                return Err(DbError::Str("No permission".to_string()).into());
+            }
+        }
+        self.comment_repo
+            .get_edits(entity_type, comment.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+}
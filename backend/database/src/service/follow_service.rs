@@ -0,0 +1,122 @@
+use crate::{pool::DatabasePool, FollowRepository};
+use std::sync::Arc;
+use types::{
+    dto::GetSavedItemsOption,
+    error::{ApiError, DbError},
+    models::{FollowEntityType, SavedItem},
+};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct FollowService {
+    follow_repo: FollowRepository,
+}
+
+impl FollowService {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            follow_repo: FollowRepository::new(db_conn),
+        }
+    }
+
+    async fn check_entity(
+        &self,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<(), ApiError> {
+        if !self.follow_repo.entity_exists(entity_type, entity_id).await {
+            return Err(DbError::Str(format!("{:?} not found", entity_type)).into());
+        }
+        Ok(())
+    }
+
+    /// Follows the entity, or unfollows it if already followed. Returns
+    /// whether the user follows it now.
+    pub async fn toggle_follow(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        self.check_entity(entity_type, entity_id).await?;
+        self.follow_repo
+            .toggle_follow(user_id, entity_type, entity_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Bookmarks the entity, or removes the bookmark. Returns whether it is
+    /// bookmarked now.
+    pub async fn toggle_bookmark(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        self.check_entity(entity_type, entity_id).await?;
+        self.follow_repo
+            .toggle_bookmark(user_id, entity_type, entity_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_follows(
+        &self,
+        user_id: Uuid,
+        opts: GetSavedItemsOption,
+    ) -> Result<Vec<SavedItem>, ApiError> {
+        self.follow_repo
+            .get_follows(
+                user_id,
+                opts.entity_type,
+                opts.offset.unwrap_or(0),
+                opts.limit.unwrap_or(20).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_bookmarks(
+        &self,
+        user_id: Uuid,
+        opts: GetSavedItemsOption,
+    ) -> Result<Vec<SavedItem>, ApiError> {
+        self.follow_repo
+            .get_bookmarks(
+                user_id,
+                opts.entity_type,
+                opts.offset.unwrap_or(0),
+                opts.limit.unwrap_or(20).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Which of `entity_ids` the user follows and which they bookmarked
+    pub async fn get_saved_ids(
+        &self,
+        user_id: Uuid,
+        entity_type: FollowEntityType,
+        entity_ids: &[Uuid],
+    ) -> (Vec<Uuid>, Vec<Uuid>) {
+        let followed = self
+            .follow_repo
+            .get_followed_ids(user_id, entity_type, entity_ids)
+            .await;
+        let bookmarked = self
+            .follow_repo
+            .get_bookmarked_ids(user_id, entity_type, entity_ids)
+            .await;
+        (followed, bookmarked)
+    }
+
+    pub async fn get_follower_ids(
+        &self,
+        entities: &[(FollowEntityType, Uuid)],
+    ) -> Result<Vec<Uuid>, ApiError> {
+        self.follow_repo
+            .get_follower_ids(entities)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{pool::DatabasePool, ManuscriptCheckRepository, ProjectRepository};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use third_party_api::ai_review::{AIReviewClient, HttpAIReviewClient, ERROR_CHECK_TIMEOUT_SECS};
+use types::{
+    error::{ApiError, DbError},
+    models::{ManuscriptCheck, ManuscriptCheckInfo, Project},
+    Statistics,
+};
+use utils::{commons::uuid_from_str, env::Env};
+use uuid::Uuid;
+
+/// Slack on top of the client timeout before a running check is picked up
+/// by another run
+const ERROR_CHECK_LEASE_MARGIN_MINUTES: i64 = 1;
+/// Checks run one after another, so a run is capped to keep it short
+const ERROR_CHECK_BATCH_SIZE: usize = 5;
+
+/// Runs the AI backend error check on project manuscripts and keeps the
+/// findings
+#[derive(Clone)]
+pub struct ManuscriptCheckService {
+    check_repo: ManuscriptCheckRepository,
+    project_repo: ProjectRepository,
+    client: Arc<dyn AIReviewClient>,
+}
+
+impl ManuscriptCheckService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env) -> Self {
+        Self::with_client(
+            db_conn,
+            Arc::new(HttpAIReviewClient::new(&env.ai_backend_url)),
+        )
+    }
+
+    pub fn with_client(db_conn: &Arc<DatabasePool>, client: Arc<dyn AIReviewClient>) -> Self {
+        Self {
+            check_repo: ManuscriptCheckRepository::new(db_conn),
+            project_repo: ProjectRepository::new(db_conn),
+            client,
+        }
+    }
+
+    /// Admins, the project team and its handling editors
+    async fn get_accessible_project(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Project, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(uuid_from_str(id)?)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        if !is_admin && !self.check_repo.can_access(project.id, user_id).await {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        Ok(project)
+    }
+
+    async fn to_info(&self, check: ManuscriptCheck) -> Result<ManuscriptCheckInfo, ApiError> {
+        let statistics = self
+            .check_repo
+            .get_check_statistics(check.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let findings = self
+            .check_repo
+            .get_findings(check.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(check.to_info(statistics, findings))
+    }
+
+    /// Queues a check of the current manuscript of the project. The AI
+    /// review job runs it.
+    pub async fn run_error_check(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<ManuscriptCheckInfo, ApiError> {
+        let project = self.get_accessible_project(id, user_id, is_admin).await?;
+        let manuscript = project
+            .manuscript
+            .filter(|m| !m.is_empty())
+            .ok_or(DbError::Str("Project has no manuscript".to_string()))?;
+        let check = self
+            .check_repo
+            .queue_check(project.id, user_id, &manuscript)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .ok_or(DbError::Str(
+                "An error check is already running".to_string(),
+            ))?;
+        self.to_info(check).await
+    }
+
+    /// Runs queued checks and returns how many were attempted. A failed
+    /// check is kept with its error.
+    pub async fn run_pending_checks(&self) -> Result<usize, ApiError> {
+        let mut count = 0;
+        while count < ERROR_CHECK_BATCH_SIZE {
+            let now = Utc::now();
+            let lease_until = now
+                + Duration::seconds(ERROR_CHECK_TIMEOUT_SECS as i64)
+                + Duration::minutes(ERROR_CHECK_LEASE_MARGIN_MINUTES);
+            let Some(check) = self
+                .check_repo
+                .claim_next_check(now, lease_until)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?
+            else {
+                break;
+            };
+            count += 1;
+            let stored = match self.client.check_manuscript(&check.manuscript).await {
+                Ok(findings) => self
+                    .check_repo
+                    .complete_check(check.id, lease_until, &findings)
+                    .await
+                    .map(|check| check.is_some()),
+                Err(e) => {
+                    self.check_repo
+                        .fail_check(check.id, lease_until, &e.to_string())
+                        .await
+                }
+            }
+            .map_err(|e| DbError::Str(e.to_string()))?;
+            if !stored {
+                println!("Dropping error check {}: lease expired", check.id);
+            }
+        }
+        Ok(count)
+    }
+
+    /// The latest check of the project, if any
+    pub async fn get_error_check(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Option<ManuscriptCheckInfo>, ApiError> {
+        let project = self.get_accessible_project(id, user_id, is_admin).await?;
+        match self.check_repo.get_latest_check(project.id).await {
+            Some(check) => Ok(Some(self.to_info(check).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_error_statistics(&self) -> Result<Statistics, ApiError> {
+        let (total_papers, total_analyses) = self
+            .check_repo
+            .get_check_counts()
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let error_statistics = self
+            .check_repo
+            .get_error_statistics()
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(Statistics {
+            total_papers,
+            total_analyses,
+            error_statistics,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    pool::DatabasePool, MilestoneReminderRepository, NotificationService, ProjectUpdateRepository,
+    ReviewRepository, UserRepository,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use types::{
+    error::{ApiError, DbError},
+    models::MilestoneReminder,
+    UserRoleType,
+};
+use utils::env::Env;
+use uuid::Uuid;
+
+/// Reminds researchers of upcoming milestone deadlines and tells everyone
+/// involved when proof is overdue
+#[derive(Clone)]
+pub struct MilestoneReminderService {
+    reminder_repo: MilestoneReminderRepository,
+    review_repo: ReviewRepository,
+    update_repo: ProjectUpdateRepository,
+    user_repo: UserRepository,
+    notification: NotificationService,
+    reminder_days: Vec<i32>,
+}
+
+impl MilestoneReminderService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env, notification: &NotificationService) -> Self {
+        Self {
+            reminder_repo: MilestoneReminderRepository::new(db_conn),
+            review_repo: ReviewRepository::new(db_conn),
+            update_repo: ProjectUpdateRepository::new(db_conn),
+            user_repo: UserRepository::new(db_conn),
+            notification: notification.clone(),
+            reminder_days: env.milestone_reminder_days.clone(),
+        }
+    }
+
+    /// Sends the reminders that are due and returns how many milestones were
+    /// due soon and overdue
+    pub async fn send_reminders(&self) -> Result<(usize, usize), ApiError> {
+        let now = Utc::now();
+        let due_soon = self
+            .reminder_repo
+            .claim_due_soon(&self.reminder_days, now)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        for reminder in due_soon.iter() {
+            let _ = self
+                .notification
+                .notify_milestone_due_soon(
+                    reminder.researcher_id,
+                    reminder.project_id,
+                    &reminder.project_name(),
+                    &reminder.milestone_title,
+                    reminder.due_at,
+                )
+                .await;
+        }
+
+        let overdue = self
+            .reminder_repo
+            .claim_overdue(now)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        if !overdue.is_empty() {
+            let admin_ids = self
+                .user_repo
+                .get_user_ids_by_role(UserRoleType::Admin)
+                .await
+                .unwrap_or_default();
+            for reminder in overdue.iter() {
+                let user_ids = self.overdue_audience(reminder, &admin_ids).await;
+                let _ = self
+                    .notification
+                    .notify_milestone_overdue(
+                        user_ids,
+                        reminder.project_id,
+                        &reminder.project_name(),
+                        &reminder.milestone_title,
+                        reminder.due_at,
+                    )
+                    .await;
+            }
+        }
+        Ok((due_soon.len(), overdue.len()))
+    }
+
+    /// The researcher, funders, handling editors and admins
+    async fn overdue_audience(
+        &self,
+        reminder: &MilestoneReminder,
+        admin_ids: &[Uuid],
+    ) -> Vec<Uuid> {
+        let mut user_ids = vec![reminder.researcher_id];
+        user_ids.extend(
+            self.update_repo
+                .get_funder_ids(reminder.project_id)
+                .await
+                .unwrap_or_default(),
+        );
+        user_ids.extend(
+            self.review_repo
+                .get_handling_editor_ids(reminder.project_id)
+                .await
+                .unwrap_or_default(),
+        );
+        user_ids.extend_from_slice(admin_ids);
+        user_ids.sort();
+        user_ids.dedup();
+        user_ids
+    }
+}
@@ -1,48 +1,90 @@
+mod ai_review_service;
 mod bounty_service;
+mod comment_service;
+mod follow_service;
+mod manuscript_check_service;
+mod milestone_reminder_service;
 mod notification_service;
 mod prediction_service;
 mod prediction_placement_service;
 mod project_service;
+mod report_service;
+mod review_service;
+mod search_service;
+mod similarity_service;
 mod token_service;
 mod user_service;
 mod util_service;
+mod webhook_service;
 
+pub use ai_review_service::*;
 pub use bounty_service::*;
+pub use comment_service::*;
+pub use follow_service::*;
+pub use manuscript_check_service::*;
+pub use milestone_reminder_service::*;
 pub use notification_service::*;
 pub use prediction_service::*;
 pub use prediction_placement_service::*;
 pub use project_service::*;
+pub use report_service::*;
+pub use review_service::*;
+pub use search_service::*;
+pub use similarity_service::*;
 pub use token_service::*;
 pub use user_service::*;
 pub use util_service::*;
+pub use webhook_service::*;
 
 use crate::DatabasePool;
 use std::sync::Arc;
-use utils::env::Env;
+use utils::{env::Env, templates::Templates};
 
 #[derive(Clone)]
 pub struct AppService {
+    pub ai_review: AIReviewService,
     pub bounty: BountyService,
+    pub comment: CommentService,
+    pub follow: FollowService,
+    pub manuscript_check: ManuscriptCheckService,
+    pub milestone_reminder: MilestoneReminderService,
     pub notification: NotificationService,
     pub prediction: PredictionService,
     pub prediction_placement: PredictionPlacementService,
     pub project: ProjectService,
+    pub report: ReportService,
+    pub review: ReviewService,
+    pub search: SearchService,
+    pub similarity: SimilarityService,
     pub token: TokenService,
     pub user: UserService,
     pub util: UtilService,
+    pub webhook: WebhookService,
 }
 
 impl AppService {
-    pub fn init(db: &Arc<DatabasePool>, env: &Env) -> Self {
+    pub fn init(db: &Arc<DatabasePool>, env: &Env, templates: &Templates) -> Self {
+        let webhook = WebhookService::new(db, env);
+        let notification = NotificationService::new(db, templates);
         Self {
-            bounty: BountyService::new(db),
-            notification: NotificationService::new(db),
+            ai_review: AIReviewService::new(db, env, &notification),
+            bounty: BountyService::new(db, &webhook),
+            comment: CommentService::new(db),
+            follow: FollowService::new(db),
+            manuscript_check: ManuscriptCheckService::new(db, env),
+            milestone_reminder: MilestoneReminderService::new(db, env, &notification),
+            notification: notification.clone(),
             prediction: PredictionService::new(db),
             prediction_placement: PredictionPlacementService::new(db),
-            project: ProjectService::new(db),
+            project: ProjectService::new(db, env, &webhook),
+            report: ReportService::new(db, env),
+            review: ReviewService::new(db, env, &notification),
+            search: SearchService::new(db),
+            similarity: SimilarityService::new(db),
             token: TokenService::new(env),
             user: UserService::new(db),
             util: UtilService::new(db),
+            webhook,
         }
     }
 }
@@ -1,24 +1,36 @@
-use std::sync::Arc;
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use crate::{DatabasePool, NotificationRepository};
-use serde_json::json;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
 use types::{
+    dto::Paginated,
     error::{ApiError, DbError},
     models::{
-        CreateNotification, Notification, NotificationResponse, NotificationTab, NotificationType,
+        AnnouncementResponse, AnnouncementSegment, BountyStatus, CommentEntityType,
+        CreateNotification, Cursor, Notification, NotificationResponse, NotificationTab,
+        NotificationType, PageRequest,
     },
 };
+use utils::{
+    constants::DEFAULT_LANGUAGE,
+    templates::{RenderedNotification, Templates},
+};
 use uuid::Uuid;
 
+const ANNOUNCEMENT_BATCH_SIZE: usize = 1000;
+
 #[derive(Clone)]
 pub struct NotificationService {
     repository: NotificationRepository,
+    templates: Templates,
 }
 
 impl NotificationService {
-    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+    pub fn new(db_conn: &Arc<DatabasePool>, templates: &Templates) -> Self {
         Self {
             repository: NotificationRepository::new(db_conn),
+            templates: templates.clone(),
         }
     }
 
@@ -56,18 +68,30 @@ impl NotificationService {
         &self,
         user_id: Uuid,
         limit: i32,
-        offset: i32,
+        page: PageRequest<i64>,
         tab: Option<NotificationTab>,
-    ) -> Result<Vec<NotificationResponse>, ApiError> {
+    ) -> Result<Paginated<NotificationResponse>, ApiError> {
         let notifications = self
             .repository
-            .get_user_notifications_by_tab(user_id, limit, offset, tab)
+            .get_user_notifications_by_tab(
+                user_id,
+                limit,
+                page.offset().unwrap_or(0),
+                page.after(),
+                tab,
+            )
             .await
             .map_err(|e| DbError::Str(e.to_string()))?;
-        Ok(notifications
-            .into_iter()
-            .map(NotificationResponse::from)
-            .collect())
+        let next_cursor =
+            page.next_cursor(&notifications, limit, |n| Cursor::new(n.created_at, n.id));
+        Ok(Paginated::new(
+            page,
+            notifications
+                .into_iter()
+                .map(NotificationResponse::from)
+                .collect(),
+            next_cursor,
+        ))
     }
 
     pub async fn get_unread_notifications(
@@ -157,6 +181,90 @@ impl NotificationService {
             .map_err(|e| DbError::Str(e.to_string()))?)
     }
 
+    /// Renders the localized title and message of `notification_type` for each
+    /// recipient's language and stores one notification per recipient.
+    async fn notify(
+        &self,
+        user_ids: Vec<Uuid>,
+        notification_type: NotificationType,
+        data: Value,
+    ) -> Result<(), ApiError> {
+        self.notify_collapsed(user_ids, notification_type, data, None, None)
+            .await
+    }
+
+    /// Like `notify`, but merges into the recipient's unread notification with
+    /// the same collapse key. Templates receive the number of distinct actors
+    /// as `count` so the message can describe the whole group.
+    async fn notify_collapsed(
+        &self,
+        user_ids: Vec<Uuid>,
+        notification_type: NotificationType,
+        data: Value,
+        collapse_key: Option<String>,
+        actor: Option<&str>,
+    ) -> Result<(), ApiError> {
+        let languages: HashMap<Uuid, String> = self
+            .repository
+            .get_user_languages(&user_ids)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .into_iter()
+            .collect();
+        for user_id in user_ids {
+            let language = languages
+                .get(&user_id)
+                .map(String::as_str)
+                .unwrap_or(DEFAULT_LANGUAGE);
+            let rendered = self.render_notification(&notification_type, language, &data, 1)?;
+            let notification = CreateNotification {
+                user_id,
+                notification_type: notification_type.clone(),
+                title: rendered.title,
+                message: rendered.message,
+                data: Some(data.clone()),
+                collapse_key: collapse_key.clone(),
+                actor: actor.map(str::to_string),
+            };
+            let notification = self.create_notification(notification).await?;
+            // Merged into an unread notification, describe the group now that
+            // the upsert returned its size
+            if notification.group_count > 1 {
+                let rendered = self.render_notification(
+                    &notification_type,
+                    language,
+                    &data,
+                    notification.group_count,
+                )?;
+                self.repository
+                    .set_notification_text(
+                        notification.id,
+                        notification.group_count,
+                        &rendered.title,
+                        &rendered.message,
+                    )
+                    .await
+                    .map_err(|e| DbError::Str(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn render_notification(
+        &self,
+        notification_type: &NotificationType,
+        language: &str,
+        data: &Value,
+        count: i32,
+    ) -> Result<RenderedNotification, ApiError> {
+        let mut context = data.clone();
+        context["count"] = json!(count);
+        Ok(self
+            .templates
+            .render_notification(notification_type, language, &context)
+            .map_err(|e| DbError::Str(e.to_string()))?)
+    }
+
     // Helper methods for creating specific types of notifications
     pub async fn notify_editor_invitation(
         &self,
@@ -164,22 +272,16 @@ impl NotificationService {
         project_name: &str,
         admin_name: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::InviteEditor,
-            title: "Editor Invitation".to_string(),
-            message: format!(
-                "{} has invited you to be an editor for project '{}'",
-                admin_name, project_name
-            ),
-            data: Some(json!({
+        self.notify(
+            vec![user_id],
+            NotificationType::InviteEditor,
+            json!({
                 "project_name": project_name,
                 "admin_name": admin_name,
                 "action": "invite_editor"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
     }
 
     pub async fn notify_editor_declined(
@@ -188,22 +290,16 @@ impl NotificationService {
         user_name: &str,
         project_name: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id: admin_id,
-            notification_type: NotificationType::DeclineEditor,
-            title: "Editor Invitation Declined".to_string(),
-            message: format!(
-                "{} has declined the editor invitation for project '{}'",
-                user_name, project_name
-            ),
-            data: Some(json!({
+        self.notify(
+            vec![admin_id],
+            NotificationType::DeclineEditor,
+            json!({
                 "user_name": user_name,
                 "project_name": project_name,
                 "action": "decline_editor"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
     }
 
     pub async fn notify_editor_accepted(
@@ -212,22 +308,208 @@ impl NotificationService {
         user_name: &str,
         project_name: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id: admin_id,
-            notification_type: NotificationType::AcceptEditor,
-            title: "Editor Invitation Accepted".to_string(),
-            message: format!(
-                "{} has accepted the editor invitation for project '{}'",
-                user_name, project_name
-            ),
-            data: Some(json!({
+        self.notify(
+            vec![admin_id],
+            NotificationType::AcceptEditor,
+            json!({
                 "user_name": user_name,
                 "project_name": project_name,
                 "action": "accept_editor"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_review_request(
+        &self,
+        reviewer_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        editor_name: &str,
+        due_at: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![reviewer_id],
+            NotificationType::ReviewRequest,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "editor_name": editor_name,
+                "due_date": due_at.format("%Y-%m-%d").to_string(),
+                "action": "review_request"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_review_reminder(
+        &self,
+        reviewer_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        due_at: DateTime<Utc>,
+        overdue: bool,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![reviewer_id],
+            NotificationType::ReviewReminder,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "due_date": due_at.format("%Y-%m-%d").to_string(),
+                "overdue": overdue,
+                "action": "review_reminder"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_review_overdue(
+        &self,
+        editor_ids: Vec<Uuid>,
+        project_id: Uuid,
+        project_name: &str,
+        reviewer_name: &str,
+        due_at: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            editor_ids,
+            NotificationType::ReviewOverdue,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "reviewer_name": reviewer_name,
+                "due_date": due_at.format("%Y-%m-%d").to_string(),
+                "action": "review_overdue"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_review_submitted(
+        &self,
+        editor_ids: Vec<Uuid>,
+        project_id: Uuid,
+        project_name: &str,
+        reviewer_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            editor_ids,
+            NotificationType::ReviewSubmitted,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "reviewer_name": reviewer_name,
+                "action": "review_submitted"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_ai_analysis_ready(
+        &self,
+        editor_ids: Vec<Uuid>,
+        project_id: Uuid,
+        project_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            editor_ids,
+            NotificationType::AIAnalysisReady,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "action": "ai_analysis_ready"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_milestone_due_soon(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        milestone_name: &str,
+        due_at: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![user_id],
+            NotificationType::MilestoneDueSoon,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "milestone_name": milestone_name,
+                "due_date": due_at.format("%Y-%m-%d").to_string(),
+                "action": "milestone_due_soon"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_milestone_overdue(
+        &self,
+        user_ids: Vec<Uuid>,
+        project_id: Uuid,
+        project_name: &str,
+        milestone_name: &str,
+        due_at: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::MilestoneOverdue,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "milestone_name": milestone_name,
+                "due_date": due_at.format("%Y-%m-%d").to_string(),
+                "action": "milestone_overdue"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_collaborator_invitation(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        user_name: &str,
+        role: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![user_id],
+            NotificationType::CollaboratorInvitation,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "user_name": user_name,
+                "role": role,
+                "action": "collaborator_invitation"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_collaborator_response(
+        &self,
+        owner_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        user_name: &str,
+        accepted: bool,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![owner_id],
+            NotificationType::CollaboratorResponse,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "user_name": user_name,
+                "accepted": accepted,
+                "action": "collaborator_response"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_new_bounty(
@@ -236,24 +518,16 @@ impl NotificationService {
         bounty_title: &str,
         creator_name: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::NewBounty,
-                title: "New Bounty Available".to_string(),
-                message: format!(
-                    "{} has created a new bounty: '{}'",
-                    creator_name, bounty_title
-                ),
-                data: Some(json!({
-                    "bounty_title": bounty_title,
-                    "creator_name": creator_name,
-                    "action": "new_bounty"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::NewBounty,
+            json!({
+                "bounty_title": bounty_title,
+                "creator_name": creator_name,
+                "action": "new_bounty"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_new_dao(
@@ -262,21 +536,16 @@ impl NotificationService {
         dao_name: &str,
         creator_name: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::NewDAO,
-                title: "New DAO Created".to_string(),
-                message: format!("{} has created a new DAO: '{}'", creator_name, dao_name),
-                data: Some(json!({
-                    "dao_name": dao_name,
-                    "creator_name": creator_name,
-                    "action": "new_dao"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::NewDAO,
+            json!({
+                "dao_name": dao_name,
+                "creator_name": creator_name,
+                "action": "new_dao"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_new_prediction(
@@ -285,24 +554,16 @@ impl NotificationService {
         prediction_title: &str,
         creator_name: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::NewPrediction,
-                title: "New Prediction Market".to_string(),
-                message: format!(
-                    "{} has created a new prediction: '{}'",
-                    creator_name, prediction_title
-                ),
-                data: Some(json!({
-                    "prediction_title": prediction_title,
-                    "creator_name": creator_name,
-                    "action": "new_prediction"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::NewPrediction,
+            json!({
+                "prediction_title": prediction_title,
+                "creator_name": creator_name,
+                "action": "new_prediction"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_bid_approved(
@@ -310,18 +571,15 @@ impl NotificationService {
         user_id: Uuid,
         bounty_title: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::ApprovedBid,
-            title: "Bid Approved".to_string(),
-            message: format!("Your bid for '{}' has been approved!", bounty_title),
-            data: Some(json!({
+        self.notify(
+            vec![user_id],
+            NotificationType::ApprovedBid,
+            json!({
                 "bounty_title": bounty_title,
                 "action": "bid_approved"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
     }
 
     pub async fn notify_bid_rejected(
@@ -329,18 +587,15 @@ impl NotificationService {
         user_id: Uuid,
         bounty_title: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::RejectedBid,
-            title: "Bid Rejected".to_string(),
-            message: format!("Your bid for '{}' has been rejected", bounty_title),
-            data: Some(json!({
+        self.notify(
+            vec![user_id],
+            NotificationType::RejectedBid,
+            json!({
                 "bounty_title": bounty_title,
                 "action": "bid_rejected"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
     }
 
     pub async fn notify_bid_reviewed(
@@ -348,18 +603,15 @@ impl NotificationService {
         user_id: Uuid,
         bounty_title: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::BidReviewed,
-            title: "Bid Under Review".to_string(),
-            message: format!("Your bid for '{}' is currently under review", bounty_title),
-            data: Some(json!({
+        self.notify(
+            vec![user_id],
+            NotificationType::BidReviewed,
+            json!({
                 "bounty_title": bounty_title,
                 "action": "bid_reviewed"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+        )
+        .await
     }
 
     pub async fn notify_new_project(
@@ -368,24 +620,16 @@ impl NotificationService {
         project_name: &str,
         creator_name: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::NewProject,
-                title: "New Project Created".to_string(),
-                message: format!(
-                    "{} has created a new project: '{}'",
-                    creator_name, project_name
-                ),
-                data: Some(json!({
-                    "project_name": project_name,
-                    "creator_name": creator_name,
-                    "action": "new_project"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::NewProject,
+            json!({
+                "project_name": project_name,
+                "creator_name": creator_name,
+                "action": "new_project"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_project_milestone(
@@ -394,66 +638,244 @@ impl NotificationService {
         project_name: &str,
         milestone_name: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::ProjectMilestone,
-                title: "Project Milestone Update".to_string(),
-                message: format!(
-                    "Milestone '{}' has been completed for project '{}'",
-                    milestone_name, project_name
-                ),
-                data: Some(json!({
-                    "project_name": project_name,
-                    "milestone_name": milestone_name,
-                    "action": "project_milestone"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::ProjectMilestone,
+            json!({
+                "project_name": project_name,
+                "milestone_name": milestone_name,
+                "action": "project_milestone"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_project_comment(
         &self,
         user_id: Uuid,
+        project_id: Uuid,
         project_name: &str,
         commenter_name: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::ProjectComment,
-            title: "New Project Comment".to_string(),
-            message: format!("{} commented on project '{}'", commenter_name, project_name),
-            data: Some(json!({
+        self.notify_collapsed(
+            vec![user_id],
+            NotificationType::ProjectComment,
+            json!({
+                "project_id": project_id,
                 "project_name": project_name,
                 "commenter_name": commenter_name,
                 "action": "project_comment"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+            Some(format!("project_comment:{}", project_id)),
+            Some(commenter_name),
+        )
+        .await
+    }
+
+    pub async fn notify_project_update(
+        &self,
+        user_ids: Vec<Uuid>,
+        project_id: Uuid,
+        update_id: Uuid,
+        project_name: &str,
+        update_title: &str,
+        user_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::ProjectUpdate,
+            json!({
+                "project_id": project_id,
+                "update_id": update_id,
+                "project_name": project_name,
+                "update_title": update_title,
+                "user_name": user_name,
+                "action": "project_update"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_project_update_comment(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        update_id: Uuid,
+        update_title: &str,
+        commenter_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify_collapsed(
+            vec![user_id],
+            NotificationType::ProjectUpdateComment,
+            json!({
+                "project_id": project_id,
+                "update_id": update_id,
+                "update_title": update_title,
+                "commenter_name": commenter_name,
+                "action": "project_update_comment"
+            }),
+            Some(format!("project_update_comment:{}", update_id)),
+            Some(commenter_name),
+        )
+        .await
     }
 
     pub async fn notify_bounty_comment(
         &self,
         user_id: Uuid,
+        bounty_id: Uuid,
         bounty_title: &str,
         commenter_name: &str,
     ) -> Result<(), ApiError> {
-        let notification = CreateNotification {
-            user_id,
-            notification_type: NotificationType::BountyComment,
-            title: "New Bounty Comment".to_string(),
-            message: format!("{} commented on bounty '{}'", commenter_name, bounty_title),
-            data: Some(json!({
+        self.notify_collapsed(
+            vec![user_id],
+            NotificationType::BountyComment,
+            json!({
+                "bounty_id": bounty_id,
                 "bounty_title": bounty_title,
                 "commenter_name": commenter_name,
                 "action": "bounty_comment"
-            })),
-        };
-        self.create_notification(notification).await?;
-        Ok(())
+            }),
+            Some(format!("bounty_comment:{}", bounty_id)),
+            Some(commenter_name),
+        )
+        .await
+    }
+
+    pub async fn notify_comment_mention(
+        &self,
+        user_ids: Vec<Uuid>,
+        entity_type: CommentEntityType,
+        entity_id: Uuid,
+        entity_title: &str,
+        comment_id: Uuid,
+        commenter_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::CommentMention,
+            json!({
+                "entity_type": entity_type,
+                "entity_id": entity_id,
+                "entity_title": entity_title,
+                "comment_id": comment_id,
+                "commenter_name": commenter_name,
+                "action": "comment_mention"
+            }),
+        )
+        .await
+    }
+
+    /// Replies to the same comment collapse into one notification
+    pub async fn notify_comment_reply(
+        &self,
+        user_id: Uuid,
+        entity_type: CommentEntityType,
+        entity_id: Uuid,
+        entity_title: &str,
+        parent_id: Uuid,
+        commenter_name: &str,
+    ) -> Result<(), ApiError> {
+        self.notify_collapsed(
+            vec![user_id],
+            NotificationType::CommentReply,
+            json!({
+                "entity_type": entity_type,
+                "entity_id": entity_id,
+                "entity_title": entity_title,
+                "comment_id": parent_id,
+                "commenter_name": commenter_name,
+                "action": "comment_reply"
+            }),
+            Some(format!("comment_reply:{}", parent_id)),
+            Some(commenter_name),
+        )
+        .await
+    }
+
+    /// `content_type` is one of "comment", "message", "project" or "bounty"
+    pub async fn notify_content_removed(
+        &self,
+        user_id: Uuid,
+        content_type: &str,
+        content_id: Uuid,
+        content_title: &str,
+        note: Option<&str>,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![user_id],
+            NotificationType::ContentRemoved,
+            json!({
+                "content_type": content_type,
+                "content_id": content_id,
+                "content_title": content_title,
+                "note": note,
+                "action": "content_removed"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_milestone_finalized(
+        &self,
+        user_ids: Vec<Uuid>,
+        project_id: Uuid,
+        project_name: &str,
+        milestone_name: &str,
+        success: bool,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::MilestoneFinalized,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "milestone_name": milestone_name,
+                "success": success,
+                "action": "milestone_finalized"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_dao_outcome(
+        &self,
+        user_ids: Vec<Uuid>,
+        dao_id: Uuid,
+        dao_name: &str,
+        passed: bool,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::DaoOutcome,
+            json!({
+                "dao_id": dao_id,
+                "dao_name": dao_name,
+                "passed": passed,
+                "action": "dao_outcome"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_bounty_status_change(
+        &self,
+        user_ids: Vec<Uuid>,
+        bounty_id: Uuid,
+        bounty_title: &str,
+        status: BountyStatus,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            user_ids,
+            NotificationType::BountyStatusChange,
+            json!({
+                "bounty_id": bounty_id,
+                "bounty_title": bounty_title,
+                "status": status,
+                "action": "bounty_status_change"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_dao_vote(
@@ -462,24 +884,16 @@ impl NotificationService {
         dao_name: &str,
         proposal_title: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::DAOVote,
-                title: "DAO Vote Required".to_string(),
-                message: format!(
-                    "A new proposal '{}' requires your vote in DAO '{}'",
-                    proposal_title, dao_name
-                ),
-                data: Some(json!({
-                    "dao_name": dao_name,
-                    "proposal_title": proposal_title,
-                    "action": "dao_vote"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::DAOVote,
+            json!({
+                "dao_name": dao_name,
+                "proposal_title": proposal_title,
+                "action": "dao_vote"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_funding_update(
@@ -488,24 +902,36 @@ impl NotificationService {
         project_name: &str,
         funding_amount: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::FundingUpdate,
-                title: "Funding Update".to_string(),
-                message: format!(
-                    "Project '{}' has received {} in funding",
-                    project_name, funding_amount
-                ),
-                data: Some(json!({
-                    "project_name": project_name,
-                    "funding_amount": funding_amount,
-                    "action": "funding_update"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::FundingUpdate,
+            json!({
+                "project_name": project_name,
+                "funding_amount": funding_amount,
+                "action": "funding_update"
+            }),
+        )
+        .await
+    }
+
+    pub async fn notify_funding_refunded(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        project_name: &str,
+        funding_amount: &str,
+    ) -> Result<(), ApiError> {
+        self.notify(
+            vec![user_id],
+            NotificationType::FundingRefunded,
+            json!({
+                "project_id": project_id,
+                "project_name": project_name,
+                "funding_amount": funding_amount,
+                "action": "funding_refunded"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_prediction_result(
@@ -514,21 +940,16 @@ impl NotificationService {
         prediction_title: &str,
         result: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::PredictionResult,
-                title: "Prediction Result".to_string(),
-                message: format!("Prediction '{}' has resolved: {}", prediction_title, result),
-                data: Some(json!({
-                    "prediction_title": prediction_title,
-                    "result": result,
-                    "action": "prediction_result"
-                })),
-            };
-            self.create_notification(notification).await?;
-        }
-        Ok(())
+        self.notify(
+            user_ids,
+            NotificationType::PredictionResult,
+            json!({
+                "prediction_title": prediction_title,
+                "result": result,
+                "action": "prediction_result"
+            }),
+        )
+        .await
     }
 
     pub async fn notify_system_message(
@@ -537,18 +958,167 @@ impl NotificationService {
         title: &str,
         message: &str,
     ) -> Result<(), ApiError> {
-        for user_id in user_ids {
-            let notification = CreateNotification {
-                user_id,
-                notification_type: NotificationType::SystemMessage,
-                title: title.to_string(),
-                message: message.to_string(),
-                data: Some(json!({
-                    "action": "system_message"
-                })),
-            };
-            self.create_notification(notification).await?;
+        self.notify(
+            user_ids,
+            NotificationType::SystemMessage,
+            json!({
+                "title": title,
+                "message": message,
+                "action": "system_message"
+            }),
+        )
+        .await
+    }
+
+    // Announcements
+    pub async fn create_announcement(
+        &self,
+        sender_id: Uuid,
+        title: &str,
+        message: &str,
+        segment: AnnouncementSegment,
+        segment_value: Option<String>,
+        scheduled_at: Option<DateTime<Utc>>,
+    ) -> Result<AnnouncementResponse, ApiError> {
+        let (title, message) = (title.trim(), message.trim());
+        if title.is_empty() || message.is_empty() {
+            return Err(DbError::Str("Title and message are required".to_string()))?;
         }
+        let segment_value = segment_value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        match segment {
+            AnnouncementSegment::All => {}
+            AnnouncementSegment::ProjectFunders => {
+                let valid = segment_value
+                    .as_deref()
+                    .is_some_and(|v| Uuid::from_str(v).is_ok());
+                if !valid {
+                    return Err(DbError::Str(
+                        "Segment value must be a project id".to_string(),
+                    ))?;
+                }
+            }
+            _ => {
+                if segment_value.is_none() {
+                    return Err(DbError::Str("Segment value is required".to_string()))?;
+                }
+            }
+        }
+        let now = Utc::now();
+        let scheduled_at = scheduled_at.unwrap_or(now);
+        let announcement = self
+            .repository
+            .create_announcement(
+                sender_id,
+                title,
+                message,
+                segment,
+                segment_value,
+                scheduled_at,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(AnnouncementResponse::from(announcement))
+    }
+
+    pub async fn get_announcements(
+        &self,
+        offset: i32,
+        limit: i32,
+    ) -> Result<Vec<AnnouncementResponse>, ApiError> {
+        let announcements = self
+            .repository
+            .get_announcements(offset, limit)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(announcements
+            .into_iter()
+            .map(AnnouncementResponse::from)
+            .collect())
+    }
+
+    pub async fn cancel_announcement(&self, id: Uuid) -> Result<AnnouncementResponse, ApiError> {
+        let announcement = self
+            .repository
+            .cancel_announcement(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .ok_or(DbError::Str(
+                "Only scheduled announcements can be cancelled".to_string(),
+            ))?;
+        Ok(AnnouncementResponse::from(announcement))
+    }
+
+    /// Delivers every scheduled announcement whose send time has passed and
+    /// returns how many were sent. Called by the announcement job.
+    pub async fn send_due_announcements(&self) -> Result<usize, ApiError> {
+        let mut count = 0;
+        while self
+            .repository
+            .send_next_due_announcement(Utc::now(), ANNOUNCEMENT_BATCH_SIZE)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .is_some()
+        {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    // Retention
+    /// Archives (or deletes, for users who opted out of archived history) read
+    /// notifications older than `read_retention` and unread ones older than
+    /// `unread_retention`, one batch at a time. Returns (removed, archived).
+    pub async fn archive_expired_notifications(
+        &self,
+        read_retention: Duration,
+        unread_retention: Duration,
+        batch_size: i64,
+    ) -> Result<(i64, i64), ApiError> {
+        let (mut removed, mut archived) = (0, 0);
+        loop {
+            let now = Utc::now();
+            let (batch_removed, batch_archived) = self
+                .repository
+                .archive_expired_notifications(
+                    now - read_retention,
+                    now - unread_retention,
+                    batch_size,
+                )
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+            removed += batch_removed;
+            archived += batch_archived;
+            if batch_removed == 0 || batch_removed < batch_size {
+                break;
+            }
+        }
+        Ok((removed, archived))
+    }
+
+    pub async fn get_archived_notifications(
+        &self,
+        user_id: Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<NotificationResponse>, ApiError> {
+        let notifications = self
+            .repository
+            .get_archived_notifications(user_id, limit, offset)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(notifications
+            .into_iter()
+            .map(NotificationResponse::from)
+            .collect())
+    }
+
+    pub async fn delete_archived_notifications(&self, user_id: Uuid) -> Result<(), ApiError> {
+        self.repository
+            .delete_archived_notifications(user_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
         Ok(())
     }
 
@@ -5,7 +5,7 @@ use crate::{
 use std::sync::Arc;
 use types::{
     error::{ApiError, DbError, UserError},
-    models::{MyPrediction, Prediction, PredictionInfo, PredictionStatus, User},
+    models::{ListingSort, MyPrediction, Prediction, PredictionInfo, PredictionStatus, User},
 };
 use utils::commons::uuid_from_str;
 use uuid::Uuid;
@@ -162,6 +162,7 @@ impl PredictionService {
         status: Option<PredictionStatus>,
         category_id: Option<Uuid>,
         is_mine: Option<bool>,
+        sort: Option<ListingSort>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<PredictionInfo>, ApiError> {
@@ -173,6 +174,7 @@ impl PredictionService {
                 category_id,
                 user.as_ref(),
                 is_mine,
+                sort,
                 offset,
                 limit,
             )
@@ -1,25 +1,39 @@
 use crate::{
-    pool::DatabasePool, PredictionRepository, ProjectRepository, UserRepository, UtilRepository,
+    pool::DatabasePool, AIReviewRepository, CollaboratorRepository, MilestonePayoutRepository,
+    PredictionRepository, ProjectRepository, ProjectUpdateRepository, UserRepository,
+    UtilRepository, WebhookService,
 };
 use chrono::{Datelike, Duration, Utc};
 use evm::EVMClient;
-use std::sync::Arc;
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc};
 use types::{
     dto::{
-        AdminProjectDashboardCounts, DaoStatisticsResponse, DaoVoteTab, EditorDashboardCounts,
-        MilestoneApprovalRequest, MilestoneApprovalStatus, ProjectCountsResponse,
-        ProjectFunderInfo, ProjectFundersResponse, ProjectUpdateStep1Request,
-        ProjectUpdateStep2Request, ProjectUpdateStep3Request, ResearchProjectDashboardResponse,
-        UpdateMilestoneRequest, UserDaoVotingStats,
+        AdminProjectDashboardCounts, CreateProjectUpdateRequest, DaoStatisticsResponse, DaoVoteTab,
+        EditProjectUpdateRequest, EditorDashboardCounts, GetMilestonePayoutsOption,
+        MilestoneApprovalRequest, MilestoneApprovalStatus, Paginated,
+        ProjectBudgetValidationResponse, ProjectCountsResponse, ProjectFunderInfo,
+        ProjectFundersResponse, ProjectUpdateStep1Request, ProjectUpdateStep2Request,
+        ProjectUpdateStep3Request, ResearchProjectDashboardResponse, UpdateMilestoneRequest,
+        UserDaoVotingStats, ValidateProjectBudgetRequest,
     },
-    error::{ApiError, DbError, UserError},
+    error::{ApiError, DbError, ProjectError, UserError},
     models::{
-        CompletedDao, DaoDetailInfo, DaoInfo, DaoVote, FunderInfo, Milestone, Project,
-        ProjectCommentInfo, ProjectIds, ProjectInfo, ProjectItemInfo,
+        diff_snapshots, CollaborationInvitationInfo, CollaboratorRole, CollaboratorStatus,
+        CompletedDao, Cursor, Dao, DaoDetailInfo, DaoInfo, DaoVote, EditorRecommendation,
+        FunderInfo, FundingRefund, ListingSort, Milestone, MilestoneBudget, MilestonePayoutInfo,
+        MilestonePayoutStatus, PageRequest, Project, ProjectBudget, ProjectCollaborator,
+        ProjectCollaboratorInfo, ProjectIds, ProjectInfo, ProjectItemInfo, ProjectPermission,
+        ProjectRevisionDiff, ProjectRevisionInfo, ProjectStatusHistory, ProjectStatusHistoryInfo,
+        ProjectUpdate, ProjectUpdateCommentInfo, ProjectUpdateInfo, ProjectUpdateVisibility, User,
+        UserInfo, WebhookEvent,
     },
     FeedbackStatus, MileStoneStatus, ProjectStatus, UserRoleType,
 };
-use utils::commons::{generate_random_number, uuid_from_str};
+use utils::{
+    commons::{generate_random_number, uuid_from_str},
+    env::Env,
+};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -28,18 +42,116 @@ pub struct ProjectService {
     user_repo: UserRepository,
     util_repo: UtilRepository,
     prediction_repo: PredictionRepository,
+    collaborator_repo: CollaboratorRepository,
+    update_repo: ProjectUpdateRepository,
+    ai_review_repo: AIReviewRepository,
+    payout_repo: MilestonePayoutRepository,
+    webhook: WebhookService,
+    editor_auto_assign: bool,
 }
 
 impl ProjectService {
-    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env, webhook: &WebhookService) -> Self {
         Self {
             project_repo: ProjectRepository::new(db_conn),
             user_repo: UserRepository::new(db_conn),
             util_repo: UtilRepository::new(db_conn),
             prediction_repo: PredictionRepository::new(db_conn),
+            collaborator_repo: CollaboratorRepository::new(db_conn),
+            update_repo: ProjectUpdateRepository::new(db_conn),
+            ai_review_repo: AIReviewRepository::new(db_conn),
+            payout_repo: MilestonePayoutRepository::new(db_conn),
+            webhook: webhook.clone(),
+            editor_auto_assign: env.editor_auto_assign,
         }
     }
 
+    /// Loads the project and checks that `user_id` owns it or collaborates on
+    /// it with a role that allows `permission`
+    pub async fn check_permission(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        permission: ProjectPermission,
+    ) -> Result<Project, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        if project.user_id == user_id {
+            return Ok(project);
+        }
+        match self.collaborator_repo.get_collaborator(id, user_id).await {
+            Some(c) if c.status == CollaboratorStatus::Accepted && permission.allows(c.role) => {
+                Ok(project)
+            }
+            _ => Err(DbError::Str("No permission".to_string()).into()),
+        }
+    }
+
+    /// Loads the project and checks that `user_id` is an admin or an editor
+    /// assigned to it. With `allow_owner` the owner is let through as well.
+    pub async fn check_editor_access(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+        allow_owner: bool,
+    ) -> Result<Project, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        if is_admin
+            || (allow_owner && project.user_id == user_id)
+            || self.project_repo.is_project_editor(id, user_id).await
+        {
+            return Ok(project);
+        }
+        Err(DbError::Str("No permission".to_string()).into())
+    }
+
+    /// Moves `project` to `to` through the status transition table and records
+    /// it in the status history. `actor_id` is `None` for on-chain events.
+    async fn transition_status(
+        &self,
+        project: &Project,
+        to: ProjectStatus,
+        actor_id: Option<Uuid>,
+        reason: Option<String>,
+    ) -> Result<(), ApiError> {
+        let from = ProjectStatus::from(project.status);
+        let to = from.transition_to(to)?;
+        if !self
+            .project_repo
+            .update_project_status(project.id, &from, &to, actor_id, reason)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+        {
+            return Err(ProjectError::StatusConflict.into());
+        }
+        self.emit_status_changed(project, &to).await;
+        Ok(())
+    }
+
+    async fn emit_status_changed(&self, project: &Project, status: &ProjectStatus) {
+        let _ = self
+            .webhook
+            .enqueue(
+                WebhookEvent::ProjectStatusChanged,
+                &[project.user_id],
+                json!({
+                    "projectId": project.id,
+                    "nerdId": project.nerd_id,
+                    "from": ProjectStatus::from(project.status),
+                    "to": status,
+                }),
+            )
+            .await;
+    }
+
     pub async fn project_to_info(&self, project: &Project) -> Result<ProjectInfo, ApiError> {
         let user = self
             .user_repo
@@ -49,26 +161,70 @@ impl ProjectService {
         let category = self.util_repo.get_category_by_ids(&project.category).await;
         let team_members = self.project_repo.get_team_members(project.id).await;
         let milestones = self.project_repo.get_milestones(project.id).await;
-        Ok(project.to_info(user.to_info(), category, team_members, milestones))
+        let mut info = project.to_info(user.to_info(), category, team_members, milestones);
+        let history = self
+            .project_repo
+            .get_project_status_history(project.id)
+            .await
+            .unwrap_or_default();
+        info.status_history = self.status_history_to_info(history).await;
+        let collaborators = self
+            .collaborator_repo
+            .get_collaborators(project.id, Some(CollaboratorStatus::Accepted))
+            .await
+            .unwrap_or_default();
+        info.collaborators = self.collaborators_to_info(collaborators).await;
+        Ok(info)
     }
 
-    pub async fn get_project_by_id(&self, id: &str) -> Result<ProjectInfo, ApiError> {
-        let project = if let Ok(id) = uuid_from_str(id) {
-            self.project_repo
-                .get_project_by_id(id)
-                .await
-                .ok_or_else(|| DbError::Str("Project not found".to_string()))?
-        } else if id.starts_with("RP-") {
-            self.project_repo
-                .get_project_by_nerd_id(id)
-                .await
-                .ok_or_else(|| DbError::Str("Project not found".to_string()))?
-        } else {
-            return Err(DbError::Str("Invalid id format".to_string()).into());
-        };
-        // Increment view count
-        let _ = self.project_repo.increment_view_count(project.id).await;
-        self.project_to_info(&project).await
+    pub async fn get_status_history(
+        &self,
+        project_id: Option<Uuid>,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ProjectStatusHistoryInfo>, ApiError> {
+        let history = self
+            .project_repo
+            .get_status_history(
+                project_id,
+                offset.unwrap_or(0),
+                limit.unwrap_or(20).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(self.status_history_to_info(history).await)
+    }
+
+    /// Loads the given users with one query, keyed by id
+    async fn get_user_infos(&self, ids: &[Uuid]) -> HashMap<Uuid, UserInfo> {
+        self.user_repo
+            .get_users_by_ids(ids)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|u| (u.id, u.to_info()))
+            .collect()
+    }
+
+    async fn status_history_to_info(
+        &self,
+        history: Vec<ProjectStatusHistory>,
+    ) -> Vec<ProjectStatusHistoryInfo> {
+        let actor_ids: Vec<Uuid> = history.iter().filter_map(|h| h.actor_id).collect();
+        let actors = self.get_user_infos(&actor_ids).await;
+        history
+            .into_iter()
+            .map(|item| {
+                let actor = item.actor_id.and_then(|id| actors.get(&id).cloned());
+                item.to_info(actor)
+            })
+            .collect()
+    }
+
+    /// Counts the view of `viewer`. Call it once the project is known to be
+    /// visible to them, so hidden projects don't collect views.
+    pub async fn record_view(&self, id: Uuid, viewer: Option<&str>) {
+        let _ = self.project_repo.increment_view_count(id, viewer).await;
     }
 
     pub async fn get_project_by_id_without_increment(
@@ -110,14 +266,8 @@ impl ProjectService {
 
     pub async fn delete_project(&self, id: &str, user_id: Uuid) -> Result<bool, ApiError> {
         let id = uuid_from_str(id)?;
-        let project = self
-            .project_repo
-            .get_project_by_id(id)
-            .await
-            .ok_or(DbError::Str("Project not found".to_string()))?;
-        if project.user_id != user_id {
-            return Err(DbError::Str("No permission".to_string()).into());
-        }
+        self.check_permission(id, user_id, ProjectPermission::Manage)
+            .await?;
         let res = self
             .project_repo
             .delete_project(id)
@@ -129,12 +279,16 @@ impl ProjectService {
     pub async fn update_project_step_1(
         &self,
         id: &str,
+        user_id: Uuid,
         payload: ProjectUpdateStep1Request,
     ) -> Result<bool, ApiError> {
+        let id = uuid_from_str(id)?;
+        self.check_permission(id, user_id, ProjectPermission::Edit)
+            .await?;
         let res = self
             .project_repo
             .update_project_step_1(
-                uuid_from_str(id)?,
+                id,
                 payload.manuscript,
                 payload.upload_files.unwrap_or_default(),
                 payload.cover_photo,
@@ -153,12 +307,16 @@ impl ProjectService {
     pub async fn update_project_step_2(
         &self,
         id: &str,
+        user_id: Uuid,
         payload: ProjectUpdateStep2Request,
     ) -> Result<bool, ApiError> {
+        let id = uuid_from_str(id)?;
+        self.check_permission(id, user_id, ProjectPermission::Edit)
+            .await?;
         let res = self
             .project_repo
             .update_project_step_2(
-                uuid_from_str(id)?,
+                id,
                 payload.details,
                 payload.personnel_cost,
                 payload.equipment_cost,
@@ -175,9 +333,12 @@ impl ProjectService {
     pub async fn update_project_step_3(
         &self,
         id: &str,
+        user_id: Uuid,
         payload: ProjectUpdateStep3Request,
     ) -> Result<bool, ApiError> {
         let project_id = uuid_from_str(id)?;
+        self.check_permission(project_id, user_id, ProjectPermission::Edit)
+            .await?;
         self.project_repo.delete_team_members(project_id).await.ok();
         self.project_repo.delete_milestones(project_id).await.ok();
         for tm in payload.team_members {
@@ -211,11 +372,486 @@ impl ProjectService {
         Ok(true)
     }
 
-    pub async fn submit_project(&self, id: &str) -> Result<bool, ApiError> {
-        self.project_repo
-            .submit_project(uuid_from_str(id)?)
+    pub async fn submit_project(&self, id: &str, user_id: Uuid) -> Result<bool, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, user_id, ProjectPermission::Edit)
+            .await?;
+        let milestones = self.project_repo.get_milestones(project.id).await;
+        let violations = ProjectBudget::from_project(&project, &milestones).validate();
+        if !violations.is_empty() {
+            return Err(ProjectError::InvalidBudget(violations).into());
+        }
+        self.transition_status(&project, ProjectStatus::PendingReview, Some(user_id), None)
+            .await?;
+
+        // Keep a snapshot of every submission so editors can see what changed
+        let team_members = self.project_repo.get_team_members(project.id).await;
+        let snapshot = json!(project.to_snapshot(&team_members, &milestones));
+        if let Err(e) = self
+            .project_repo
+            .create_project_revision(
+                project.id,
+                &ProjectStatus::from(project.status),
+                Some(user_id),
+                &snapshot,
+            )
+            .await
+        {
+            println!("Failed to store revision of project {}: {}", project.id, e);
+        }
+        if let Err(e) = self.ai_review_repo.queue_review(project.id).await {
+            println!("Failed to queue AI review of project {}: {}", project.id, e);
+        }
+
+        if self.editor_auto_assign {
+            match self.auto_assign_editor(project.id).await {
+                Ok(false) => println!("No editor available for project {}", project.id),
+                Err(e) => println!("Failed to auto-assign editor to {}: {:?}", project.id, e),
+                _ => {}
+            }
+        }
+        Ok(true)
+    }
+
+    /// Runs the submit-time budget checks without submitting. Values in
+    /// `payload` take the place of the stored ones.
+    pub async fn validate_project_budget(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        payload: ValidateProjectBudgetRequest,
+    ) -> Result<ProjectBudgetValidationResponse, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, user_id, ProjectPermission::Read)
+            .await?;
+        let milestones = self.project_repo.get_milestones(project.id).await;
+        let mut budget = ProjectBudget::from_project(&project, &milestones);
+        budget.funding_goal = payload.funding_goal.or(budget.funding_goal);
+        budget.duration = payload.duration.or(budget.duration);
+        budget.personnel_cost = payload.personnel_cost.or(budget.personnel_cost);
+        budget.equipment_cost = payload.equipment_cost.or(budget.equipment_cost);
+        budget.materials_cost = payload.materials_cost.or(budget.materials_cost);
+        budget.overhead_cost = payload.overhead_cost.or(budget.overhead_cost);
+        budget.other_cost = payload.other_cost.or(budget.other_cost);
+        if let Some(mut milestones) = payload.milestones {
+            milestones.sort_by_key(|m| m.number);
+            budget.milestones = milestones
+                .into_iter()
+                .map(|m| MilestoneBudget {
+                    number: m.number,
+                    funding_amount: m.funding_amount,
+                    days_after_start: m.days_after_start,
+                })
+                .collect();
+        }
+        let violations = budget.validate();
+        Ok(ProjectBudgetValidationResponse {
+            valid: violations.is_empty(),
+            violations,
+        })
+    }
+
+    /// Drafts are readable by the owner and every accepted collaborator
+    pub async fn get_project_draft(
+        &self,
+        id: &str,
+        user_id: Uuid,
+    ) -> Result<ProjectInfo, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, user_id, ProjectPermission::Read)
+            .await?;
+        self.project_to_info(&project).await
+    }
+
+    pub async fn get_collaborators(
+        &self,
+        id: &str,
+        user_id: Uuid,
+    ) -> Result<Vec<ProjectCollaboratorInfo>, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, user_id, ProjectPermission::Read)
+            .await?;
+        let collaborators = self
+            .collaborator_repo
+            .get_collaborators(project.id, None)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(self.collaborators_to_info(collaborators).await)
+    }
+
+    pub async fn invite_collaborator(
+        &self,
+        id: &str,
+        owner_id: Uuid,
+        user_id: Uuid,
+        role: CollaboratorRole,
+    ) -> Result<ProjectCollaboratorInfo, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, owner_id, ProjectPermission::Manage)
+            .await?;
+        if user_id == project.user_id {
+            return Err(DbError::Str("The owner can't be a collaborator".to_string()).into());
+        }
+        let user = self
+            .user_repo
+            .get_user_by_id(user_id)
+            .await
+            .ok_or(ApiError::UserError(UserError::UserNotFound))?;
+        let collaborator = self
+            .collaborator_repo
+            .create_collaborator(project.id, user.id, role, owner_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .ok_or(DbError::Str("User already invited".to_string()))?;
+        Ok(collaborator.to_info(user.to_info()))
+    }
+
+    /// The owner can remove anyone; a collaborator can remove themselves
+    pub async fn remove_collaborator(
+        &self,
+        id: &str,
+        actor_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let id = uuid_from_str(id)?;
+        if actor_id != user_id {
+            self.check_permission(id, actor_id, ProjectPermission::Manage)
+                .await?;
+        }
+        let res = self
+            .collaborator_repo
+            .delete_collaborator(id, user_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        if !res {
+            return Err(DbError::Str("Collaborator not found".to_string()).into());
+        }
+        Ok(res)
+    }
+
+    pub async fn respond_collaboration(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        accept: bool,
+    ) -> Result<bool, ApiError> {
+        let status = if accept {
+            CollaboratorStatus::Accepted
+        } else {
+            CollaboratorStatus::Declined
+        };
+        let res = self
+            .collaborator_repo
+            .respond_invitation(uuid_from_str(id)?, user_id, status)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        if !res {
+            return Err(DbError::Str("Invitation not found".to_string()).into());
+        }
+        Ok(res)
+    }
+
+    pub async fn get_collaboration_invitations(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<CollaborationInvitationInfo>, ApiError> {
+        let invitations = self
+            .collaborator_repo
+            .get_invitations(user_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let inviter_ids: Vec<Uuid> = invitations.iter().filter_map(|i| i.invited_by).collect();
+        let inviters = self.get_user_infos(&inviter_ids).await;
+        Ok(invitations
+            .into_iter()
+            .map(|invitation| {
+                let invited_by = invitation
+                    .invited_by
+                    .and_then(|id| inviters.get(&id).cloned());
+                invitation.to_info(invited_by)
+            })
+            .collect())
+    }
+
+    /// Funders-only updates are visible to funders, the team and admins
+    async fn can_view_funders_only(
+        &self,
+        project: &Project,
+        user_id: Option<Uuid>,
+        is_admin: bool,
+    ) -> bool {
+        let Some(user_id) = user_id else {
+            return false;
+        };
+        is_admin
+            || self.update_repo.is_funder(project.id, user_id).await
+            || self
+                .check_permission(project.id, user_id, ProjectPermission::Read)
+                .await
+                .is_ok()
+    }
+
+    pub async fn create_project_update(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        payload: CreateProjectUpdateRequest,
+    ) -> Result<ProjectUpdateInfo, ApiError> {
+        let project = self
+            .check_permission(uuid_from_str(id)?, user_id, ProjectPermission::Edit)
+            .await?;
+        let user = self
+            .user_repo
+            .get_user_by_id(user_id)
+            .await
+            .ok_or(ApiError::UserError(UserError::UserNotFound))?;
+        let update = self
+            .update_repo
+            .create_update(project.id, user_id, &payload)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(update.to_info(user.to_info(), 0))
+    }
+
+    pub async fn edit_project_update(
+        &self,
+        update_id: &str,
+        user_id: Uuid,
+        payload: EditProjectUpdateRequest,
+    ) -> Result<ProjectUpdateInfo, ApiError> {
+        let update = self
+            .update_repo
+            .get_update_by_id(uuid_from_str(update_id)?)
+            .await
+            .ok_or(DbError::Str("Update not found".to_string()))?;
+        self.check_permission(update.project_id, user_id, ProjectPermission::Edit)
+            .await?;
+        let update = self
+            .update_repo
+            .edit_update(update.id, &payload)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let user = self
+            .user_repo
+            .get_user_by_id(update.user_id)
+            .await
+            .ok_or(ApiError::UserError(UserError::UserNotFound))?;
+        let count_comments = self.update_repo.get_comment_count(update.id).await;
+        Ok(update.to_info(user.to_info(), count_comments))
+    }
+
+    pub async fn delete_project_update(
+        &self,
+        update_id: &str,
+        user_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let update = self
+            .update_repo
+            .get_update_by_id(uuid_from_str(update_id)?)
+            .await
+            .ok_or(DbError::Str("Update not found".to_string()))?;
+        self.check_permission(update.project_id, user_id, ProjectPermission::Edit)
+            .await?;
+        self.update_repo
+            .delete_update(update.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_project_updates(
+        &self,
+        id: &str,
+        user_id: Option<Uuid>,
+        is_admin: bool,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ProjectUpdateInfo>, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(uuid_from_str(id)?)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let include_funders_only = self
+            .can_view_funders_only(&project, user_id, is_admin)
+            .await;
+        let updates = self
+            .update_repo
+            .get_updates(
+                project.id,
+                include_funders_only,
+                offset.unwrap_or(0),
+                limit.unwrap_or(10).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let update_ids: Vec<Uuid> = updates.iter().map(|u| u.id).collect();
+        let author_ids: Vec<Uuid> = updates.iter().map(|u| u.user_id).collect();
+        let count_comments = self.update_repo.get_comment_counts(&update_ids).await;
+        let authors = self.get_user_infos(&author_ids).await;
+        Ok(updates
+            .into_iter()
+            .filter_map(|update| {
+                let author = authors.get(&update.user_id)?.clone();
+                let count = count_comments.get(&update.id).copied().unwrap_or(0);
+                Some(update.to_info(author, count))
+            })
+            .collect())
+    }
+
+    /// Loads an update, failing if it is funders-only and hidden from the user
+    async fn get_visible_update(
+        &self,
+        update_id: &str,
+        user_id: Option<Uuid>,
+        is_admin: bool,
+    ) -> Result<ProjectUpdate, ApiError> {
+        let update = self
+            .update_repo
+            .get_update_by_id(uuid_from_str(update_id)?)
+            .await
+            .ok_or(DbError::Str("Update not found".to_string()))?;
+        if update.visibility == ProjectUpdateVisibility::FundersOnly {
+            let project = self
+                .project_repo
+                .get_project_by_id(update.project_id)
+                .await
+                .ok_or(DbError::Str("Project not found".to_string()))?;
+            if !self
+                .can_view_funders_only(&project, user_id, is_admin)
+                .await
+            {
+                return Err(DbError::Str("No permission".to_string()).into());
+            }
+        }
+        Ok(update)
+    }
+
+    pub async fn get_project_update_comments(
+        &self,
+        update_id: &str,
+        user_id: Option<Uuid>,
+        is_admin: bool,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ProjectUpdateCommentInfo>, ApiError> {
+        let update = self
+            .get_visible_update(update_id, user_id, is_admin)
+            .await?;
+        let comments = self
+            .update_repo
+            .get_comments(update.id, offset.unwrap_or(0), limit.unwrap_or(20).min(100))
             .await
-            .map_err(|_| DbError::Str("Submit project failed".to_string()).into())
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let author_ids: Vec<Uuid> = comments.iter().map(|c| c.user_id).collect();
+        let authors = self.get_user_infos(&author_ids).await;
+        Ok(comments
+            .into_iter()
+            .filter_map(|comment| {
+                let author = authors.get(&comment.user_id)?.clone();
+                Some(comment.to_info(author))
+            })
+            .collect())
+    }
+
+    /// Returns the new comment along with the update it was posted on
+    pub async fn submit_project_update_comment(
+        &self,
+        update_id: &str,
+        user: &User,
+        is_admin: bool,
+        comment: &str,
+    ) -> Result<(ProjectUpdateCommentInfo, ProjectUpdate), ApiError> {
+        let update = self
+            .get_visible_update(update_id, Some(user.id), is_admin)
+            .await?;
+        let comment = self
+            .update_repo
+            .create_comment(update.id, user.id, comment)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok((comment.to_info(user.to_info()), update))
+    }
+
+    pub async fn get_funder_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>, ApiError> {
+        self.update_repo
+            .get_funder_ids(project_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    async fn collaborators_to_info(
+        &self,
+        collaborators: Vec<ProjectCollaborator>,
+    ) -> Vec<ProjectCollaboratorInfo> {
+        let user_ids: Vec<Uuid> = collaborators.iter().map(|c| c.user_id).collect();
+        let users = self.get_user_infos(&user_ids).await;
+        collaborators
+            .into_iter()
+            .filter_map(|collaborator| {
+                let user = users.get(&collaborator.user_id)?.clone();
+                Some(collaborator.to_info(user))
+            })
+            .collect()
+    }
+
+    pub async fn get_project_revisions(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<ProjectRevisionInfo>, ApiError> {
+        let project = self
+            .check_editor_access(uuid_from_str(id)?, user_id, is_admin, true)
+            .await?;
+        let revisions = self
+            .project_repo
+            .get_project_revisions(project.id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(revisions.iter().map(|r| r.to_info()).collect())
+    }
+
+    /// Field-level diff between two submitted versions. Defaults to the latest
+    /// version against the one before it.
+    pub async fn get_project_revision_diff(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+        from: Option<i32>,
+        to: Option<i32>,
+    ) -> Result<ProjectRevisionDiff, ApiError> {
+        let project_id = self
+            .check_editor_access(uuid_from_str(id)?, user_id, is_admin, true)
+            .await?
+            .id;
+        let to = match to {
+            Some(version) => {
+                self.project_repo
+                    .get_project_revision(project_id, version)
+                    .await
+            }
+            None => self
+                .project_repo
+                .get_project_revisions(project_id)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?
+                .pop(),
+        }
+        .ok_or(DbError::Str("Project revision not found".to_string()))?;
+        let from = self
+            .project_repo
+            .get_project_revision(project_id, from.unwrap_or(to.version - 1))
+            .await
+            .ok_or(DbError::Str(
+                "There is no earlier revision to compare with".to_string(),
+            ))?;
+        Ok(ProjectRevisionDiff {
+            project_id,
+            changes: diff_snapshots(&from.snapshot, &to.snapshot),
+            from: from.to_info(),
+            to: to.to_info(),
+        })
     }
 
     pub async fn get_project_ids(&self) -> Result<Vec<ProjectIds>, ApiError> {
@@ -235,10 +871,14 @@ impl ProjectService {
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
         is_public: Option<bool>,
-        offset: Option<i32>,
+        sort: Option<ListingSort>,
+        page: PageRequest,
         limit: Option<i32>,
-    ) -> Result<Vec<ProjectItemInfo>, ApiError> {
+    ) -> Result<Paginated<ProjectItemInfo>, ApiError> {
+        let is_search = title.as_ref().is_some_and(|s| !s.is_empty());
+        page.ensure_default_order(!is_search && sort.is_none_or(|s| s == ListingSort::Updated))?;
         let status = status.map(|s| ProjectStatus::from(s).to_i16());
+        let limit = limit.unwrap_or(5);
         let projects = self
             .project_repo
             .get_projects(
@@ -249,11 +889,14 @@ impl ProjectService {
                 user_id,
                 is_mine,
                 is_public,
-                offset,
-                limit,
+                sort,
+                page.after(),
+                page.offset(),
+                Some(limit),
             )
             .await
             .map_err(|_| DbError::Str("Get projects failed".to_string()))?;
+        let next_cursor = page.next_cursor(&projects, limit, |p| Cursor::new(p.updated_at, p.id));
         let mut project_infos = Vec::new();
         for pro in projects {
             if let Some(user) = self.user_repo.get_user_by_id(pro.user_id).await {
@@ -269,10 +912,15 @@ impl ProjectService {
                 ));
             }
         }
-        Ok(project_infos)
+        Ok(Paginated::new(page, project_infos, next_cursor))
     }
 
-    pub async fn assign_editor(&self, id: &str, editor_id: Uuid) -> Result<bool, ApiError> {
+    pub async fn assign_editor(
+        &self,
+        id: &str,
+        editor_id: Uuid,
+        admin_id: Uuid,
+    ) -> Result<bool, ApiError> {
         let id = uuid_from_str(id)?;
         let editor = self
             .user_repo
@@ -287,28 +935,97 @@ impl ProjectService {
             .get_project_by_id(id)
             .await
             .ok_or(DbError::Str("Project not found".to_string()))?;
-        if project.status != ProjectStatus::PendingReview.to_i16() {
-            return Err(DbError::Str("Project's status is not PendingReview".to_string()).into());
-        }
+        self.assign_editor_to(&project, &editor, Some(admin_id))
+            .await?;
+        Ok(true)
+    }
+
+    async fn assign_editor_to(
+        &self,
+        project: &Project,
+        editor: &User,
+        actor_id: Option<Uuid>,
+    ) -> Result<(), ApiError> {
+        let from = ProjectStatus::from(project.status);
+        let to = from.transition_to(ProjectStatus::UnderReview)?;
+        let reason = format!(
+            "Assigned editor {}",
+            editor.name.clone().unwrap_or_default()
+        );
         if !self
             .project_repo
-            .create_project_editor(id, &project.nerd_id, editor_id)
+            .assign_project_editor(
+                project.id,
+                &project.nerd_id,
+                editor.id,
+                &from,
+                &to,
+                actor_id,
+                Some(reason),
+            )
             .await
-            .unwrap_or_default()
+            .map_err(|e| DbError::Str(e.to_string()))?
         {
-            return Err(DbError::Str("Can't create a project editor".to_string()).into());
+            return Err(ProjectError::StatusConflict.into());
         }
-        if !self
+        self.emit_status_changed(project, &to).await;
+        Ok(())
+    }
+
+    /// Ranks the editors not yet assigned to the project. Editors with a
+    /// conflict of interest are kept at the end so admins can see why.
+    pub async fn get_editor_recommendations(
+        &self,
+        id: &str,
+        limit: Option<i32>,
+    ) -> Result<Vec<EditorRecommendation>, ApiError> {
+        let id = uuid_from_str(id)?;
+        let project = self
             .project_repo
-            .update_project_status(id, &ProjectStatus::UnderReview)
+            .get_project_by_id(id)
             .await
-            .unwrap_or_default()
-        {
-            return Err(DbError::Str(
-                "Can't update the status of the project when assigning an editor".to_string(),
-            )
-            .into());
-        }
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let mut candidates = self
+            .project_repo
+            .get_editor_candidates(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let category_count = project.category.len();
+        candidates.sort_by(|a, b| a.rank_cmp(b, category_count));
+        candidates.truncate(limit.unwrap_or(10).clamp(1, 50) as usize);
+        let editor_ids: Vec<Uuid> = candidates.iter().map(|c| c.user_id).collect();
+        let editors = self.get_user_infos(&editor_ids).await;
+        Ok(candidates
+            .iter()
+            .filter_map(|candidate| {
+                let editor = editors.get(&candidate.user_id)?.clone();
+                Some(candidate.to_recommendation(editor, category_count))
+            })
+            .collect())
+    }
+
+    /// Assigns the best ranked editor without a conflict of interest.
+    /// Returns false when no editor is available.
+    async fn auto_assign_editor(&self, project_id: Uuid) -> Result<bool, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(project_id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let recommendation = self
+            .get_editor_recommendations(&project_id.to_string(), None)
+            .await?
+            .into_iter()
+            .find(|r| !r.has_conflict);
+        let Some(recommendation) = recommendation else {
+            return Ok(false);
+        };
+        let editor = self
+            .user_repo
+            .get_user_by_id(recommendation.editor.id)
+            .await
+            .ok_or(DbError::Str("Editor not found".to_string()))?;
+        self.assign_editor_to(&project, &editor, None).await?;
         Ok(true)
     }
 
@@ -320,15 +1037,12 @@ impl ProjectService {
         feedback: Option<String>,
     ) -> Result<bool, ApiError> {
         let id = uuid_from_str(id)?;
-        if !self
+        let project = self
             .project_repo
-            .update_project_editor(id, editor_id, &status, feedback)
+            .get_project_by_id(id)
             .await
-            .unwrap_or_default()
-        {
-            return Err(DbError::Str("Update project editor failed".to_string()).into());
-        }
-        let status = match status {
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let project_status = match status {
             FeedbackStatus::Accepted => ProjectStatus::ApprovedEditor,
             FeedbackStatus::RevisionRequired => ProjectStatus::RevisionEditor,
             FeedbackStatus::Rejected => ProjectStatus::Rejected,
@@ -336,17 +1050,17 @@ impl ProjectService {
                 return Err(DbError::Str("Status should not be Pending".to_string()).into());
             }
         };
+        let from = ProjectStatus::from(project.status);
+        let to = from.transition_to(project_status)?;
         if !self
             .project_repo
-            .update_project_status(id, &status)
+            .decide_editor(id, editor_id, &status, feedback, &from, &to)
             .await
-            .unwrap_or_default()
+            .map_err(|e| DbError::Str(e.to_string()))?
         {
-            return Err(DbError::Str(
-                "Can't update the status of the project when editor decision".to_string(),
-            )
-            .into());
+            return Err(DbError::Str("Update project editor failed".to_string()).into());
         }
+        self.emit_status_changed(&project, &to).await;
         Ok(true)
     }
 
@@ -356,21 +1070,19 @@ impl ProjectService {
         status: FeedbackStatus,
         feedback: Option<String>,
         to_dao: bool,
+        admin_id: Uuid,
         evm: &EVMClient,
     ) -> Result<bool, ApiError> {
         let id = uuid_from_str(id)?;
+        let project = self
+            .project_repo
+            .get_project_by_id(id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let from = ProjectStatus::from(project.status);
         let (status, dao_at, started_at) = match status {
             FeedbackStatus::Accepted if to_dao => {
-                let project = self
-                    .project_repo
-                    .get_project_by_id(id)
-                    .await
-                    .ok_or(DbError::Str("Project not found".to_string()))?;
-                if project.status != ProjectStatus::ApprovedEditor.to_i16() {
-                    return Err(
-                        DbError::Str("Project's status is not ApprovedEditor".to_string()).into(),
-                    );
-                }
+                from.transition_to(ProjectStatus::DaoVoting)?;
                 let researcher = self
                     .user_repo
                     .get_user_by_id(project.user_id)
@@ -403,17 +1115,26 @@ impl ProjectService {
                 return Err(DbError::Str("Coming soon...".to_string()).into());
                 // (ProjectStatus::Funding, None, Some(Utc::now()))
             }
-            FeedbackStatus::RevisionRequired => (ProjectStatus::RevisionAdmin, None, None),
-            FeedbackStatus::Rejected => (ProjectStatus::Rejected, None, None),
+            FeedbackStatus::RevisionRequired => (
+                from.transition_to(ProjectStatus::RevisionAdmin)?,
+                None,
+                None,
+            ),
+            FeedbackStatus::Rejected => (from.transition_to(ProjectStatus::Rejected)?, None, None),
             FeedbackStatus::Pending => {
                 return Err(DbError::Str("Status should not be Pending".to_string()).into());
             }
         };
-        if let Ok(project) = self
+        let previous = project;
+        let decided = self
             .project_repo
-            .decide_admin(id, &status, feedback, dao_at, started_at)
-            .await
-        {
+            .decide_admin(id, &from, &status, feedback, dao_at, started_at, admin_id)
+            .await;
+        if let Err(sqlx::Error::RowNotFound) = decided {
+            return Err(ProjectError::StatusConflict.into());
+        }
+        if let Ok(project) = decided {
+            self.emit_status_changed(&previous, &status).await;
             if project.status == ProjectStatus::DaoVoting.to_i16() {
                 if !self
                     .project_repo
@@ -481,13 +1202,21 @@ impl ProjectService {
     pub async fn update_milestone(
         &self,
         id: &str,
+        user_id: Uuid,
         payload: UpdateMilestoneRequest,
     ) -> Result<bool, ApiError> {
+        let milestone = self
+            .project_repo
+            .get_milestone_by_id(uuid_from_str(id)?)
+            .await
+            .ok_or(DbError::Str("Milestone not found".to_string()))?;
+        self.check_permission(milestone.project_id, user_id, ProjectPermission::Edit)
+            .await?;
         let proof_status = if payload.is_draft { 0 } else { 1 };
         if !self
             .project_repo
             .update_milestone(
-                uuid_from_str(id)?,
+                milestone.id,
                 payload.progress_description,
                 payload.deliverables,
                 payload.challenges,
@@ -534,10 +1263,28 @@ impl ProjectService {
                     .ok_or_else(|| DbError::Str("Project not found".to_string()))?;
 
                 // Call the funding contract to approve the milestone
-                let _transaction_id = evm
+                let transaction_id = evm
                     .approve_milestone_by_admin(project.proposal_id as u64, milestone.number as u64)
                     .await
                     .map_err(|e| DbError::Str(format!("Failed to call funding contract: {}", e)))?;
+
+                // The approval is on chain now, a failure here must not fail the request
+                if let Err(e) = self
+                    .payout_repo
+                    .record_payout(
+                        project.id,
+                        milestone.number,
+                        MilestonePayoutStatus::ApprovedByAdmin,
+                        Some(&transaction_id),
+                        None,
+                    )
+                    .await
+                {
+                    println!(
+                        "Failed to record payout of milestone {}: {:?}",
+                        milestone.id, e
+                    );
+                }
             }
             MilestoneApprovalStatus::Rejected => {}
         }
@@ -563,45 +1310,88 @@ impl ProjectService {
         Ok(self.project_repo.get_milestones(uuid_from_str(id)?).await)
     }
 
-    pub async fn get_project_comments(
+    /// The funding contract confirmed the admin approval of the milestone
+    /// (`milestone_index` is 0-based)
+    pub async fn milestone_claimable(
         &self,
-        id: &str,
-        offset: Option<i32>,
-        limit: Option<i32>,
-    ) -> Result<Vec<ProjectCommentInfo>, ApiError> {
-        let project_comments = self
-            .project_repo
-            .get_project_comments(uuid_from_str(id)?, offset, limit)
-            .await
-            .unwrap_or_default();
-        let mut pc_infos = Vec::new();
-        for pc in project_comments {
-            if let Some(user) = self.user_repo.get_user_by_id(pc.user_id).await {
-                pc_infos.push(pc.to_info(user.to_info()));
-            }
-        }
-        Ok(pc_infos)
+        proposal_id: i64,
+        milestone_index: i16,
+    ) -> Result<bool, ApiError> {
+        self.record_milestone_payout(
+            proposal_id,
+            milestone_index,
+            MilestonePayoutStatus::Claimable,
+            None,
+            None,
+        )
+        .await
     }
 
-    pub async fn submit_project_comment(
+    /// The researcher claimed the funds of the milestone. `amount` is in wei.
+    pub async fn milestone_claimed(
         &self,
-        id: &str,
-        user_id: Uuid,
-        comment: &str,
+        proposal_id: i64,
+        milestone_index: i16,
+        tx_hash: &str,
+        amount: u128,
+    ) -> Result<bool, ApiError> {
+        let amount = (amount as f64) / 10f64.powi(18);
+        self.record_milestone_payout(
+            proposal_id,
+            milestone_index,
+            MilestonePayoutStatus::Claimed,
+            Some(tx_hash),
+            Some(amount as i32),
+        )
+        .await
+    }
+
+    async fn record_milestone_payout(
+        &self,
+        proposal_id: i64,
+        milestone_index: i16,
+        status: MilestonePayoutStatus,
+        tx_hash: Option<&str>,
+        claimed_amount: Option<i32>,
     ) -> Result<bool, ApiError> {
-        let res = if let Some(project) = self
+        let project = self
             .project_repo
-            .get_project_by_id(uuid_from_str(id)?)
+            .get_project_by_proposal_id(proposal_id)
             .await
-        {
-            self.project_repo
-                .submit_project_comment(user_id, project.id, &project.nerd_id, comment)
-                .await
-                .unwrap_or_default()
-        } else {
-            false
-        };
-        Ok(res)
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        self.payout_repo
+            .record_payout(
+                project.id,
+                milestone_index + 1,
+                status,
+                tx_hash,
+                claimed_amount,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_milestone_payouts(
+        &self,
+        payload: GetMilestonePayoutsOption,
+    ) -> Result<Vec<MilestonePayoutInfo>, ApiError> {
+        self.payout_repo
+            .get_payouts(payload.status, payload.offset, payload.limit)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_project_by_proposal_id(&self, proposal_id: i64) -> Option<Project> {
+        self.project_repo
+            .get_project_by_proposal_id(proposal_id)
+            .await
+    }
+
+    pub async fn get_dao_by_project_id(&self, project_id: Uuid) -> Option<Dao> {
+        self.project_repo
+            .get_dao_by_project_id(project_id)
+            .await
+            .ok()
     }
 
     pub async fn get_daos(
@@ -610,12 +1400,13 @@ impl ProjectService {
         status: Option<i16>,
         user_id: Option<Uuid>,
         is_mine: Option<bool>,
+        sort: Option<ListingSort>,
         offset: Option<i32>,
         limit: Option<i32>,
     ) -> Result<Vec<DaoInfo>, ApiError> {
         let daos = self
             .project_repo
-            .get_daos(title, status, user_id, is_mine, offset, limit)
+            .get_daos(title, status, user_id, is_mine, sort, offset, limit)
             .await
             .map_err(|_| DbError::Str("Get daos failed".to_string()))?;
         let mut dao_infos = Vec::new();
@@ -746,11 +1537,18 @@ impl ProjectService {
             if project.status == ProjectStatus::DaoVoting.to_i16() {
                 if status {
                     if self
-                        .project_repo
-                        .update_project_status(project.id, &ProjectStatus::Funding)
+                        .transition_status(
+                            &project,
+                            ProjectStatus::Funding,
+                            None,
+                            Some("DAO vote passed".to_string()),
+                        )
                         .await
                         .is_ok()
                     {
+                        if let Err(e) = self.project_repo.set_project_started_at(project.id).await {
+                            println!("Failed to set start of project {}: {}", project.id, e);
+                        }
                         let milestones = self.project_repo.get_milestones(project.id).await;
                         if !milestones.is_empty() {
                             if !self
@@ -800,17 +1598,40 @@ impl ProjectService {
                         }
                     }
                 } else {
-                    self.project_repo
-                        .update_project_status(project.id, &ProjectStatus::Rejected)
-                        .await
-                        .ok();
+                    self.transition_status(
+                        &project,
+                        ProjectStatus::Rejected,
+                        None,
+                        Some("DAO vote failed".to_string()),
+                    )
+                    .await
+                    .ok();
                 }
             }
             if let Ok(dao) = self.project_repo.get_dao_by_project_id(project.id).await {
                 if dao.status == 0 {
                     // active
-                    let status = if status { 1i16 } else { 2i16 };
-                    self.project_repo.finish_dao(dao.id, status).await.ok();
+                    let dao_status = if status { 1i16 } else { 2i16 };
+                    if self
+                        .project_repo
+                        .finish_dao(dao.id, dao_status)
+                        .await
+                        .is_ok()
+                    {
+                        let _ = self
+                            .webhook
+                            .enqueue(
+                                WebhookEvent::DaoFinished,
+                                &[project.user_id],
+                                json!({
+                                    "daoId": dao.id,
+                                    "projectId": project.id,
+                                    "proposalId": proposal_id,
+                                    "approved": status,
+                                }),
+                            )
+                            .await;
+                    }
                 }
             }
         }
@@ -863,6 +1684,49 @@ impl ProjectService {
         Ok(true)
     }
 
+    /// Applies a `Refunded` event of the funding contract, once per
+    /// `tx_hash` and `log_index`. Returns the project and the refunds
+    /// recorded against its fundings, which are empty for a replayed event.
+    pub async fn refund_funding(
+        &self,
+        proposal_id: i64,
+        wallet: &str,
+        amount: u128,
+        tx_hash: &str,
+        log_index: i64,
+    ) -> Result<(Project, Vec<FundingRefund>), ApiError> {
+        let amount = ((amount as f64) / 10f64.powi(18)) as i32;
+        let project = self
+            .project_repo
+            .get_project_by_proposal_id(proposal_id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        if self
+            .project_repo
+            .is_refund_recorded(tx_hash, log_index)
+            .await
+        {
+            return Ok((project, Vec::new()));
+        }
+        let refunds = self
+            .project_repo
+            .refund_funding(project.id, wallet, amount, tx_hash, log_index)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let applied: i32 = refunds.iter().map(|r| r.amount).sum();
+        if applied < amount {
+            println!(
+                "Refund of {} to {} on project {} exceeds its remaining funding by {} ({})",
+                amount,
+                wallet,
+                project.id,
+                amount - applied,
+                tx_hash
+            );
+        }
+        Ok((project, refunds))
+    }
+
     pub async fn get_similar_projects(
         &self,
         id: &str,
@@ -1048,7 +1912,7 @@ impl ProjectService {
     async fn get_project_funders(&self, project_id: Uuid) -> Result<Vec<FunderInfo>, ApiError> {
         let fundings = self
             .project_repo
-            .get_project_funding(project_id, Some(3), Some(0))
+            .get_project_funding(project_id, None, Some(3), Some(0))
             .await
             .map_err(|_| DbError::Str("Failed to get project funding".to_string()))?;
 
@@ -1091,14 +1955,16 @@ impl ProjectService {
     pub async fn get_project_funders_full(
         &self,
         project_id: Uuid,
-        offset: Option<i32>,
+        page: PageRequest,
         limit: Option<i32>,
     ) -> Result<ProjectFundersResponse, ApiError> {
+        let limit = limit.unwrap_or(5);
         let fundings = self
             .project_repo
-            .get_project_funding(project_id, limit, offset)
+            .get_project_funding(project_id, page.after(), Some(limit), page.offset())
             .await
             .map_err(|_| DbError::Str("Failed to get project funding".to_string()))?;
+        let next_cursor = page.next_cursor(&fundings, limit, |f| Cursor::new(f.created_at, f.id));
 
         let total_count = self
             .project_repo
@@ -1110,7 +1976,7 @@ impl ProjectService {
         let mut total_amount = 0;
 
         for funding in &fundings {
-            total_amount += funding.amount;
+            total_amount += funding.amount - funding.refunded_amount;
 
             let funder_info = if let Some(user_id) = funding.user_id {
                 if let Some(user) = self.user_repo.get_user_by_id(user_id).await {
@@ -1123,6 +1989,8 @@ impl ProjectService {
                         avatar_url: user.avatar_url.clone(),
                         number: funding.number,
                         amount: funding.amount,
+                        refunded_amount: funding.refunded_amount,
+                        refund_status: funding.refund_status(),
                         created_at: funding.created_at,
                         updated_at: funding.updated_at,
                     }
@@ -1136,6 +2004,8 @@ impl ProjectService {
                         avatar_url: None,
                         number: funding.number,
                         amount: funding.amount,
+                        refunded_amount: funding.refunded_amount,
+                        refund_status: funding.refund_status(),
                         created_at: funding.created_at,
                         updated_at: funding.updated_at,
                     }
@@ -1150,6 +2020,8 @@ impl ProjectService {
                     avatar_url: None,
                     number: funding.number,
                     amount: funding.amount,
+                    refunded_amount: funding.refunded_amount,
+                    refund_status: funding.refund_status(),
                     created_at: funding.created_at,
                     updated_at: funding.updated_at,
                 }
@@ -1161,6 +2033,7 @@ impl ProjectService {
             funders,
             total_amount,
             total_count,
+            next_cursor: next_cursor.map(|c| c.encode()),
         })
     }
 
@@ -1198,6 +2071,20 @@ impl ProjectService {
                         "Failed to update milestone {} status: {}",
                         current_milestone.id, e
                     );
+                } else {
+                    let _ = self
+                        .webhook
+                        .enqueue(
+                            WebhookEvent::MilestoneFinalized,
+                            &[project.user_id],
+                            json!({
+                                "projectId": project.id,
+                                "milestoneId": current_milestone.id,
+                                "milestoneNumber": current_milestone_number,
+                                "success": success,
+                            }),
+                        )
+                        .await;
                 }
                 // Update prediction result based on milestone success
                 if let Err(e) = self
@@ -1259,12 +2146,20 @@ impl ProjectService {
             .iter()
             .filter(|p| p.status == ProjectStatus::Completed.to_i16())
             .count() as i32;
+        let payouts = self
+            .payout_repo
+            .get_payouts_by_user_id(user_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let total_claimed = payouts.iter().filter_map(|p| p.claimed_amount).sum();
 
         Ok(ResearchProjectDashboardResponse {
             total_projects,
             total_funded,
             total_backers,
             completed,
+            total_claimed,
+            payouts,
         })
     }
 
@@ -0,0 +1,210 @@
+use crate::{pool::DatabasePool, ReportRepository, UserRepository};
+use std::collections::HashMap;
+use std::sync::Arc;
+use types::{
+    dto::{
+        CreateReportRequest, GetModerationQueueOption, ModerateContentAction,
+        ModerateContentRequest,
+    },
+    error::{ApiError, DbError},
+    models::{
+        ModerationAction, ModerationQueueItem, ModerationStatus, Report, ReportInfo, ReportStatus,
+        ReportTarget, ReportTargetType, UserInfo,
+    },
+};
+use utils::env::Env;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ReportService {
+    report_repo: ReportRepository,
+    user_repo: UserRepository,
+    auto_hide_threshold: i64,
+}
+
+impl ReportService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env) -> Self {
+        Self {
+            report_repo: ReportRepository::new(db_conn),
+            user_repo: UserRepository::new(db_conn),
+            auto_hide_threshold: env.report_auto_hide_threshold,
+        }
+    }
+
+    async fn get_target(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+    ) -> Result<ReportTarget, ApiError> {
+        let target = self
+            .report_repo
+            .get_target(target_type, target_id)
+            .await
+            .ok_or(DbError::Str(format!("{:?} not found", target_type)))?;
+        Ok(target)
+    }
+
+    /// Hidden and removed content is only shown to its author and admins
+    pub async fn ensure_visible(
+        &self,
+        target_type: ReportTargetType,
+        target_id: Uuid,
+        viewer_id: Option<Uuid>,
+        is_admin: bool,
+    ) -> Result<(), ApiError> {
+        if is_admin {
+            return Ok(());
+        }
+        if let Some(target) = self.report_repo.get_target(target_type, target_id).await {
+            if target.moderation_status != ModerationStatus::Visible
+                && viewer_id != Some(target.author_id)
+            {
+                return Err(DbError::Str(format!("{:?} not found", target_type)).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Files a report and hides the content once it has enough open reports.
+    /// Chat messages can only be reported by their recipient.
+    pub async fn create_report(
+        &self,
+        reporter_id: Uuid,
+        payload: CreateReportRequest,
+    ) -> Result<Report, ApiError> {
+        let target = self
+            .get_target(payload.target_type, payload.target_id)
+            .await?;
+        if target.author_id == reporter_id {
+            return Err(DbError::Str("You can't report your own content".to_string()).into());
+        }
+        if payload.target_type == ReportTargetType::BountyChat
+            && target.recipient_id != Some(reporter_id)
+        {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        if target.moderation_status == ModerationStatus::Removed {
+            return Err(DbError::Str("Content was already removed".to_string()).into());
+        }
+        let report = self
+            .report_repo
+            .create_report(
+                reporter_id,
+                payload.target_type,
+                payload.target_id,
+                payload.reason,
+                payload.details.filter(|d| !d.trim().is_empty()),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .ok_or(DbError::Str("You already reported this".to_string()))?;
+        if self.auto_hide_threshold > 0 {
+            self.report_repo
+                .auto_hide(
+                    payload.target_type,
+                    payload.target_id,
+                    self.auto_hide_threshold,
+                )
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+        }
+        Ok(report)
+    }
+
+    pub async fn get_moderation_queue(
+        &self,
+        opts: GetModerationQueueOption,
+    ) -> Result<Vec<ModerationQueueItem>, ApiError> {
+        let status = opts.status.unwrap_or(ReportStatus::Open);
+        let mut items = self
+            .report_repo
+            .get_moderation_queue(status, opts.target_type, opts.offset, opts.limit)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let targets: Vec<(ReportTargetType, Uuid)> = items
+            .iter()
+            .map(|item| (item.target_type, item.target_id))
+            .collect();
+        let reports = self
+            .report_repo
+            .get_reports(&targets, status)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+
+        let reporter_ids: Vec<Uuid> = reports.iter().map(|r| r.reporter_id).collect();
+        let users: HashMap<Uuid, UserInfo> = self
+            .user_repo
+            .get_users_by_ids(&reporter_ids)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .iter()
+            .map(|u| (u.id, u.to_info()))
+            .collect();
+        let mut infos: HashMap<(ReportTargetType, Uuid), Vec<ReportInfo>> = HashMap::new();
+        for report in reports {
+            let Some(reporter) = users.get(&report.reporter_id) else {
+                continue;
+            };
+            infos
+                .entry((report.target_type, report.target_id))
+                .or_default()
+                .push(report.to_info(reporter.clone()));
+        }
+        for item in items.iter_mut() {
+            item.reports = infos
+                .remove(&(item.target_type, item.target_id))
+                .unwrap_or_default();
+        }
+        Ok(items)
+    }
+
+    /// Applies an admin decision to reported content. Hiding or removing it
+    /// resolves the open reports, restoring it dismisses them. Removed
+    /// content stays removed. Returns the content as it was before.
+    pub async fn moderate_content(
+        &self,
+        admin_id: Uuid,
+        payload: ModerateContentRequest,
+    ) -> Result<ReportTarget, ApiError> {
+        let target = self
+            .get_target(payload.target_type, payload.target_id)
+            .await?;
+        if target.moderation_status == ModerationStatus::Removed {
+            return Err(DbError::Str("Content was already removed".to_string()).into());
+        }
+        let (moderation_status, action, report_status) = match payload.action {
+            ModerateContentAction::Hide => (
+                ModerationStatus::Hidden,
+                ModerationAction::Hide,
+                ReportStatus::Resolved,
+            ),
+            ModerateContentAction::Restore => (
+                ModerationStatus::Visible,
+                ModerationAction::Restore,
+                ReportStatus::Dismissed,
+            ),
+            ModerateContentAction::Remove => (
+                ModerationStatus::Removed,
+                ModerationAction::Remove,
+                ReportStatus::Resolved,
+            ),
+        };
+        let changed = self
+            .report_repo
+            .moderate(
+                admin_id,
+                payload.target_type,
+                payload.target_id,
+                moderation_status,
+                action,
+                payload.note.filter(|n| !n.trim().is_empty()),
+                report_status,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        if !changed {
+            return Err(DbError::Str(format!("{:?} not found", payload.target_type)).into());
+        }
+        Ok(target)
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{
+    pool::DatabasePool, NotificationService, ProjectRepository, ReviewRepository, UserRepository,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::HashMap, sync::Arc};
+use types::{
+    dto::SubmitProjectReviewRequest,
+    error::{ApiError, DbError},
+    models::{
+        Project, ProjectReview, ProjectReviewInfo, ProjectReviewStatus, ProjectReviewSummary,
+        ProjectReviewsResponse, User, UserInfo,
+    },
+    ProjectStatus, UserRoleType,
+};
+use utils::{commons::uuid_from_str, env::Env};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ReviewService {
+    review_repo: ReviewRepository,
+    project_repo: ProjectRepository,
+    user_repo: UserRepository,
+    notification: NotificationService,
+    review_deadline: Duration,
+    review_reminder_before: Duration,
+}
+
+impl ReviewService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env, notification: &NotificationService) -> Self {
+        Self {
+            review_repo: ReviewRepository::new(db_conn),
+            project_repo: ProjectRepository::new(db_conn),
+            user_repo: UserRepository::new(db_conn),
+            notification: notification.clone(),
+            review_deadline: env.review_deadline,
+            review_reminder_before: env.review_reminder_before,
+        }
+    }
+
+    async fn get_project_under_review(&self, id: Uuid) -> Result<Project, ApiError> {
+        let project = self
+            .project_repo
+            .get_project_by_id(id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        if ProjectStatus::from(project.status) != ProjectStatus::UnderReview {
+            return Err(DbError::Str("Project is not under review".to_string()).into());
+        }
+        Ok(project)
+    }
+
+    async fn check_handling_editor(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(), ApiError> {
+        if !is_admin
+            && !self
+                .review_repo
+                .is_handling_editor(project_id, user_id)
+                .await
+        {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        Ok(())
+    }
+
+    pub async fn invite_reviewer(
+        &self,
+        id: &str,
+        reviewer_id: Uuid,
+        due_at: Option<DateTime<Utc>>,
+        inviter: &User,
+        is_admin: bool,
+    ) -> Result<ProjectReviewInfo, ApiError> {
+        let id = uuid_from_str(id)?;
+        let project = self.get_project_under_review(id).await?;
+        self.check_handling_editor(id, inviter.id, is_admin).await?;
+        let reviewer = self
+            .user_repo
+            .get_user_by_id(reviewer_id)
+            .await
+            .ok_or(DbError::Str("Reviewer not found".to_string()))?;
+        if !reviewer.roles.contains(&UserRoleType::Editor.to_string()) {
+            return Err(DbError::Str("Reviewer must be an editor".to_string()).into());
+        }
+        if reviewer.id == project.user_id
+            || self.review_repo.is_handling_editor(id, reviewer.id).await
+        {
+            return Err(DbError::Str("Reviewer has a conflict of interest".to_string()).into());
+        }
+        let due_at = due_at.unwrap_or_else(|| Utc::now() + self.review_deadline);
+        if due_at <= Utc::now() {
+            return Err(DbError::Str("Review deadline must be in the future".to_string()).into());
+        }
+        let review = self
+            .review_repo
+            .create_review(id, reviewer.id, inviter.id, due_at)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+            .ok_or(DbError::Str("Reviewer already invited".to_string()))?;
+        let _ = self
+            .notification
+            .notify_review_request(
+                reviewer.id,
+                id,
+                &project.title.unwrap_or_default(),
+                &inviter.name.clone().unwrap_or_default(),
+                due_at,
+            )
+            .await;
+        Ok(review.to_info(reviewer.to_info()))
+    }
+
+    pub async fn cancel_reviewer(
+        &self,
+        id: &str,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<bool, ApiError> {
+        let id = uuid_from_str(id)?;
+        self.check_handling_editor(id, user_id, is_admin).await?;
+        let res = self
+            .review_repo
+            .cancel_review(id, reviewer_id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        if !res {
+            return Err(DbError::Str("Pending review not found".to_string()).into());
+        }
+        Ok(res)
+    }
+
+    pub async fn submit_review(
+        &self,
+        id: &str,
+        reviewer: &User,
+        payload: SubmitProjectReviewRequest,
+    ) -> Result<ProjectReviewInfo, ApiError> {
+        let id = uuid_from_str(id)?;
+        let project = self.get_project_under_review(id).await?;
+        if !self
+            .review_repo
+            .submit_review(id, reviewer.id, &payload)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?
+        {
+            return Err(DbError::Str("Pending review not found".to_string()).into());
+        }
+        let review = self
+            .review_repo
+            .get_review(id, reviewer.id)
+            .await
+            .ok_or(DbError::Str("Review not found".to_string()))?;
+        let editor_ids = self
+            .review_repo
+            .get_handling_editor_ids(id)
+            .await
+            .unwrap_or_default();
+        let _ = self
+            .notification
+            .notify_review_submitted(
+                editor_ids,
+                id,
+                &project.title.unwrap_or_default(),
+                &reviewer.name.clone().unwrap_or_default(),
+            )
+            .await;
+        Ok(review.to_info(reviewer.to_info()))
+    }
+
+    /// Admins and handling editors get every review with the summary; a
+    /// reviewer only sees their own review.
+    pub async fn get_project_reviews(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<ProjectReviewsResponse, ApiError> {
+        let id = uuid_from_str(id)?;
+        if is_admin || self.review_repo.is_handling_editor(id, user_id).await {
+            let reviews = self
+                .review_repo
+                .get_reviews(id)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+            let summary = ProjectReviewSummary::from_reviews(&reviews);
+            return Ok(ProjectReviewsResponse {
+                reviews: self.reviews_to_info(reviews).await,
+                summary: Some(summary),
+            });
+        }
+        let review = self
+            .review_repo
+            .get_review(id, user_id)
+            .await
+            .ok_or(DbError::Str("No permission".to_string()))?;
+        Ok(ProjectReviewsResponse {
+            reviews: self.reviews_to_info(vec![review]).await,
+            summary: None,
+        })
+    }
+
+    pub async fn get_assigned_reviews(
+        &self,
+        user_id: Uuid,
+        status: Option<i16>,
+        offset: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ProjectReviewInfo>, ApiError> {
+        let status = status
+            .map(ProjectReviewStatus::try_from)
+            .transpose()
+            .map_err(|_| DbError::Str("Invalid review status".to_string()))?;
+        let reviews = self
+            .review_repo
+            .get_reviews_by_reviewer(
+                user_id,
+                status,
+                offset.unwrap_or(0),
+                limit.unwrap_or(20).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(self.reviews_to_info(reviews).await)
+    }
+
+    async fn reviews_to_info(&self, reviews: Vec<ProjectReview>) -> Vec<ProjectReviewInfo> {
+        let reviewer_ids: Vec<Uuid> = reviews.iter().map(|r| r.reviewer_id).collect();
+        let reviewers: HashMap<Uuid, UserInfo> = self
+            .user_repo
+            .get_users_by_ids(&reviewer_ids)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|u| (u.id, u.to_info()))
+            .collect();
+        reviews
+            .into_iter()
+            .filter_map(|review| {
+                let reviewer = reviewers.get(&review.reviewer_id)?.clone();
+                Some(review.to_info(reviewer))
+            })
+            .collect()
+    }
+
+    /// Reminds reviewers of upcoming deadlines, then flags overdue reviews to
+    /// the reviewer and the handling editors. Each review is notified once per stage.
+    pub async fn send_review_reminders(&self) -> Result<usize, ApiError> {
+        let now = Utc::now();
+        let reminders = self
+            .review_repo
+            .claim_review_reminders(now, now + self.review_reminder_before)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let overdue = self
+            .review_repo
+            .claim_overdue_reviews(now)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let count = reminders.len() + overdue.len();
+        for reminder in reminders {
+            let _ = self
+                .notification
+                .notify_review_reminder(
+                    reminder.reviewer_id,
+                    reminder.project_id,
+                    &reminder.project_title.unwrap_or_default(),
+                    reminder.due_at,
+                    false,
+                )
+                .await;
+        }
+        for reminder in overdue {
+            let project_title = reminder.project_title.unwrap_or_default();
+            let _ = self
+                .notification
+                .notify_review_reminder(
+                    reminder.reviewer_id,
+                    reminder.project_id,
+                    &project_title,
+                    reminder.due_at,
+                    true,
+                )
+                .await;
+            let editor_ids = self
+                .review_repo
+                .get_handling_editor_ids(reminder.project_id)
+                .await
+                .unwrap_or_default();
+            let _ = self
+                .notification
+                .notify_review_overdue(
+                    editor_ids,
+                    reminder.project_id,
+                    &project_title,
+                    &reminder.reviewer_name.unwrap_or_default(),
+                    reminder.due_at,
+                )
+                .await;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{pool::DatabasePool, SearchRepository};
+use std::sync::Arc;
+use types::{
+    dto::SearchOption,
+    error::{ApiError, DbError},
+    models::{SearchEntityType, SearchResponse},
+};
+
+const DEFAULT_LIMIT: i32 = 10;
+const DEFAULT_TYPEAHEAD_LIMIT: i32 = 5;
+const MAX_LIMIT: i32 = 50;
+const MAX_QUERY_LENGTH: usize = 100;
+
+#[derive(Clone)]
+pub struct SearchService {
+    search_repo: SearchRepository,
+}
+
+impl SearchService {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            search_repo: SearchRepository::new(db_conn),
+        }
+    }
+
+    /// Searches every requested type and groups the results by type, each
+    /// group ranked best first
+    pub async fn search(&self, opts: SearchOption) -> Result<SearchResponse, ApiError> {
+        let text: String = opts.q.trim().chars().take(MAX_QUERY_LENGTH).collect();
+        if text.is_empty() {
+            return Ok(SearchResponse::default());
+        }
+        let types = match opts.types.as_ref().filter(|t| !t.trim().is_empty()) {
+            Some(types) => types
+                .split(',')
+                .map(|t| t.parse::<SearchEntityType>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DbError::Str)?,
+            None => SearchEntityType::ALL.to_vec(),
+        };
+        let typeahead = opts.typeahead.unwrap_or_default();
+        let default_limit = if typeahead {
+            DEFAULT_TYPEAHEAD_LIMIT
+        } else {
+            DEFAULT_LIMIT
+        };
+        let limit = opts.limit.unwrap_or(default_limit).clamp(1, MAX_LIMIT);
+        let with_snippet = !typeahead;
+        let tsquery = prefix_tsquery(&text);
+        let tsquery = tsquery.as_deref();
+
+        let mut res = SearchResponse::default();
+        for search_type in types {
+            let results = match search_type {
+                SearchEntityType::Project => {
+                    self.search_repo
+                        .search_projects(&text, tsquery, limit, with_snippet)
+                        .await
+                }
+                SearchEntityType::Bounty => {
+                    self.search_repo
+                        .search_bounties(&text, tsquery, limit, with_snippet)
+                        .await
+                }
+                SearchEntityType::Dao => {
+                    self.search_repo
+                        .search_daos(&text, tsquery, limit, with_snippet)
+                        .await
+                }
+                SearchEntityType::Prediction => {
+                    self.search_repo
+                        .search_predictions(&text, limit, with_snippet)
+                        .await
+                }
+                SearchEntityType::User => self.search_repo.search_users(&text, limit).await,
+                SearchEntityType::Category => {
+                    self.search_repo.search_categories(&text, limit).await
+                }
+            }
+            .map_err(|e| DbError::Str(e.to_string()))?;
+            match search_type {
+                SearchEntityType::Project => res.projects = results,
+                SearchEntityType::Bounty => res.bounties = results,
+                SearchEntityType::Dao => res.daos = results,
+                SearchEntityType::Prediction => res.predictions = results,
+                SearchEntityType::User => res.users = results,
+                SearchEntityType::Category => res.categories = results,
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Builds a tsquery that requires every word and matches the last one as a
+/// prefix, so results show up while the user is still typing. Returns `None`
+/// when nothing searchable is left after dropping punctuation.
+fn prefix_tsquery(text: &str) -> Option<String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("{}:*", words.join(" & ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_punctuation_only_input() {
+        assert_eq!(prefix_tsquery("?! -- ..."), None);
+        assert_eq!(prefix_tsquery("   "), None);
+    }
+
+    #[test]
+    fn requires_every_word_and_prefixes_the_last() {
+        assert_eq!(
+            prefix_tsquery("quantum  dot, sens"),
+            Some("quantum & dot & sens:*".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_non_ascii_words() {
+        assert_eq!(
+            prefix_tsquery("Größe café 量子"),
+            Some("Größe & café & 量子:*".to_string())
+        );
+    }
+}
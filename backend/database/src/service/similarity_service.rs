@@ -0,0 +1,186 @@
+use crate::{pool::DatabasePool, ProjectRepository, SimilarityRepository};
+use std::sync::Arc;
+use types::{
+    dto::{SimilarityReindexCursor, SimilarityReindexResponse, SimilarityReindexStep},
+    error::{ApiError, DbError},
+    models::{SimilarityEntityType, SimilarityMatchInfo},
+};
+use utils::commons::uuid_from_str;
+use uuid::Uuid;
+
+/// Matches below this estimated Jaccard similarity are not kept
+const MIN_SIMILARITY_SCORE: f64 = 0.5;
+const MAX_SIMILARITY_MATCHES: usize = 10;
+/// Items fingerprinted or checked per reindex call
+const SIMILARITY_REINDEX_BATCH_SIZE: usize = 200;
+
+/// Fingerprints project and bounty texts with MinHash to catch resubmitted
+/// or copied proposals
+#[derive(Clone)]
+pub struct SimilarityService {
+    similarity_repo: SimilarityRepository,
+    project_repo: ProjectRepository,
+}
+
+impl SimilarityService {
+    pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
+        Self {
+            similarity_repo: SimilarityRepository::new(db_conn),
+            project_repo: ProjectRepository::new(db_conn),
+        }
+    }
+
+    pub async fn index_bounty(&self, id: Uuid) -> Result<(), ApiError> {
+        let source = self
+            .similarity_repo
+            .get_bounty_source(id)
+            .await
+            .ok_or(DbError::Str("Bounty not found".to_string()))?;
+        if let Some(minhash) = source.to_minhash() {
+            self.similarity_repo
+                .upsert_fingerprint(SimilarityEntityType::Bounty, id, &minhash)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Fingerprints the project and stores the projects and bounties that
+    /// resemble it
+    pub async fn check_project(&self, id: Uuid) -> Result<Vec<SimilarityMatchInfo>, ApiError> {
+        let source = self
+            .similarity_repo
+            .get_project_source(id)
+            .await
+            .ok_or(DbError::Str("Project not found".to_string()))?;
+        let mut matches = Vec::new();
+        if let Some(minhash) = source.to_minhash() {
+            self.similarity_repo
+                .upsert_fingerprint(SimilarityEntityType::Project, id, &minhash)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+            let candidates = self
+                .similarity_repo
+                .get_candidates(SimilarityEntityType::Project, id, &minhash)
+                .await
+                .map_err(|e| DbError::Str(e.to_string()))?;
+            matches = candidates
+                .into_iter()
+                .map(|c| (c.entity_type, c.entity_id, minhash.similarity(&c.signature)))
+                .filter(|m| m.2 >= MIN_SIMILARITY_SCORE)
+                .collect();
+            matches.sort_by(|a, b| b.2.total_cmp(&a.2));
+            matches.truncate(MAX_SIMILARITY_MATCHES);
+        }
+        self.similarity_repo
+            .replace_matches(id, &matches)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        self.similarity_repo
+            .get_matches(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Admins and the editors assigned to the project
+    pub async fn get_project_matches(
+        &self,
+        id: &str,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<SimilarityMatchInfo>, ApiError> {
+        let id = uuid_from_str(id)?;
+        if !is_admin && !self.project_repo.is_project_editor(id, user_id).await {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        self.similarity_repo
+            .get_matches(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Rebuilds the fingerprints of up to `limit` projects or bounties after
+    /// `after`. Returns their ids and how many had text to fingerprint.
+    async fn fingerprint_batch(
+        &self,
+        entity_type: SimilarityEntityType,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<(Vec<Uuid>, usize), ApiError> {
+        let sources = match entity_type {
+            SimilarityEntityType::Project => {
+                self.similarity_repo.get_project_sources(after, limit).await
+            }
+            SimilarityEntityType::Bounty => {
+                self.similarity_repo.get_bounty_sources(after, limit).await
+            }
+        }
+        .map_err(|e| DbError::Str(e.to_string()))?;
+        let mut count = 0;
+        for source in &sources {
+            if let Some(minhash) = source.to_minhash() {
+                self.similarity_repo
+                    .upsert_fingerprint(entity_type, source.id, &minhash)
+                    .await
+                    .map_err(|e| DbError::Str(e.to_string()))?;
+                count += 1;
+            }
+        }
+        Ok((sources.iter().map(|s| s.id).collect(), count))
+    }
+
+    /// Works through one batch of the reindex starting at `from`: rebuilds
+    /// the fingerprints of projects and bounties, then recomputes the matches
+    /// of the projects in review. `next` tells where the following batch
+    /// starts.
+    pub async fn reindex(
+        &self,
+        from: SimilarityReindexCursor,
+    ) -> Result<SimilarityReindexResponse, ApiError> {
+        let mut res = SimilarityReindexResponse::default();
+        let mut cursor = from;
+        let mut remaining = SIMILARITY_REINDEX_BATCH_SIZE;
+        while remaining > 0 {
+            let limit = remaining as i64;
+            let ids = match cursor.step {
+                SimilarityReindexStep::Projects => {
+                    let (ids, count) = self
+                        .fingerprint_batch(SimilarityEntityType::Project, cursor.after, limit)
+                        .await?;
+                    res.projects += count;
+                    ids
+                }
+                SimilarityReindexStep::Bounties => {
+                    let (ids, count) = self
+                        .fingerprint_batch(SimilarityEntityType::Bounty, cursor.after, limit)
+                        .await?;
+                    res.bounties += count;
+                    ids
+                }
+                SimilarityReindexStep::Matches => {
+                    let ids = self
+                        .similarity_repo
+                        .get_project_ids_in_review(cursor.after, limit)
+                        .await
+                        .map_err(|e| DbError::Str(e.to_string()))?;
+                    for id in &ids {
+                        self.check_project(*id).await?;
+                        res.checked_projects += 1;
+                    }
+                    ids
+                }
+            };
+            remaining -= ids.len();
+            if ids.len() < limit as usize {
+                match cursor.step.next() {
+                    Some(step) => cursor = SimilarityReindexCursor { step, after: None },
+                    None => return Ok(res),
+                }
+            } else {
+                cursor.after = ids.last().copied();
+            }
+        }
+        res.next = Some(cursor);
+        Ok(res)
+    }
+}
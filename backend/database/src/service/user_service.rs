@@ -348,6 +348,7 @@ impl UserService {
                 funding_updates: user.funding_updates,
                 dao_proposals: user.dao_proposals,
                 prediction_markets: user.prediction_markets,
+                archive_notifications: user.archive_notifications,
             },
             privacy: UserPrivacySettingsResponse {
                 profile_visibility: user.profile_visibility,
@@ -420,6 +421,7 @@ impl UserService {
                 payload.funding_updates,
                 payload.dao_proposals,
                 payload.prediction_markets,
+                payload.archive_notifications,
             )
             .await
             .map_err(|_| DbError::Str("Failed to update notification settings".to_string()))?;
@@ -431,6 +433,7 @@ impl UserService {
             funding_updates: user.funding_updates,
             dao_proposals: user.dao_proposals,
             prediction_markets: user.prediction_markets,
+            archive_notifications: user.archive_notifications,
         })
     }
 
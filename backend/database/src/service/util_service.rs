@@ -1,4 +1,8 @@
-use crate::{pool::DatabasePool, repository::UtilRepository};
+use crate::{
+    pool::DatabasePool,
+    repository::{TrendingRepository, UtilRepository},
+};
+use chrono::{Duration, Utc};
 use serde_json::Value;
 use std::{str::FromStr, sync::Arc};
 use types::{
@@ -14,12 +18,14 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct UtilService {
     util_repo: UtilRepository,
+    trending_repo: TrendingRepository,
 }
 
 impl UtilService {
     pub fn new(db_conn: &Arc<DatabasePool>) -> Self {
         Self {
             util_repo: UtilRepository::new(db_conn),
+            trending_repo: TrendingRepository::new(db_conn),
         }
     }
 
@@ -222,6 +228,44 @@ impl UtilService {
         Ok(self.util_repo.delete_category(id).await)
     }
 
+    /// Recomputes the trending score of every project, bounty, DAO and
+    /// prediction from the activity within `window`, then drops the views
+    /// that fell out of it. Returns the number of rows whose score changed.
+    pub async fn refresh_trending_scores(
+        &self,
+        window: Duration,
+        half_life: Duration,
+    ) -> Result<u64, ApiError> {
+        let since = Utc::now() - window;
+        let half_life_hours = (half_life.num_minutes() as f64 / 60.0).max(1.0);
+        let mut updated = 0;
+        updated += self
+            .trending_repo
+            .refresh_projects(since, half_life_hours)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        updated += self
+            .trending_repo
+            .refresh_bounties(since, half_life_hours)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        updated += self
+            .trending_repo
+            .refresh_daos(since, half_life_hours)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        updated += self
+            .trending_repo
+            .refresh_predictions(since, half_life_hours)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        self.trending_repo
+            .prune_view_events(since)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(updated)
+    }
+
     pub async fn get_last_block_number(&self) -> Result<Option<String>, ApiError> {
         self.util_repo
             .get_last_block_number()
@@ -0,0 +1,296 @@
+use std::sync::Arc;
+
+use crate::{DatabasePool, WebhookRepository};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use third_party_api::webhook::{check_webhook_url, WebhookSender};
+use types::{
+    dto::{CreateWebhookEndpointRequest, GetWebhookDeliveriesOption, UpdateWebhookEndpointRequest},
+    error::{ApiError, DbError},
+    models::{
+        WebhookDeliveryInfo, WebhookEndpoint, WebhookEndpointInfo, WebhookEvent, WebhookOutboxItem,
+    },
+};
+use utils::{commons::generate_webhook_secret, env::Env};
+use uuid::Uuid;
+
+/// How long a claimed delivery stays invisible to other senders
+const WEBHOOK_LEASE_MINUTES: i64 = 5;
+const WEBHOOK_MAX_RETRY_DELAY_HOURS: i64 = 24;
+
+#[derive(Clone)]
+pub struct WebhookService {
+    repository: WebhookRepository,
+    sender: WebhookSender,
+    max_attempts: i32,
+    retry_base: Duration,
+    batch_size: i64,
+}
+
+impl WebhookService {
+    pub fn new(db_conn: &Arc<DatabasePool>, env: &Env) -> Self {
+        Self {
+            repository: WebhookRepository::new(db_conn),
+            sender: WebhookSender::new().expect("Failed to build the webhook client"),
+            max_attempts: env.webhook_max_attempts,
+            retry_base: env.webhook_retry_base,
+            batch_size: env.webhook_batch_size,
+        }
+    }
+
+    /// Registers an endpoint. Endpoints registered by admins are global and
+    /// receive events for every resource. The secret is only returned here.
+    pub async fn create_endpoint(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+        payload: CreateWebhookEndpointRequest,
+    ) -> Result<WebhookEndpointInfo, ApiError> {
+        Self::check_url(&payload.url).await?;
+        let secret = generate_webhook_secret();
+        let endpoint = self
+            .repository
+            .create_endpoint(
+                user_id,
+                &payload.url,
+                &secret,
+                &Self::event_ids(&payload.events),
+                is_admin,
+                payload.description,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let mut info = endpoint.to_info();
+        info.secret = Some(endpoint.secret);
+        Ok(info)
+    }
+
+    pub async fn get_endpoints(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<WebhookEndpointInfo>, ApiError> {
+        let endpoints = self
+            .repository
+            .get_endpoints(if is_admin { None } else { Some(user_id) })
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(endpoints.iter().map(|e| e.to_info()).collect())
+    }
+
+    pub async fn update_endpoint(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+        payload: UpdateWebhookEndpointRequest,
+    ) -> Result<WebhookEndpointInfo, ApiError> {
+        self.get_owned_endpoint(id, user_id, is_admin).await?;
+        if let Some(url) = &payload.url {
+            Self::check_url(url).await?;
+        }
+        let endpoint = self
+            .repository
+            .update_endpoint(
+                id,
+                payload.url,
+                payload.events.as_deref().map(Self::event_ids),
+                payload.is_active,
+                payload.description,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(endpoint.to_info())
+    }
+
+    pub async fn rotate_endpoint_secret(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<WebhookEndpointInfo, ApiError> {
+        self.get_owned_endpoint(id, user_id, is_admin).await?;
+        let endpoint = self
+            .repository
+            .rotate_endpoint_secret(id, &generate_webhook_secret())
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let mut info = endpoint.to_info();
+        info.secret = Some(endpoint.secret);
+        Ok(info)
+    }
+
+    pub async fn delete_endpoint(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<bool, ApiError> {
+        self.get_owned_endpoint(id, user_id, is_admin).await?;
+        self.repository
+            .delete_endpoint(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    pub async fn get_deliveries(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+        opts: GetWebhookDeliveriesOption,
+    ) -> Result<Vec<WebhookDeliveryInfo>, ApiError> {
+        let deliveries = self
+            .repository
+            .get_deliveries(
+                if is_admin { None } else { Some(user_id) },
+                opts.endpoint_id,
+                opts.event,
+                opts.status,
+                opts.offset.unwrap_or(0),
+                opts.limit.unwrap_or(20).min(100),
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(deliveries.iter().map(|d| d.to_info()).collect())
+    }
+
+    pub async fn retry_delivery(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<WebhookDeliveryInfo, ApiError> {
+        let delivery = self
+            .repository
+            .get_delivery_by_id(id)
+            .await
+            .ok_or(DbError::Str("Webhook delivery not found".to_string()))?;
+        self.get_owned_endpoint(delivery.endpoint_id, user_id, is_admin)
+            .await?;
+        let delivery = self
+            .repository
+            .retry_delivery(id)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(delivery.to_info())
+    }
+
+    /// Queues `event` in the outbox for every subscribed endpoint. `owner_ids`
+    /// are the users the event concerns (e.g. the project or bounty owner).
+    pub async fn enqueue(
+        &self,
+        event: WebhookEvent,
+        owner_ids: &[Uuid],
+        data: Value,
+    ) -> Result<u64, ApiError> {
+        let payload = json!({
+            "event": event.name(),
+            "createdAt": Utc::now(),
+            "data": data,
+        });
+        self.repository
+            .enqueue_deliveries(event, owner_ids, &payload)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// Sends every due delivery in the outbox and returns how many were
+    /// attempted. Failures are rescheduled with exponential backoff until
+    /// `max_attempts` is reached. A delivery that cannot be stored does not
+    /// stop the rest of the batch, its lease expires and it is sent again.
+    pub async fn deliver_due(&self) -> Result<usize, ApiError> {
+        let now = Utc::now();
+        let items = self
+            .repository
+            .claim_due_deliveries(
+                now,
+                now + Duration::minutes(WEBHOOK_LEASE_MINUTES),
+                self.batch_size,
+            )
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        let count = items.len();
+        for item in items {
+            let id = item.id;
+            if let Err(e) = self.deliver(item).await {
+                println!("Failed to store webhook delivery {}: {:?}", id, e);
+            }
+        }
+        Ok(count)
+    }
+
+    async fn deliver(&self, item: WebhookOutboxItem) -> Result<(), ApiError> {
+        let result = self
+            .sender
+            .send(
+                &item.url,
+                &item.secret,
+                item.event.name(),
+                &item.id.to_string(),
+                Utc::now().timestamp(),
+                item.payload.to_string(),
+            )
+            .await;
+        let (status_code, error) = match result {
+            Ok(code) if (200..300).contains(&code) => {
+                return self
+                    .repository
+                    .mark_delivery_succeeded(item.id, code as i32)
+                    .await
+                    .map_err(|e| DbError::Str(e.to_string()).into());
+            }
+            Ok(code) => (
+                Some(code as i32),
+                format!("Receiver responded with {}", code),
+            ),
+            Err(e) => (None, e.to_string()),
+        };
+        let attempts = item.attempts + 1;
+        let next_attempt_at =
+            (attempts < self.max_attempts).then(|| Utc::now() + self.retry_delay(attempts));
+        self.repository
+            .mark_delivery_failed(item.id, status_code, &error, next_attempt_at)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()).into())
+    }
+
+    /// `retry_base * 2^(attempts - 1)`, capped at a day
+    fn retry_delay(&self, attempts: i32) -> Duration {
+        let factor = 2i32.saturating_pow(attempts.saturating_sub(1).max(0) as u32);
+        let max = Duration::hours(WEBHOOK_MAX_RETRY_DELAY_HOURS);
+        self.retry_base
+            .checked_mul(factor)
+            .map_or(max, |delay| delay.min(max))
+    }
+
+    async fn check_url(url: &str) -> Result<(), ApiError> {
+        check_webhook_url(url)
+            .await
+            .map_err(|e| DbError::Str(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_owned_endpoint(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<WebhookEndpoint, ApiError> {
+        let endpoint = self
+            .repository
+            .get_endpoint_by_id(id)
+            .await
+            .ok_or(DbError::Str("Webhook endpoint not found".to_string()))?;
+        if !is_admin && endpoint.user_id != user_id {
+            return Err(DbError::Str("No permission".to_string()).into());
+        }
+        Ok(endpoint)
+    }
+
+    fn event_ids(events: &[WebhookEvent]) -> Vec<i16> {
+        let mut ids: Vec<i16> = events.iter().map(|e| (*e).into()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
@@ -0,0 +1,14 @@
+use database::AppService;
+use std::sync::Arc;
+
+pub async fn run(service: Arc<AppService>) -> Result<(), anyhow::Error> {
+    let count = service.ai_review.run_due_reviews().await?;
+    if count > 0 {
+        println!("attempted ai reviews: {}", count);
+    }
+    let count = service.manuscript_check.run_pending_checks().await?;
+    if count > 0 {
+        println!("attempted manuscript error checks: {}", count);
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use database::AppService;
+use std::sync::Arc;
+
+pub async fn run(service: Arc<AppService>) -> Result<(), anyhow::Error> {
+    let count = service.notification.send_due_announcements().await?;
+    if count > 0 {
+        println!("sent announcements: {}", count);
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use database::AppService;
 use evm::{DAO_CONTRACTEvents, EVMClient, FUNDING_CONTRACTEvents};
 use std::sync::Arc;
+use types::models::{Dao, FollowEntityType, FundingRefund, Project};
 use utils::env::Env;
 
 pub async fn run(
@@ -50,11 +51,27 @@ pub async fn run(
                     .ok();
             }
             DAO_CONTRACTEvents::ProjectApprovedFilter(ev) => {
+                let proposal_id = ev.project_id.as_u64() as i64;
+                let project = service
+                    .project
+                    .get_project_by_proposal_id(proposal_id)
+                    .await;
+                let active_dao = match &project {
+                    Some(project) => service
+                        .project
+                        .get_dao_by_project_id(project.id)
+                        .await
+                        .filter(|dao| dao.status == 0),
+                    None => None,
+                };
                 service
                     .project
-                    .finished_dao(ev.project_id.as_u64() as i64, ev.proceeded_to_funding)
+                    .finished_dao(proposal_id, ev.proceeded_to_funding)
                     .await
                     .ok();
+                if let Some(dao) = active_dao {
+                    notify_dao_outcome(&service, dao, ev.proceeded_to_funding).await;
+                }
             }
             _ => {}
         }
@@ -66,7 +83,7 @@ pub async fn run(
         .await?;
 
     // println!("Funding contract events: {:?}", events);
-    for event in &events {
+    for (event, meta) in &events {
         match event {
             FUNDING_CONTRACTEvents::DonatedFilter(ev) => {
                 service
@@ -79,6 +96,25 @@ pub async fn run(
                     .await
                     .ok();
             }
+            FUNDING_CONTRACTEvents::RefundedFilter(ev) => {
+                let wallet = format!("{:?}", ev.donor);
+                match service
+                    .project
+                    .refund_funding(
+                        ev.project_id.as_u64() as i64,
+                        &wallet,
+                        ev.amount.as_u128(),
+                        &format!("{:?}", meta.transaction_hash),
+                        meta.log_index.as_u64() as i64,
+                    )
+                    .await
+                {
+                    Ok((project, refunds)) => {
+                        notify_funding_refunded(&service, &wallet, project, &refunds).await
+                    }
+                    Err(e) => println!("Failed to store refund of {}: {:?}", wallet, e),
+                }
+            }
             FUNDING_CONTRACTEvents::PredictionPlacedFilter(ev) => {
                 if let Err(e) = service
                     .prediction_placement
@@ -109,6 +145,33 @@ pub async fn run(
                     .milestone_finalized(project_id, milestone_index, success)
                     .await
                     .ok();
+                notify_milestone_finalized(&service, project_id, milestone_index, success).await;
+            }
+            FUNDING_CONTRACTEvents::MilestoneApprovedByAdminFilter(ev) => {
+                if let Err(e) = service
+                    .project
+                    .milestone_claimable(
+                        ev.project_id.as_u64() as i64,
+                        ev.milestone_index.as_u32() as i16,
+                    )
+                    .await
+                {
+                    println!("Failed to store milestone approval: {:?}", e);
+                }
+            }
+            FUNDING_CONTRACTEvents::ClaimedByResearcherFilter(ev) => {
+                if let Err(e) = service
+                    .project
+                    .milestone_claimed(
+                        ev.project_id.as_u64() as i64,
+                        ev.milestone_index.as_u32() as i16,
+                        &format!("{:?}", meta.transaction_hash),
+                        ev.amount.as_u128(),
+                    )
+                    .await
+                {
+                    println!("Failed to store milestone claim: {:?}", e);
+                }
             }
             _ => {}
         }
@@ -163,3 +226,93 @@ pub async fn run(
 
     Ok(())
 }
+
+/// Lets followers of the DAO and of its project know how the vote ended,
+/// once the DAO is no longer active
+async fn notify_dao_outcome(service: &AppService, dao: Dao, passed: bool) {
+    match service.project.get_dao_by_project_id(dao.project_id).await {
+        Some(finished) if finished.status != 0 => {}
+        _ => return,
+    }
+    let followers = service
+        .follow
+        .get_follower_ids(&[
+            (FollowEntityType::Dao, dao.id),
+            (FollowEntityType::Project, dao.project_id),
+        ])
+        .await
+        .unwrap_or_default();
+    if !followers.is_empty() {
+        let _ = service
+            .notification
+            .notify_dao_outcome(followers, dao.id, &dao.title, passed)
+            .await;
+    }
+}
+
+async fn notify_funding_refunded(
+    service: &AppService,
+    wallet: &str,
+    project: Project,
+    refunds: &[FundingRefund],
+) {
+    let amount: i32 = refunds.iter().map(|r| r.amount).sum();
+    if amount == 0 {
+        return;
+    }
+    let Ok(user) = service.user.get_user_by_wallet(wallet).await else {
+        return;
+    };
+    let _ = service
+        .notification
+        .notify_funding_refunded(
+            user.id,
+            project.id,
+            &project.title.unwrap_or(project.nerd_id),
+            &format!("{} NERD", amount),
+        )
+        .await;
+}
+
+async fn notify_milestone_finalized(
+    service: &AppService,
+    proposal_id: i64,
+    milestone_index: i16,
+    success: bool,
+) {
+    let Some(project) = service
+        .project
+        .get_project_by_proposal_id(proposal_id)
+        .await
+    else {
+        return;
+    };
+    let Ok(milestones) = service
+        .project
+        .get_milestones(&project.id.to_string())
+        .await
+    else {
+        return;
+    };
+    // milestone_index is 0-based, milestone numbers start at 1
+    let Some(milestone) = milestones.iter().find(|m| m.number == milestone_index + 1) else {
+        return;
+    };
+    let followers = service
+        .follow
+        .get_follower_ids(&[(FollowEntityType::Project, project.id)])
+        .await
+        .unwrap_or_default();
+    if !followers.is_empty() {
+        let _ = service
+            .notification
+            .notify_milestone_finalized(
+                followers,
+                project.id,
+                &project.title.unwrap_or_default(),
+                &milestone.title,
+                success,
+            )
+            .await;
+    }
+}
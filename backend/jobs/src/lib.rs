@@ -1,4 +1,11 @@
+mod ai_review_job;
+mod announcement_job;
 mod evm_job;
+mod milestone_reminder_job;
+mod notification_retention_job;
+mod review_reminder_job;
+mod trending_job;
+mod webhook_job;
 
 use anyhow::Context;
 use database::{AppService, DatabasePool};
@@ -6,7 +13,7 @@ use evm::EVMClient;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::{Mutex, Notify};
 use tokio_cron_scheduler::{Job, JobScheduler};
-use utils::env::Env;
+use utils::{env::Env, templates::Templates};
 
 pub async fn run() -> Result<(), anyhow::Error> {
     let env = Env::init();
@@ -14,7 +21,8 @@ pub async fn run() -> Result<(), anyhow::Error> {
         .await
         .unwrap_or_else(|e| panic!("Database error: {e}"));
     let db = Arc::new(connection);
-    let service = Arc::new(AppService::init(&db, &env));
+    let templates = Templates::init(&env.templates_dir);
+    let service = Arc::new(AppService::init(&db, &env, &templates));
     let evm_client = Arc::new(EVMClient::init(
         &env.dao_contract_address,
         &env.funding_contract_address,
@@ -98,6 +106,15 @@ pub async fn serve(
     let job_env = env.clone();
     let job_is_running = is_evm_job_running.clone();
     let schedule = env.evm_job_schedule.clone();
+    let announcement_schedule = env.announcement_job_schedule.clone();
+    let retention_schedule = env.notification_retention_job_schedule.clone();
+    let retention_env = env.clone();
+    let webhook_schedule = env.webhook_job_schedule.clone();
+    let review_reminder_schedule = env.review_reminder_job_schedule.clone();
+    let trending_schedule = env.trending_job_schedule.clone();
+    let trending_env = env.clone();
+    let ai_review_schedule = env.ai_review_job_schedule.clone();
+    let milestone_reminder_schedule = env.milestone_reminder_job_schedule.clone();
     let job_evm_client = evm_client.clone();
 
     scheduler
@@ -135,6 +152,120 @@ pub async fn serve(
         .await
         .context("Failed to add evm job to scheduler")?;
 
+    let announcement_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&announcement_schedule, move |_uuid, _l| {
+                let service = announcement_service.clone();
+                Box::pin(async move {
+                    if let Err(err) = announcement_job::run(service).await {
+                        println!("announcement job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create announcement job")?,
+        )
+        .await
+        .context("Failed to add announcement job to scheduler")?;
+
+    let retention_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&retention_schedule, move |_uuid, _l| {
+                let service = retention_service.clone();
+                let env = retention_env.clone();
+                Box::pin(async move {
+                    if let Err(err) = notification_retention_job::run(service, env).await {
+                        println!("notification retention job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create notification retention job")?,
+        )
+        .await
+        .context("Failed to add notification retention job to scheduler")?;
+
+    let webhook_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&webhook_schedule, move |_uuid, _l| {
+                let service = webhook_service.clone();
+                Box::pin(async move {
+                    if let Err(err) = webhook_job::run(service).await {
+                        println!("webhook job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create webhook job")?,
+        )
+        .await
+        .context("Failed to add webhook job to scheduler")?;
+
+    let review_reminder_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&review_reminder_schedule, move |_uuid, _l| {
+                let service = review_reminder_service.clone();
+                Box::pin(async move {
+                    if let Err(err) = review_reminder_job::run(service).await {
+                        println!("review reminder job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create review reminder job")?,
+        )
+        .await
+        .context("Failed to add review reminder job to scheduler")?;
+
+    let trending_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&trending_schedule, move |_uuid, _l| {
+                let service = trending_service.clone();
+                let env = trending_env.clone();
+                Box::pin(async move {
+                    if let Err(err) = trending_job::run(service, env).await {
+                        println!("trending job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create trending job")?,
+        )
+        .await
+        .context("Failed to add trending job to scheduler")?;
+
+    let ai_review_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&ai_review_schedule, move |_uuid, _l| {
+                let service = ai_review_service.clone();
+                Box::pin(async move {
+                    if let Err(err) = ai_review_job::run(service).await {
+                        println!("ai review job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create ai review job")?,
+        )
+        .await
+        .context("Failed to add ai review job to scheduler")?;
+
+    let milestone_reminder_service = service.clone();
+    scheduler
+        .add(
+            Job::new_async(&milestone_reminder_schedule, move |_uuid, _l| {
+                let service = milestone_reminder_service.clone();
+                Box::pin(async move {
+                    if let Err(err) = milestone_reminder_job::run(service).await {
+                        println!("Milestone reminder job failed: {:?}", err);
+                    }
+                })
+            })
+            .context("Failed to create Milestone reminder job")?,
+        )
+        .await
+        .context("Failed to add Milestone reminder job to scheduler")?;
+
     scheduler
         .start()
         .await
@@ -0,0 +1,13 @@
+use database::AppService;
+use std::sync::Arc;
+
+pub async fn run(service: Arc<AppService>) -> Result<(), anyhow::Error> {
+    let (due_soon, overdue) = service.milestone_reminder.send_reminders().await?;
+    if due_soon + overdue > 0 {
+        println!(
+            "milestone reminders: {} due soon, {} overdue",
+            due_soon, overdue
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use database::AppService;
+use std::sync::Arc;
+use utils::env::Env;
+
+pub async fn run(service: Arc<AppService>, env: Env) -> Result<(), anyhow::Error> {
+    let (removed, archived) = service
+        .notification
+        .archive_expired_notifications(
+            env.notification_read_retention,
+            env.notification_unread_retention,
+            env.notification_retention_batch_size,
+        )
+        .await?;
+    if removed > 0 {
+        println!(
+            "notification retention: removed {}, archived {}",
+            removed, archived
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use database::AppService;
+use std::sync::Arc;
+
+pub async fn run(service: Arc<AppService>) -> Result<(), anyhow::Error> {
+    let count = service.review.send_review_reminders().await?;
+    if count > 0 {
+        println!("sent review reminders: {}", count);
+    }
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use database::AppService;
+use std::sync::Arc;
+use utils::env::Env;
+
+pub async fn run(service: Arc<AppService>, env: Env) -> Result<(), anyhow::Error> {
+    let updated = service
+        .util
+        .refresh_trending_scores(env.trending_window, env.trending_half_life)
+        .await?;
+    if updated > 0 {
+        println!("trending: updated {} scores", updated);
+    }
+    Ok(())
+}
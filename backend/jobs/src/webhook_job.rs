@@ -0,0 +1,10 @@
+use database::AppService;
+use std::sync::Arc;
+
+pub async fn run(service: Arc<AppService>) -> Result<(), anyhow::Error> {
+    let count = service.webhook.deliver_due().await?;
+    if count > 0 {
+        println!("attempted webhook deliveries: {}", count);
+    }
+    Ok(())
+}
@@ -156,7 +156,7 @@ impl EVMClient {
         &self,
         from_block_number: Option<u64>,
         to_block_number: Option<u64>,
-    ) -> Result<(Vec<FUNDING_CONTRACTEvents>, Option<u64>), anyhow::Error> {
+    ) -> Result<(Vec<(FUNDING_CONTRACTEvents, LogMeta)>, Option<u64>), anyhow::Error> {
         let events = self.funding_contract.events();
         let to_block_number = to_block_number.unwrap_or(
             self.provider
@@ -171,7 +171,7 @@ impl EVMClient {
         let filtered_events = events
             .from_block(from_block_number)
             .to_block(to_block_number)
-            .query()
+            .query_with_meta()
             .await?;
 
         Ok((filtered_events, Some(to_block_number)))
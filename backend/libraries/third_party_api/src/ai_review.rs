@@ -0,0 +1,441 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use types::{
+    analysis_error::Analysis,
+    dto::{AIReviewRequest, AIReviewResult, ManuscriptFindingResult},
+    models::FindingSeverity,
+    AnalysisErrorType,
+};
+
+/// How long the AI backend gets to review a single project
+pub const REVIEW_TIMEOUT_SECS: u64 = 120;
+/// How long the AI backend gets to check a single manuscript
+pub const ERROR_CHECK_TIMEOUT_SECS: u64 = 300;
+
+#[async_trait]
+pub trait AIReviewClient: Send + Sync {
+    async fn review_project(
+        &self,
+        request: &AIReviewRequest,
+    ) -> Result<AIReviewResult, anyhow::Error>;
+
+    /// Checks the manuscript at `manuscript` (a file URL) for errors
+    async fn check_manuscript(
+        &self,
+        manuscript: &str,
+    ) -> Result<Vec<ManuscriptFindingResult>, anyhow::Error>;
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExternalApiResponse {
+    status: String,
+    data: ExternalReview,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExternalReview {
+    analysis: String,
+    scores: ExternalScores,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExternalScores {
+    objectives: f64,
+    methodology: f64,
+    budget: f64,
+    expertise: f64,
+    innovation: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExternalErrorCheckResponse {
+    status: String,
+    data: ExternalErrorCheck,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ExternalErrorCheck {
+    analysis: Vec<Analysis>,
+}
+
+fn to_score(value: f64) -> i16 {
+    value.round().clamp(0.0, 100.0) as i16
+}
+
+fn text_field(finding: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| finding.get(key).and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+/// Findings come either as plain strings or as objects; unknown analysis
+/// types are dropped
+fn to_findings(analysis: &Analysis) -> Vec<ManuscriptFindingResult> {
+    let Some(error_type) = AnalysisErrorType::from_label(&analysis._type) else {
+        return Vec::new();
+    };
+    let findings = match &analysis.findings {
+        Value::Array(findings) => findings.clone(),
+        Value::Null => Vec::new(),
+        finding => vec![finding.clone()],
+    };
+    findings
+        .iter()
+        .filter_map(|finding| match finding {
+            Value::String(text) => Some(ManuscriptFindingResult {
+                error_type,
+                severity: FindingSeverity::default(),
+                title: String::new(),
+                description: text.clone(),
+                location: None,
+            }),
+            Value::Object(_) => Some(ManuscriptFindingResult {
+                error_type,
+                severity: text_field(finding, &["severity", "level"])
+                    .map(|s| FindingSeverity::from_label(&s))
+                    .unwrap_or_default(),
+                title: text_field(finding, &["title", "error", "issue"]).unwrap_or_default(),
+                description: text_field(finding, &["description", "explanation", "details"])
+                    .unwrap_or_default(),
+                location: text_field(finding, &["location", "section"]),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Talks to the AI backend at `AI_BACKEND_URL`
+#[derive(Clone)]
+pub struct HttpAIReviewClient {
+    base_url: String,
+}
+
+impl HttpAIReviewClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AIReviewClient for HttpAIReviewClient {
+    async fn review_project(
+        &self,
+        request: &AIReviewRequest,
+    ) -> Result<AIReviewResult, anyhow::Error> {
+        if self.base_url.is_empty() {
+            return Err(anyhow::anyhow!("AI_BACKEND_URL is not set"));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REVIEW_TIMEOUT_SECS))
+            .build()?;
+        let response = client
+            .post(format!("{}/api/v1/projects/review", self.base_url))
+            .header("User-Agent", "NerdNuggets-Backend/2.0")
+            .json(request)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "AI backend request failed with status: {}",
+                response.status()
+            ));
+        }
+        let external_response: ExternalApiResponse = response.json().await?;
+        if external_response.status != "success" {
+            return Err(anyhow::anyhow!(
+                "AI backend responded with status: {}",
+                external_response.status
+            ));
+        }
+        let review = external_response.data;
+        Ok(AIReviewResult {
+            analysis: review.analysis,
+            objectives: to_score(review.scores.objectives),
+            methodology: to_score(review.scores.methodology),
+            budget: to_score(review.scores.budget),
+            expertise: to_score(review.scores.expertise),
+            innovation: to_score(review.scores.innovation),
+        })
+    }
+
+    async fn check_manuscript(
+        &self,
+        manuscript: &str,
+    ) -> Result<Vec<ManuscriptFindingResult>, anyhow::Error> {
+        if self.base_url.is_empty() {
+            return Err(anyhow::anyhow!("AI_BACKEND_URL is not set"));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_secs(ERROR_CHECK_TIMEOUT_SECS))
+            .build()?;
+        let response = client
+            .post(format!("{}/api/v1/manuscripts/error-check", self.base_url))
+            .header("User-Agent", "NerdNuggets-Backend/2.0")
+            .json(&serde_json::json!({ "manuscript": manuscript }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "AI backend request failed with status: {}",
+                response.status()
+            ));
+        }
+        let external_response: ExternalErrorCheckResponse = response.json().await?;
+        if external_response.status != "success" {
+            return Err(anyhow::anyhow!(
+                "AI backend responded with status: {}",
+                external_response.status
+            ));
+        }
+        Ok(external_response
+            .data
+            .analysis
+            .iter()
+            .flat_map(to_findings)
+            .collect())
+    }
+}
+
+/// Answers every review with `result` and every error check with `findings`
+/// without calling the AI backend. The first `failures` calls fail, to
+/// exercise retries.
+#[derive(Clone, Default)]
+pub struct MockAIReviewClient {
+    result: AIReviewResult,
+    findings: Vec<ManuscriptFindingResult>,
+    failures: Arc<AtomicUsize>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl MockAIReviewClient {
+    pub fn new(result: AIReviewResult) -> Self {
+        Self {
+            result,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_findings(mut self, findings: Vec<ManuscriptFindingResult>) -> Self {
+        self.findings = findings;
+        self
+    }
+
+    pub fn failing_first(self, failures: usize) -> Self {
+        self.failures.store(failures, Ordering::SeqCst);
+        self
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Counts the call and tells whether it should fail
+    fn next_call_fails(&self) -> bool {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+
+#[async_trait]
+impl AIReviewClient for MockAIReviewClient {
+    async fn review_project(
+        &self,
+        request: &AIReviewRequest,
+    ) -> Result<AIReviewResult, anyhow::Error> {
+        if self.next_call_fails() {
+            return Err(anyhow::anyhow!(
+                "Mock review of {} failed",
+                request.project_id
+            ));
+        }
+        Ok(self.result.clone())
+    }
+
+    async fn check_manuscript(
+        &self,
+        manuscript: &str,
+    ) -> Result<Vec<ManuscriptFindingResult>, anyhow::Error> {
+        if self.next_call_fails() {
+            return Err(anyhow::anyhow!("Mock error check of {} failed", manuscript));
+        }
+        Ok(self.findings.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Accepts one request and replies with `status` and a JSON `body`
+    async fn respond_once(
+        listener: TcpListener,
+        status: &'static str,
+        body: &'static str,
+    ) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(str::to_string)
+                    })
+                    .and_then(|l| l.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    }
+
+    fn request() -> AIReviewRequest {
+        AIReviewRequest {
+            title: "Deep Sea Survey".to_string(),
+            description: "Mapping vent ecosystems".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn parses_and_clamps_scores() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(respond_once(
+            listener,
+            "200 OK",
+            r#"{"status":"success","data":{"analysis":"Solid plan","scores":{"objectives":81.6,"methodology":70,"budget":120,"expertise":-3,"innovation":55}}}"#,
+        ));
+
+        let result = HttpAIReviewClient::new(&url)
+            .review_project(&request())
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            AIReviewResult {
+                analysis: "Solid plan".to_string(),
+                objectives: 82,
+                methodology: 70,
+                budget: 100,
+                expertise: 0,
+                innovation: 55,
+            }
+        );
+
+        let sent = receiver.await.unwrap();
+        assert!(sent.starts_with("POST /api/v1/projects/review"));
+        assert!(sent.contains(r#""title":"Deep Sea Survey""#));
+    }
+
+    #[tokio::test]
+    async fn reports_backend_failures() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(respond_once(listener, "503 Service Unavailable", "{}"));
+
+        let result = HttpAIReviewClient::new(&url)
+            .review_project(&request())
+            .await;
+        assert!(result.is_err());
+        receiver.await.unwrap();
+
+        assert!(HttpAIReviewClient::new("")
+            .review_project(&request())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn parses_error_check_findings() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(respond_once(
+            listener,
+            "200 OK",
+            r#"{"status":"success","data":{"analysis":[
+                {"type":"Math Errors","counts":"2","findings":[
+                    {"title":"Sign error","description":"Eq. 3 drops a minus","severity":"high","location":"Section 2.1"},
+                    "Units don't match in Table 1"
+                ]},
+                {"type":"methodology","counts":0,"findings":[]},
+                {"type":"citations","counts":1,"findings":["Unknown type"]}
+            ]}}"#,
+        ));
+
+        let findings = HttpAIReviewClient::new(&url)
+            .check_manuscript("https://files.example/paper.pdf")
+            .await
+            .unwrap();
+        assert_eq!(
+            findings,
+            vec![
+                ManuscriptFindingResult {
+                    error_type: AnalysisErrorType::MathError,
+                    severity: FindingSeverity::High,
+                    title: "Sign error".to_string(),
+                    description: "Eq. 3 drops a minus".to_string(),
+                    location: Some("Section 2.1".to_string()),
+                },
+                ManuscriptFindingResult {
+                    error_type: AnalysisErrorType::MathError,
+                    severity: FindingSeverity::Medium,
+                    title: String::new(),
+                    description: "Units don't match in Table 1".to_string(),
+                    location: None,
+                },
+            ]
+        );
+
+        let sent = receiver.await.unwrap();
+        assert!(sent.starts_with("POST /api/v1/manuscripts/error-check"));
+        assert!(sent.contains(r#""manuscript":"https://files.example/paper.pdf""#));
+    }
+
+    #[tokio::test]
+    async fn mock_fails_then_succeeds() {
+        let expected = AIReviewResult {
+            analysis: "Looks fine".to_string(),
+            objectives: 50,
+            ..Default::default()
+        };
+        let client = MockAIReviewClient::new(expected.clone()).failing_first(2);
+        assert!(client.review_project(&request()).await.is_err());
+        assert!(client.review_project(&request()).await.is_err());
+        assert_eq!(client.review_project(&request()).await.unwrap(), expected);
+        assert_eq!(client.calls(), 3);
+    }
+}
@@ -1,4 +1,6 @@
+pub mod ai_review;
 pub mod apple_oauth;
 pub mod arweave;
 pub mod google_oauth;
 pub mod nerdbunny_api;
+pub mod webhook;
@@ -0,0 +1,395 @@
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+    Client,
+};
+use ring::hmac;
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::net::lookup_host;
+use url::{Host, Url};
+
+pub const SIGNATURE_HEADER: &str = "X-NerdNuggets-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-NerdNuggets-Timestamp";
+pub const EVENT_HEADER: &str = "X-NerdNuggets-Event";
+pub const DELIVERY_HEADER: &str = "X-NerdNuggets-Delivery";
+
+/// Signs `"{timestamp}.{body}"` with the endpoint secret. Receivers recompute
+/// it to verify the payload and reject stale timestamps to prevent replays.
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={}", hex::encode(tag.as_ref()))
+}
+
+/// Why a webhook could not be delivered. Only these fixed messages are stored
+/// and shown to endpoint owners, never the receiver's or resolver's own errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookError {
+    InvalidUrl,
+    InsecureUrl,
+    ForbiddenAddress,
+    Timeout,
+    Connection,
+    Request,
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            WebhookError::InvalidUrl => "Webhook url is invalid",
+            WebhookError::InsecureUrl => "Webhook url must use https",
+            WebhookError::ForbiddenAddress => "Webhook host resolves to a non-public address",
+            WebhookError::Timeout => "Receiver timed out",
+            WebhookError::Connection => "Could not connect to receiver",
+            WebhookError::Request => "Request to receiver failed",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+impl From<reqwest::Error> for WebhookError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            WebhookError::Timeout
+        } else if e.is_connect() {
+            WebhookError::Connection
+        } else {
+            WebhookError::Request
+        }
+    }
+}
+
+/// False for loopback, private, link-local and other addresses that are not
+/// reachable on the public internet
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Shared address space (100.64.0.0/10) and 0.0.0.0/8
+                || (a == 100 && (64..128).contains(&b))
+                || a == 0)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // Unique local (fc00::/7) and link-local (fe80::/10)
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Resolves hosts for the webhook client, keeping public addresses only so a
+/// host cannot be pointed at an internal service after it was checked
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(Box::new(WebhookError::ForbiddenAddress) as _);
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Checks that `url` is https and that its host only resolves to public
+/// addresses
+pub async fn check_webhook_url(url: &str) -> Result<Url, WebhookError> {
+    check_url(url, false).await
+}
+
+async fn check_url(url: &str, allow_private: bool) -> Result<Url, WebhookError> {
+    let url = Url::parse(url).map_err(|_| WebhookError::InvalidUrl)?;
+    if allow_private {
+        return Ok(url);
+    }
+    if url.scheme() != "https" {
+        return Err(WebhookError::InsecureUrl);
+    }
+    let host = url.host().ok_or(WebhookError::InvalidUrl)?;
+    let ips: Vec<IpAddr> = match host {
+        Host::Ipv4(ip) => vec![IpAddr::V4(ip)],
+        Host::Ipv6(ip) => vec![IpAddr::V6(ip)],
+        Host::Domain(domain) => lookup_host((domain, url.port_or_known_default().unwrap_or(443)))
+            .await
+            .map_err(|_| WebhookError::Connection)?
+            .map(|addr| addr.ip())
+            .collect(),
+    };
+    if ips.is_empty() || !ips.into_iter().all(is_public_ip) {
+        return Err(WebhookError::ForbiddenAddress);
+    }
+    Ok(url)
+}
+
+/// Posts signed webhook deliveries. Cheap to clone, every clone shares one
+/// connection pool. Redirects are not followed.
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: Client,
+    allow_private: bool,
+}
+
+impl WebhookSender {
+    pub fn new() -> Result<Self, WebhookError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .map_err(|_| WebhookError::Request)?;
+        Ok(Self {
+            client,
+            allow_private: false,
+        })
+    }
+
+    /// Posts a signed webhook delivery and returns the response status code.
+    /// Transport errors (DNS, connection, timeout) are returned as errors.
+    pub async fn send(
+        &self,
+        url: &str,
+        secret: &str,
+        event: &str,
+        delivery_id: &str,
+        timestamp: i64,
+        body: String,
+    ) -> Result<u16, WebhookError> {
+        let url = check_url(url, self.allow_private).await?;
+        let response = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "NerdNuggets-Webhooks/1.0")
+            .header(EVENT_HEADER, event)
+            .header(DELIVERY_HEADER, delivery_id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign_webhook_payload(secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await?;
+        Ok(response.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Sends to the local test receivers, which are plain http on loopback
+    fn local_sender() -> WebhookSender {
+        WebhookSender {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(Policy::none())
+                .build()
+                .unwrap(),
+            allow_private: true,
+        }
+    }
+
+    /// Accepts one request, replies with `status` and returns the raw request.
+    async fn receive_once(listener: TcpListener, status: &'static str) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(str::to_string)
+                    })
+                    .and_then(|l| l.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 {}\r\nLocation: http://127.0.0.1:1/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    }
+
+    #[test]
+    fn signature_is_stable() {
+        assert_eq!(
+            sign_webhook_payload("secret", 1700000000, "{}"),
+            sign_webhook_payload("secret", 1700000000, "{}")
+        );
+        assert_ne!(
+            sign_webhook_payload("secret", 1700000000, "{}"),
+            sign_webhook_payload("other", 1700000000, "{}")
+        );
+        assert!(sign_webhook_payload("secret", 1, "{}").starts_with("sha256="));
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payload_to_local_receiver() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(receive_once(listener, "204 No Content"));
+
+        let body = r#"{"event":"dao.finished","data":{"proposal_id":1}}"#.to_string();
+        let status = local_sender()
+            .send(
+                &url,
+                "secret",
+                "dao.finished",
+                "delivery-1",
+                1700000000,
+                body.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, 204);
+
+        let request = receiver.await.unwrap().to_lowercase();
+        assert!(request.starts_with("post /hook"));
+        assert!(request.contains("x-nerdnuggets-event: dao.finished"));
+        assert!(request.contains("x-nerdnuggets-delivery: delivery-1"));
+        let signature = sign_webhook_payload("secret", 1700000000, &body);
+        assert!(request.contains(&format!("x-nerdnuggets-signature: {}", signature)));
+        assert!(request.ends_with(&body.to_lowercase()));
+    }
+
+    #[tokio::test]
+    async fn reports_receiver_failures() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(receive_once(listener, "500 Internal Server Error"));
+
+        let status = local_sender()
+            .send(
+                &url,
+                "secret",
+                "dao.finished",
+                "delivery-2",
+                1,
+                "{}".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, 500);
+        receiver.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_follow_redirects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let receiver = tokio::spawn(receive_once(listener, "302 Found"));
+
+        let status = local_sender()
+            .send(
+                &url,
+                "secret",
+                "dao.finished",
+                "delivery-3",
+                1,
+                "{}".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, 302);
+        receiver.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_insecure_and_internal_urls() {
+        assert_eq!(
+            check_webhook_url("not a url").await.unwrap_err(),
+            WebhookError::InvalidUrl
+        );
+        assert_eq!(
+            check_webhook_url("http://8.8.8.8/hook").await.unwrap_err(),
+            WebhookError::InsecureUrl
+        );
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://10.1.2.3/hook",
+            "https://172.16.0.1/hook",
+            "https://192.168.1.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://100.64.0.1/hook",
+            "https://0.0.0.0/hook",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[fe80::1]/hook",
+            "https://[::ffff:10.0.0.1]/hook",
+        ] {
+            assert_eq!(
+                check_webhook_url(url).await.unwrap_err(),
+                WebhookError::ForbiddenAddress,
+                "{}",
+                url
+            );
+        }
+        assert!(check_webhook_url("https://8.8.8.8/hook").await.is_ok());
+        assert!(check_webhook_url("https://[2606:4700::1111]/hook")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn refuses_to_send_to_internal_receivers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("https://{}/hook", listener.local_addr().unwrap());
+        let result = WebhookSender::new()
+            .unwrap()
+            .send(
+                &url,
+                "secret",
+                "dao.finished",
+                "delivery-4",
+                1,
+                "{}".to_string(),
+            )
+            .await;
+        assert_eq!(result.unwrap_err(), WebhookError::ForbiddenAddress);
+    }
+}
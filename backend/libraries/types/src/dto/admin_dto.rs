@@ -56,3 +56,45 @@ pub struct AdminDeleteUnreportPostRequest {
 pub struct AdminDeleteCategoryRequest {
     pub id: Uuid,
 }
+
+/// Reindexing fingerprints projects, then bounties, then recomputes the
+/// matches of the projects in review
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SimilarityReindexStep {
+    #[default]
+    Projects,
+    Bounties,
+    Matches,
+}
+
+impl SimilarityReindexStep {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Projects => Some(Self::Bounties),
+            Self::Bounties => Some(Self::Matches),
+            Self::Matches => None,
+        }
+    }
+}
+
+/// Where a reindex batch starts. Empty starts from the beginning.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarityReindexCursor {
+    #[serde(default)]
+    pub step: SimilarityReindexStep,
+    /// Id of the last item done in `step`
+    pub after: Option<Uuid>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarityReindexResponse {
+    pub projects: usize,
+    pub bounties: usize,
+    /// Projects in review whose matches were recomputed
+    pub checked_projects: usize,
+    /// Pass back to continue; empty once everything was reindexed
+    pub next: Option<SimilarityReindexCursor>,
+}
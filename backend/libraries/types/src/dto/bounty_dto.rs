@@ -5,6 +5,7 @@ use validator::Validate;
 
 use crate::models::{
     BidMilestoneSubmissionStatus, BidStatus, BountyDifficulty, BountyReviewType, BountyStatus,
+    ListingSort,
 };
 
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
@@ -57,6 +58,8 @@ pub struct GetBountysOption {
     pub category_id: Option<Uuid>,
     pub difficulty: Option<BountyDifficulty>,
     pub is_mine: Option<bool>,
+    pub sort: Option<ListingSort>,
+    pub cursor: Option<String>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
@@ -107,10 +110,21 @@ pub struct OffsetAndLimitOption {
     pub limit: Option<i32>,
 }
 
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBountyCommentsOption {
+    pub cursor: Option<String>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBountyCommentRequest {
+    #[validate(length(min = 1))]
     pub comment: String,
+    /// Replies to this comment. Replies to a reply join the same thread.
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
@@ -142,6 +156,7 @@ pub struct SendBountyChatRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetBountyChatsOption {
     pub chat_number: String,
+    pub cursor: Option<String>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
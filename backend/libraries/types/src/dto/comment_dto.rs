@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EditCommentRequest {
+    #[validate(length(min = 1))]
+    pub comment: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactCommentRequest {
+    #[validate(length(min = 1, max = 16))]
+    pub emoji: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PinCommentRequest {
+    pub is_pinned: bool,
+}
@@ -1,15 +1,21 @@
 mod admin_dto;
 mod bounty_dto;
+mod comment_dto;
 mod prediction_dto;
 mod project_dto;
+mod report_dto;
 mod token_dto;
 mod user_dto;
 mod util_dto;
+mod webhook_dto;
 
 pub use admin_dto::*;
 pub use bounty_dto::*;
+pub use comment_dto::*;
 pub use prediction_dto::*;
 pub use project_dto::*;
+pub use report_dto::*;
 pub use token_dto::*;
 pub use user_dto::*;
 pub use util_dto::*;
+pub use webhook_dto::*;
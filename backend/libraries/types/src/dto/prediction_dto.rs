@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::models::PredictionStatus;
+use crate::models::{ListingSort, PredictionStatus};
 
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +11,7 @@ pub struct GetPredictionsOption {
     pub status: Option<PredictionStatus>,
     pub category_id: Option<Uuid>,
     pub is_mine: Option<bool>,
+    pub sort: Option<ListingSort>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
@@ -3,7 +3,13 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::models::DaoInfo;
+use crate::{
+    models::{
+        BudgetViolation, CollaboratorRole, DaoInfo, FindingSeverity, FundingRefundStatus,
+        ListingSort, MilestonePayoutInfo, MilestonePayoutStatus, ProjectUpdateVisibility,
+    },
+    AnalysisErrorType,
+};
 
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -68,6 +74,8 @@ pub struct GetProjectsOption {
     pub category_id: Option<Uuid>,
     pub is_mine: Option<bool>,
     pub is_public: Option<bool>,
+    pub sort: Option<ListingSort>,
+    pub cursor: Option<String>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
@@ -100,6 +108,7 @@ pub struct UpdateMilestoneRequest {
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetProjectCommentsOption {
+    pub cursor: Option<String>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
@@ -107,7 +116,10 @@ pub struct GetProjectCommentsOption {
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitProjectCommentRequest {
+    #[validate(length(min = 1))]
     pub comment: String,
+    /// Replies to this comment. Replies to a reply join the same thread.
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
@@ -116,6 +128,7 @@ pub struct GetDaosOption {
     pub title: Option<String>,
     pub status: Option<i16>,
     pub is_mine: Option<bool>,
+    pub sort: Option<ListingSort>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
@@ -138,10 +151,36 @@ pub struct GetSimilarProjectsOption {
 #[derive(Clone, Serialize, Deserialize, Validate, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetProjectFundersOption {
+    pub cursor: Option<String>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
 
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMilestonePayoutsOption {
+    pub status: Option<MilestonePayoutStatus>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProjectStatusHistoryOption {
+    pub project_id: Option<Uuid>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProjectRevisionDiffOption {
+    /// Defaults to the version before `to`
+    pub from: Option<i32>,
+    /// Defaults to the latest version
+    pub to: Option<i32>,
+}
+
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectStatusCount {
@@ -217,6 +256,8 @@ pub struct ProjectFundersResponse {
     pub funders: Vec<ProjectFunderInfo>,
     pub total_amount: i32,
     pub total_count: i64,
+    /// Only set when paging by cursor and more funders follow
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
@@ -230,6 +271,8 @@ pub struct ProjectFunderInfo {
     pub avatar_url: Option<String>,
     pub number: i16,
     pub amount: i32,
+    pub refunded_amount: i32,
+    pub refund_status: FundingRefundStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -241,6 +284,9 @@ pub struct ResearchProjectDashboardResponse {
     pub total_funded: i32,
     pub total_backers: i32,
     pub completed: i32,
+    pub total_claimed: i32,
+    /// Newest first
+    pub payouts: Vec<MilestonePayoutInfo>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
@@ -279,3 +325,148 @@ pub struct UserDaoVotesResponse {
     pub total: i64,
     pub stats: UserDaoVotingStats,
 }
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteReviewerRequest {
+    pub reviewer_id: Uuid,
+    /// Defaults to the configured review deadline from now
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitProjectReviewRequest {
+    #[validate(range(min = 1, max = 10))]
+    pub score_objectives: i16,
+    #[validate(range(min = 1, max = 10))]
+    pub score_methodology: i16,
+    #[validate(range(min = 1, max = 10))]
+    pub score_budget: i16,
+    #[validate(range(min = 1, max = 10))]
+    pub score_expertise: i16,
+    #[validate(range(min = 1, max = 10))]
+    pub score_innovation: i16,
+    /// FeedbackStatus => 1: accepted, 2: request revision, 3: rejected
+    #[validate(range(min = 1, max = 3))]
+    pub recommendation: i16,
+    pub comments: Option<String>,
+    /// Only visible to the handling editor and admins
+    pub confidential_comments: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAssignedReviewsOption {
+    pub status: Option<i16>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEditorRecommendationsOption {
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteCollaboratorRequest {
+    pub user_id: Uuid,
+    pub role: CollaboratorRole,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProjectUpdateRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    /// Rich text as produced by the editor
+    #[validate(length(min = 1))]
+    pub content: String,
+    pub attachments: Option<Vec<String>>,
+    pub visibility: ProjectUpdateVisibility,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EditProjectUpdateRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    #[validate(length(min = 1))]
+    pub content: Option<String>,
+    pub attachments: Option<Vec<String>>,
+    pub visibility: Option<ProjectUpdateVisibility>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProjectUpdatesOption {
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitProjectUpdateCommentRequest {
+    #[validate(length(min = 1))]
+    pub comment: String,
+}
+
+/// Unsaved values to check in place of the stored ones
+#[derive(Clone, Serialize, Deserialize, Validate, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateProjectBudgetRequest {
+    pub funding_goal: Option<i32>,
+    pub duration: Option<i32>,
+    pub personnel_cost: Option<i32>,
+    pub equipment_cost: Option<i32>,
+    pub materials_cost: Option<i32>,
+    pub overhead_cost: Option<i32>,
+    pub other_cost: Option<i32>,
+    pub milestones: Option<Vec<ProjectMilestoneRequest>>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBudgetValidationResponse {
+    pub valid: bool,
+    pub violations: Vec<BudgetViolation>,
+}
+
+/// What the AI backend is given to review a submitted project
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AIReviewRequest {
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub details: String,
+    pub manuscript: Option<String>,
+    pub funding_goal: Option<i32>,
+    pub duration: Option<i32>,
+    pub tags: Vec<String>,
+}
+
+/// Scores are between 0 and 100
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AIReviewResult {
+    pub analysis: String,
+    pub objectives: i16,
+    pub methodology: i16,
+    pub budget: i16,
+    pub expertise: i16,
+    pub innovation: i16,
+}
+
+/// A manuscript error reported by the AI backend
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManuscriptFindingResult {
+    pub error_type: AnalysisErrorType,
+    pub severity: FindingSeverity,
+    pub title: String,
+    pub description: String,
+    pub location: Option<String>,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{ReportReason, ReportStatus, ReportTargetType};
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReportRequest {
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub reason: ReportReason,
+    #[validate(length(max = 2000))]
+    pub details: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetModerationQueueOption {
+    /// Open reports when empty
+    pub status: Option<ReportStatus>,
+    pub target_type: Option<ReportTargetType>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub enum ModerateContentAction {
+    Hide,
+    Restore,
+    Remove,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerateContentRequest {
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub action: ModerateContentAction,
+    /// Shown to the author when the content is removed
+    #[validate(length(max = 2000))]
+    pub note: Option<String>,
+}
@@ -178,6 +178,7 @@ pub struct UserNotificationSettingsRequest {
     pub funding_updates: bool,
     pub dao_proposals: bool,
     pub prediction_markets: bool,
+    pub archive_notifications: Option<bool>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -189,6 +190,7 @@ pub struct UserNotificationSettingsResponse {
     pub funding_updates: bool,
     pub dao_proposals: bool,
     pub prediction_markets: bool,
+    pub archive_notifications: bool,
 }
 
 // Privacy Settings
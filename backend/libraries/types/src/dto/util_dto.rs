@@ -1,5 +1,9 @@
-use crate::models::{City, Country, Degree, EmploymentsInfo, HashTagsInfo, WallPapers};
+use crate::models::{
+    City, Country, Cursor, Degree, EmploymentsInfo, FollowEntityType, HashTagsInfo, PageRequest,
+    WallPapers,
+};
 use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -173,3 +177,72 @@ pub struct ExtractProjectInfoResponse {
     pub status: String,
     pub data: NerdNuggetsInfo,
 }
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchOption {
+    pub q: String,
+    /// Comma separated, e.g. `project,user`. All types when empty.
+    pub types: Option<String>,
+    /// Per type
+    pub limit: Option<i32>,
+    /// Lighter results for suggestions while typing: no snippets and a
+    /// smaller default limit
+    pub typeahead: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSavedItemsOption {
+    /// All types when empty
+    pub entity_type: Option<FollowEntityType>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+/// A listing response. Offset requests keep getting a bare array; cursor
+/// requests get the items along with the cursor of the next page, which is
+/// `null` on the last page.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum Paginated<T> {
+    List(Vec<T>),
+    #[serde(rename_all = "camelCase")]
+    Page {
+        items: Vec<T>,
+        next_cursor: Option<String>,
+    },
+}
+
+impl<T> Paginated<T> {
+    pub fn new<Id: Display + FromStr + Copy>(
+        page: PageRequest<Id>,
+        items: Vec<T>,
+        next_cursor: Option<Cursor<Id>>,
+    ) -> Self {
+        if page.is_cursor() {
+            Paginated::Page {
+                items,
+                next_cursor: next_cursor.map(|c| c.encode()),
+            }
+        } else {
+            Paginated::List(items)
+        }
+    }
+    pub fn items_mut(&mut self) -> &mut Vec<T> {
+        match self {
+            Paginated::List(items) => items,
+            Paginated::Page { items, .. } => items,
+        }
+    }
+    /// Replaces every item with the items `f` returns, keeping the cursor
+    pub fn flat_map<U, I: IntoIterator<Item = U>>(self, f: impl FnMut(T) -> I) -> Paginated<U> {
+        match self {
+            Paginated::List(items) => Paginated::List(items.into_iter().flat_map(f).collect()),
+            Paginated::Page { items, next_cursor } => Paginated::Page {
+                items: items.into_iter().flat_map(f).collect(),
+                next_cursor,
+            },
+        }
+    }
+}
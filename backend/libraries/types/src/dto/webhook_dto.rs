@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::WebhookEvent;
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookEndpointRequest {
+    #[validate(url)]
+    pub url: String,
+    /// Events to deliver; empty means every event
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookEndpointRequest {
+    #[validate(url)]
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEvent>>,
+    pub is_active: Option<bool>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWebhookDeliveriesOption {
+    pub endpoint_id: Option<Uuid>,
+    pub event: Option<i16>,
+    pub status: Option<i16>,
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
@@ -1,5 +1,7 @@
 use super::upload_err::UploadError;
-use crate::error::{db_error::DbError, token_error::TokenError, user_error::UserError};
+use crate::error::{
+    db_error::DbError, project_error::ProjectError, token_error::TokenError, user_error::UserError,
+};
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
@@ -13,6 +15,8 @@ pub enum ApiError {
     DbError(#[from] DbError),
     #[error(transparent)]
     UploadError(#[from] UploadError),
+    #[error(transparent)]
+    ProjectError(#[from] ProjectError),
 }
 
 impl IntoResponse for ApiError {
@@ -22,6 +26,7 @@ impl IntoResponse for ApiError {
             ApiError::UserError(error) => error.into_response(),
             ApiError::DbError(error) => error.into_response(),
             ApiError::UploadError(error) => error.into_response(),
+            ApiError::ProjectError(error) => error.into_response(),
         }
     }
 }
@@ -1,5 +1,6 @@
 mod api_error;
 mod db_error;
+mod project_error;
 mod request_error;
 mod token_error;
 mod upload_err;
@@ -7,6 +8,7 @@ mod user_error;
 
 pub use api_error::*;
 pub use db_error::*;
+pub use project_error::*;
 pub use request_error::*;
 pub use token_error::*;
 pub use upload_err::*;
@@ -0,0 +1,37 @@
+use crate::{models::BudgetViolation, response::ApiErrorResponse, ProjectStatus};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProjectError {
+    #[error("Project can't move from {from:?} to {to:?}.")]
+    InvalidStatusTransition {
+        from: ProjectStatus,
+        to: ProjectStatus,
+    },
+    #[error("Project status has changed. Please reload and try again.")]
+    StatusConflict,
+    #[error("Project budget is inconsistent.")]
+    InvalidBudget(Vec<BudgetViolation>),
+}
+
+impl IntoResponse for ProjectError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            ProjectError::InvalidStatusTransition { .. } => StatusCode::BAD_REQUEST,
+            ProjectError::StatusConflict => StatusCode::CONFLICT,
+            ProjectError::InvalidBudget(ref violations) => {
+                return ApiErrorResponse::send_with_details(
+                    StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+                    Some(self.to_string()),
+                    json!({ "violations": violations }),
+                );
+            }
+        };
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -9,6 +9,9 @@ mod subscription;
 
 use std::fmt;
 
+use error::ProjectError;
+use postgres_macro::define_pg_enum;
+
 pub use subscription::*;
 
 use serde::{Deserialize, Serialize};
@@ -84,7 +87,7 @@ impl AIErrorCheckStatus {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProjectStatus {
     #[default]
     Creating,
@@ -118,8 +121,45 @@ impl ProjectStatus {
     pub fn to_i16(&self) -> i16 {
         self.to_owned() as i16
     }
+
+    pub fn can_transition_to(&self, to: ProjectStatus) -> bool {
+        PROJECT_STATUS_TRANSITIONS.contains(&(*self, to))
+    }
+
+    /// Checks `self -> to` against `PROJECT_STATUS_TRANSITIONS`
+    pub fn transition_to(&self, to: ProjectStatus) -> Result<ProjectStatus, ProjectError> {
+        if self.can_transition_to(to) {
+            Ok(to)
+        } else {
+            Err(ProjectError::InvalidStatusTransition { from: *self, to })
+        }
+    }
 }
 
+/// Every status change a project may go through. All service paths that
+/// change `project.status` must be validated against this table.
+pub const PROJECT_STATUS_TRANSITIONS: &[(ProjectStatus, ProjectStatus)] = &[
+    // Researcher submits, or resubmits after a revision request
+    (ProjectStatus::Creating, ProjectStatus::PendingReview),
+    (ProjectStatus::RevisionEditor, ProjectStatus::PendingReview),
+    (ProjectStatus::RevisionAdmin, ProjectStatus::PendingReview),
+    // Admin assigns an editor
+    (ProjectStatus::PendingReview, ProjectStatus::UnderReview),
+    // Editor decision
+    (ProjectStatus::UnderReview, ProjectStatus::ApprovedEditor),
+    (ProjectStatus::UnderReview, ProjectStatus::RevisionEditor),
+    (ProjectStatus::UnderReview, ProjectStatus::Rejected),
+    // Admin decision
+    (ProjectStatus::ApprovedEditor, ProjectStatus::DaoVoting),
+    (ProjectStatus::ApprovedEditor, ProjectStatus::Funding),
+    (ProjectStatus::ApprovedEditor, ProjectStatus::RevisionAdmin),
+    (ProjectStatus::ApprovedEditor, ProjectStatus::Rejected),
+    // DAO result
+    (ProjectStatus::DaoVoting, ProjectStatus::Funding),
+    (ProjectStatus::DaoVoting, ProjectStatus::Rejected),
+    (ProjectStatus::Funding, ProjectStatus::Completed),
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub enum MileStoneStatus {
     #[default]
@@ -263,7 +303,7 @@ pub enum NerdNuggetsOAuth2AppName {
     NerdBunny,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AnalysisErrorType {
     DataAnalysisError,
     LogicalFrameworkError,
@@ -273,6 +313,40 @@ pub enum AnalysisErrorType {
     TechnicalPresentationError,
 }
 
+define_pg_enum!(AnalysisErrorType {
+    DataAnalysisError = 0,
+    LogicalFrameworkError = 1,
+    MathError = 2,
+    MethodologyError = 3,
+    ResearchQualityError = 4,
+    TechnicalPresentationError = 5,
+});
+
+impl AnalysisErrorType {
+    /// Reads the analysis type names used by the AI backend, such as
+    /// `math`, `Math Errors` or `logical_framework_error`
+    pub fn from_label(label: &str) -> Option<Self> {
+        let key: String = label
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let key = key
+            .strip_suffix("errors")
+            .or_else(|| key.strip_suffix("error"))
+            .unwrap_or(&key);
+        match key {
+            "dataanalysis" | "data" => Some(Self::DataAnalysisError),
+            "logicalframework" | "logical" | "logic" => Some(Self::LogicalFrameworkError),
+            "math" | "mathematical" => Some(Self::MathError),
+            "methodology" | "methodological" => Some(Self::MethodologyError),
+            "researchquality" => Some(Self::ResearchQualityError),
+            "technicalpresentation" | "presentation" => Some(Self::TechnicalPresentationError),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, Default, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorStatistics {
@@ -292,3 +366,102 @@ pub struct Statistics {
     pub total_analyses: i64,
     pub error_statistics: ErrorStatistics,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATUSES: [ProjectStatus; 10] = [
+        ProjectStatus::Creating,
+        ProjectStatus::PendingReview,
+        ProjectStatus::UnderReview,
+        ProjectStatus::RevisionEditor,
+        ProjectStatus::ApprovedEditor,
+        ProjectStatus::RevisionAdmin,
+        ProjectStatus::DaoVoting,
+        ProjectStatus::Funding,
+        ProjectStatus::Completed,
+        ProjectStatus::Rejected,
+    ];
+
+    #[test]
+    fn allows_the_review_workflow() {
+        let path = [
+            ProjectStatus::Creating,
+            ProjectStatus::PendingReview,
+            ProjectStatus::UnderReview,
+            ProjectStatus::ApprovedEditor,
+            ProjectStatus::DaoVoting,
+            ProjectStatus::Funding,
+            ProjectStatus::Completed,
+        ];
+        for step in path.windows(2) {
+            assert_eq!(step[0].transition_to(step[1]).unwrap(), step[1]);
+        }
+        assert!(ProjectStatus::RevisionEditor.can_transition_to(ProjectStatus::PendingReview));
+        assert!(ProjectStatus::RevisionAdmin.can_transition_to(ProjectStatus::PendingReview));
+        assert!(ProjectStatus::UnderReview.can_transition_to(ProjectStatus::Rejected));
+        assert!(ProjectStatus::ApprovedEditor.can_transition_to(ProjectStatus::RevisionAdmin));
+        assert!(ProjectStatus::DaoVoting.can_transition_to(ProjectStatus::Rejected));
+    }
+
+    #[test]
+    fn forbids_skipping_and_reversing_steps() {
+        let forbidden = [
+            (ProjectStatus::Creating, ProjectStatus::UnderReview),
+            (ProjectStatus::PendingReview, ProjectStatus::ApprovedEditor),
+            (ProjectStatus::UnderReview, ProjectStatus::DaoVoting),
+            (ProjectStatus::ApprovedEditor, ProjectStatus::UnderReview),
+            (ProjectStatus::Funding, ProjectStatus::DaoVoting),
+            (ProjectStatus::PendingReview, ProjectStatus::PendingReview),
+        ];
+        for (from, to) in forbidden {
+            assert!(
+                matches!(
+                    from.transition_to(to),
+                    Err(ProjectError::InvalidStatusTransition { from: f, to: t }) if f == from && t == to
+                ),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+
+    #[test]
+    fn completed_and_rejected_are_final() {
+        for from in [ProjectStatus::Completed, ProjectStatus::Rejected] {
+            for to in ALL_STATUSES {
+                assert!(!from.can_transition_to(to), "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn transition_table_has_no_duplicates() {
+        for (i, transition) in PROJECT_STATUS_TRANSITIONS.iter().enumerate() {
+            assert!(!PROJECT_STATUS_TRANSITIONS[i + 1..].contains(transition));
+        }
+    }
+
+    #[test]
+    fn reads_analysis_error_labels() {
+        let labels = [
+            ("Math Errors", AnalysisErrorType::MathError),
+            ("math", AnalysisErrorType::MathError),
+            ("mathematical_error", AnalysisErrorType::MathError),
+            ("Methodology", AnalysisErrorType::MethodologyError),
+            ("logical_framework_error", AnalysisErrorType::LogicalFrameworkError),
+            ("Logic Errors", AnalysisErrorType::LogicalFrameworkError),
+            ("Data Analysis Errors", AnalysisErrorType::DataAnalysisError),
+            ("research-quality", AnalysisErrorType::ResearchQualityError),
+            ("Technical Presentation Error", AnalysisErrorType::TechnicalPresentationError),
+        ];
+        for (label, expected) in labels {
+            assert_eq!(AnalysisErrorType::from_label(label), Some(expected), "{label}");
+        }
+        for label in ["", "errors", "citations", "math-ish"] {
+            assert_eq!(AnalysisErrorType::from_label(label), None, "{label}");
+        }
+    }
+}
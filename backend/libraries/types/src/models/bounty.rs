@@ -1,4 +1,4 @@
-use crate::models::{Category, UserInfo};
+use crate::models::{Category, CommentInfo, UserInfo};
 use chrono::{DateTime, NaiveDate, Utc};
 use postgres_macro::define_pg_enum;
 use serde::{Deserialize, Serialize};
@@ -39,6 +39,12 @@ pub struct Bounty {
     pub rejected_at: Option<DateTime<Utc>>,
     pub canceled_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
+
+    /// Set when the listing was filtered by a search term
+    #[sqlx(default)]
+    pub search_rank: Option<f32>,
+    #[sqlx(default)]
+    pub search_snippet: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -77,6 +83,11 @@ pub struct BountyInfo {
     pub rejected_at: Option<DateTime<Utc>>,
     pub canceled_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
+    pub search_rank: Option<f32>,
+    pub search_snippet: Option<String>,
+    /// Whether the signed in user follows or bookmarked the bounty
+    pub is_following: bool,
+    pub is_bookmarked: bool,
 }
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
@@ -170,26 +181,6 @@ pub struct BidMilestone {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
-pub struct BountyComment {
-    pub id: Uuid,
-    pub user_id: Uuid,
-    pub bounty_id: Uuid,
-    pub nerd_id: String,
-    pub comment: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
-
-#[derive(Clone, Deserialize, Serialize, Default, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct BountyCommentInfo {
-    pub id: Uuid,
-    pub user: UserInfo,
-    pub comment: String,
-    pub created_at: DateTime<Utc>,
-}
-
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
 pub struct BountyChat {
     pub id: Uuid,
@@ -483,6 +474,10 @@ impl Bounty {
             rejected_at: self.rejected_at,
             canceled_at: self.canceled_at,
             started_at: self.started_at,
+            search_rank: self.search_rank,
+            search_snippet: self.search_snippet.clone(),
+            is_following: false,
+            is_bookmarked: false,
         }
     }
 }
@@ -514,17 +509,6 @@ impl Bid {
     }
 }
 
-impl BountyComment {
-    pub fn to_info(&self, user: UserInfo) -> BountyCommentInfo {
-        BountyCommentInfo {
-            id: self.id,
-            user,
-            comment: self.comment.clone(),
-            created_at: self.created_at,
-        }
-    }
-}
-
 impl BountyChat {
     pub fn to_info(&self, sender: UserInfo, receiver: UserInfo) -> BountyChatInfo {
         BountyChatInfo {
@@ -588,3 +572,25 @@ impl BountyMilestoneSubmission {
         }
     }
 }
+
+/// Flat comment as listed by `GET /bounty/:id/comment`. The threaded
+/// listing returns `CommentInfo`.
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BountyCommentInfo {
+    pub id: Uuid,
+    pub user: UserInfo,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<CommentInfo> for BountyCommentInfo {
+    fn from(comment: CommentInfo) -> Self {
+        Self {
+            id: comment.id,
+            user: comment.user,
+            comment: comment.comment,
+            created_at: comment.created_at,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserInfo;
+
+/// What a comment thread hangs off
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentEntityType {
+    Project,
+    Bounty,
+}
+
+impl std::fmt::Display for CommentEntityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommentEntityType::Project => write!(f, "project"),
+            CommentEntityType::Bounty => write!(f, "bounty"),
+        }
+    }
+}
+
+/// A row of `project_comment` or `bounty_comment`. `entity_id` is the
+/// project or bounty id.
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
+pub struct Comment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub entity_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub comment: String,
+    pub is_pinned: bool,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The project or bounty a comment is posted on
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct CommentTarget {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub nerd_id: String,
+    pub title: Option<String>,
+}
+
+/// A comment with its replies. Replies are a single level deep; replying to
+/// a reply adds to the same thread. Deleted comments only show up while
+/// they still have replies, without their text.
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentInfo {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub user: UserInfo,
+    pub comment: String,
+    pub is_pinned: bool,
+    pub is_deleted: bool,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub reactions: Vec<CommentReaction>,
+    pub replies: Vec<CommentInfo>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CommentInfo {
+    /// The comment followed by its replies, leaving out deleted comments
+    pub fn flatten(mut self) -> Vec<CommentInfo> {
+        let replies = std::mem::take(&mut self.replies);
+        std::iter::once(self)
+            .chain(replies)
+            .filter(|c| !c.is_deleted)
+            .collect()
+    }
+}
+
+/// A comment that was just posted or edited, with who should hear about it
+#[derive(Clone, Debug)]
+pub struct PostedComment {
+    pub comment: Comment,
+    pub target: CommentTarget,
+    /// Author of the comment being replied to
+    pub parent_user_id: Option<Uuid>,
+    /// Users mentioned for the first time, without the author
+    pub mentioned_user_ids: Vec<Uuid>,
+}
+
+/// How many users reacted to a comment with an emoji, and whether the
+/// signed in user is one of them
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentReaction {
+    #[serde(skip)]
+    pub comment_id: Uuid,
+    pub emoji: String,
+    pub count: i64,
+    pub reacted: bool,
+}
+
+/// The text a comment had before one of its edits
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentEdit {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub comment: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+impl Comment {
+    pub fn to_info(&self, user: UserInfo) -> CommentInfo {
+        let is_deleted = self.deleted_at.is_some();
+        CommentInfo {
+            id: self.id,
+            parent_id: self.parent_id,
+            user,
+            comment: if is_deleted {
+                String::new()
+            } else {
+                self.comment.clone()
+            },
+            is_pinned: self.is_pinned,
+            is_deleted,
+            edited_at: self.edited_at,
+            reactions: Vec::new(),
+            replies: Vec::new(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::error::DbError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use std::{fmt::Display, str::FromStr};
+use uuid::Uuid;
+
+/// Position in a keyset paginated listing: the sort timestamp and id of the
+/// last item of the previous page. Clients get it as an opaque string.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cursor<Id = Uuid> {
+    pub at: DateTime<Utc>,
+    pub id: Id,
+}
+
+impl<Id: Display + FromStr> Cursor<Id> {
+    pub fn new(at: DateTime<Utc>, id: Id) -> Self {
+        Self { at, id }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}.{}", self.at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(value: &str) -> Result<Self, DbError> {
+        let invalid = || DbError::Str("Invalid cursor".to_string());
+        let bytes = URL_SAFE_NO_PAD.decode(value).map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (at, id) = text.split_once('.').ok_or_else(invalid)?;
+        let at = at
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = id.parse().map_err(|_| invalid())?;
+        Ok(Self { at, id })
+    }
+}
+
+/// How a listing is paged. `Offset` is the original `LIMIT/OFFSET` mode;
+/// passing a `cursor` query parameter switches to keyset mode, where an
+/// empty cursor asks for the first page.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PageRequest<Id = Uuid> {
+    Offset(Option<i32>),
+    After(Option<Cursor<Id>>),
+}
+
+impl<Id: Display + FromStr + Copy> PageRequest<Id> {
+    pub fn new(offset: Option<i32>, cursor: Option<&str>) -> Result<Self, DbError> {
+        match cursor.map(str::trim) {
+            None => Ok(PageRequest::Offset(offset)),
+            Some("") => Ok(PageRequest::After(None)),
+            Some(cursor) => Ok(PageRequest::After(Some(Cursor::decode(cursor)?))),
+        }
+    }
+
+    pub fn is_cursor(&self) -> bool {
+        matches!(self, PageRequest::After(_))
+    }
+
+    pub fn offset(&self) -> Option<i32> {
+        match self {
+            PageRequest::Offset(offset) => *offset,
+            PageRequest::After(_) => None,
+        }
+    }
+
+    pub fn after(&self) -> Option<Cursor<Id>> {
+        match self {
+            PageRequest::Offset(_) => None,
+            PageRequest::After(cursor) => *cursor,
+        }
+    }
+
+    /// Keyset mode only resumes the default recency order (`updated_at, id`).
+    /// Search ranking and the other listing sorts order by computed keys
+    /// (ratios, flags, `NULLS LAST`) that a cursor doesn't carry, so they are
+    /// out of scope for cursors and stay on offset paging.
+    pub fn ensure_default_order(&self, is_default_order: bool) -> Result<(), DbError> {
+        if self.is_cursor() && !is_default_order {
+            return Err(DbError::Str(
+                "Cursor pagination only supports the default order; use offset paging for search and other sorts"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Cursor of the page after `rows`, when in keyset mode and the page was full
+    pub fn next_cursor<T>(
+        &self,
+        rows: &[T],
+        limit: i32,
+        key: impl Fn(&T) -> Cursor<Id>,
+    ) -> Option<Cursor<Id>> {
+        if !self.is_cursor() || (rows.len() as i32) < limit {
+            return None;
+        }
+        rows.last().map(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> Cursor {
+        Cursor::new(
+            DateTime::from_timestamp_micros(1_727_000_000_123_456).unwrap(),
+            Uuid::parse_str("6f1c2b1e-1d9a-4a3e-9c3b-1a2b3c4d5e6f").unwrap(),
+        )
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = cursor();
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+
+        let numeric = Cursor::new(cursor.at, 42i64);
+        assert_eq!(Cursor::<i64>::decode(&numeric.encode()).unwrap(), numeric);
+    }
+
+    #[test]
+    fn rejects_invalid_cursors() {
+        let encode = |text: &str| URL_SAFE_NO_PAD.encode(text);
+        for value in [
+            "not base64!".to_string(),
+            URL_SAFE_NO_PAD.encode([0xff, 0xfe]),
+            encode("1727000000123456"),
+            encode("yesterday.6f1c2b1e-1d9a-4a3e-9c3b-1a2b3c4d5e6f"),
+            encode("1727000000123456.not-a-uuid"),
+            encode(&format!(
+                "{}.6f1c2b1e-1d9a-4a3e-9c3b-1a2b3c4d5e6f",
+                i64::MAX
+            )),
+        ] {
+            assert!(Cursor::<Uuid>::decode(&value).is_err(), "{}", value);
+        }
+        assert!(Cursor::<i64>::decode(&cursor().encode()).is_err());
+    }
+
+    #[test]
+    fn page_request_picks_the_mode() {
+        assert_eq!(
+            PageRequest::<Uuid>::new(Some(20), None).unwrap(),
+            PageRequest::Offset(Some(20))
+        );
+        assert_eq!(
+            PageRequest::<Uuid>::new(Some(20), Some(" ")).unwrap(),
+            PageRequest::After(None)
+        );
+        let page = PageRequest::new(None, Some(&cursor().encode())).unwrap();
+        assert_eq!(page.after(), Some(cursor()));
+        assert_eq!(page.offset(), None);
+        assert!(PageRequest::<Uuid>::new(None, Some("bogus")).is_err());
+    }
+
+    #[test]
+    fn cursors_require_the_default_order() {
+        let page: PageRequest = PageRequest::After(None);
+        assert!(page.ensure_default_order(true).is_ok());
+        assert!(page.ensure_default_order(false).is_err());
+
+        let offset: PageRequest = PageRequest::Offset(None);
+        assert!(offset.ensure_default_order(false).is_ok());
+    }
+
+    #[test]
+    fn next_cursor_only_for_full_keyset_pages() {
+        let rows = vec![cursor(), Cursor::new(cursor().at, Uuid::nil())];
+        let key = |c: &Cursor| *c;
+
+        let page: PageRequest = PageRequest::After(None);
+        assert_eq!(page.next_cursor(&rows, 2, key), Some(rows[1]));
+        assert_eq!(page.next_cursor(&rows, 3, key), None);
+        assert_eq!(page.next_cursor(&[], 0, key), None);
+
+        let offset: PageRequest = PageRequest::Offset(None);
+        assert_eq!(offset.next_cursor(&rows, 2, key), None);
+    }
+}
@@ -25,6 +25,12 @@ pub struct Dao {
     pub cover_photo: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    /// Set when the listing was filtered by a search term
+    #[sqlx(default)]
+    pub search_rank: Option<f32>,
+    #[sqlx(default)]
+    pub search_snippet: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
@@ -49,6 +55,8 @@ pub struct DaoInfo {
     pub cover_photo: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub search_rank: Option<f32>,
+    pub search_snippet: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
@@ -133,6 +141,8 @@ impl Dao {
             cover_photo: self.cover_photo.clone(),
             created_at: self.created_at,
             updated_at: self.updated_at,
+            search_rank: self.search_rank,
+            search_snippet: self.search_snippet.clone(),
         }
     }
 
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserInfo;
+
+const WEIGHT_CATEGORY: f64 = 0.5;
+const WEIGHT_WORKLOAD: f64 = 0.3;
+const WEIGHT_TURNAROUND: f64 = 0.2;
+/// Turnaround at which the turnaround score drops to one half
+const TURNAROUND_HALF_LIFE_HOURS: f64 = 7.0 * 24.0;
+/// Score used for editors without any finished assignment yet
+const DEFAULT_TURNAROUND_SCORE: f64 = 0.5;
+
+/// Workload and history of an editor relative to one project
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct EditorCandidate {
+    pub user_id: Uuid,
+    pub category_overlap: i64,
+    pub open_assignments: i64,
+    pub completed_assignments: i64,
+    pub avg_turnaround_hours: Option<f64>,
+    pub has_conflict: bool,
+}
+
+impl EditorCandidate {
+    /// Weighted score in `0.0..=1.0`; editors with a conflict of interest score 0
+    pub fn score(&self, category_count: usize) -> f64 {
+        if self.has_conflict {
+            return 0.0;
+        }
+        let category = if category_count == 0 {
+            0.0
+        } else {
+            (self.category_overlap as f64 / category_count as f64).min(1.0)
+        };
+        let workload = 1.0 / (1.0 + self.open_assignments as f64);
+        let turnaround = self
+            .avg_turnaround_hours
+            .map(|h| 1.0 / (1.0 + h.max(0.0) / TURNAROUND_HALF_LIFE_HOURS))
+            .unwrap_or(DEFAULT_TURNAROUND_SCORE);
+        WEIGHT_CATEGORY * category + WEIGHT_WORKLOAD * workload + WEIGHT_TURNAROUND * turnaround
+    }
+
+    /// Orders editors without a conflict first, then by descending score
+    pub fn rank_cmp(&self, other: &Self, category_count: usize) -> Ordering {
+        self.has_conflict.cmp(&other.has_conflict).then(
+            other
+                .score(category_count)
+                .total_cmp(&self.score(category_count)),
+        )
+    }
+
+    pub fn to_recommendation(
+        &self,
+        editor: UserInfo,
+        category_count: usize,
+    ) -> EditorRecommendation {
+        EditorRecommendation {
+            editor,
+            score: self.score(category_count),
+            category_overlap: self.category_overlap,
+            open_assignments: self.open_assignments,
+            completed_assignments: self.completed_assignments,
+            avg_turnaround_hours: self.avg_turnaround_hours,
+            has_conflict: self.has_conflict,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EditorRecommendation {
+    pub editor: UserInfo,
+    pub score: f64,
+    pub category_overlap: i64,
+    pub open_assignments: i64,
+    pub completed_assignments: i64,
+    pub avg_turnaround_hours: Option<f64>,
+    /// Same institution as the researcher
+    pub has_conflict: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(overlap: i64, open: i64, turnaround: Option<f64>) -> EditorCandidate {
+        EditorCandidate {
+            user_id: Uuid::new_v4(),
+            category_overlap: overlap,
+            open_assignments: open,
+            completed_assignments: 0,
+            avg_turnaround_hours: turnaround,
+            has_conflict: false,
+        }
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn scores_a_perfect_candidate_as_one() {
+        assert_close(candidate(2, 0, Some(0.0)).score(2), 1.0);
+    }
+
+    #[test]
+    fn weighs_category_workload_and_turnaround() {
+        // Half the categories, one open assignment, one half life of turnaround
+        let score = candidate(1, 1, Some(TURNAROUND_HALF_LIFE_HOURS)).score(2);
+        assert_close(
+            score,
+            WEIGHT_CATEGORY * 0.5 + WEIGHT_WORKLOAD * 0.5 + WEIGHT_TURNAROUND * 0.5,
+        );
+    }
+
+    #[test]
+    fn uses_default_turnaround_without_history() {
+        let score = candidate(0, 0, None).score(0);
+        assert_close(
+            score,
+            WEIGHT_WORKLOAD + WEIGHT_TURNAROUND * DEFAULT_TURNAROUND_SCORE,
+        );
+    }
+
+    #[test]
+    fn caps_category_overlap() {
+        assert_close(
+            candidate(5, 0, Some(0.0)).score(2),
+            candidate(2, 0, Some(0.0)).score(2),
+        );
+    }
+
+    #[test]
+    fn scores_conflicts_as_zero() {
+        let mut conflicted = candidate(2, 0, Some(0.0));
+        conflicted.has_conflict = true;
+        assert_eq!(conflicted.score(2), 0.0);
+    }
+
+    #[test]
+    fn ranks_conflicts_last() {
+        let mut best = candidate(2, 0, Some(0.0));
+        best.has_conflict = true;
+        let weak = candidate(0, 5, None);
+        let good = candidate(1, 0, Some(24.0));
+        let mut candidates = [best.clone(), weak.clone(), good.clone()];
+        candidates.sort_by(|a, b| a.rank_cmp(b, 2));
+        let order: Vec<Uuid> = candidates.iter().map(|c| c.user_id).collect();
+        assert_eq!(order, vec![good.user_id, weak.user_id, best.user_id]);
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What users can follow and bookmark
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum FollowEntityType {
+    Project,
+    Bounty,
+    Dao,
+}
+
+define_pg_enum!(FollowEntityType {
+    Project = 0,
+    Bounty = 1,
+    Dao = 2,
+});
+
+/// A followed or bookmarked project, bounty or DAO. `created_at` is when
+/// the user saved it.
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedItem {
+    pub entity_type: FollowEntityType,
+    pub entity_id: Uuid,
+    pub nerd_id: String,
+    pub title: Option<String>,
+    pub cover_photo: Option<String>,
+    pub status: i16,
+    pub created_at: DateTime<Utc>,
+}
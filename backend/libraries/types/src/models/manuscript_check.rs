@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{AIErrorCheckStatus, AnalysisErrorType, ErrorStatistics};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FindingSeverity {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+define_pg_enum!(FindingSeverity {
+    Low = 0,
+    Medium = 1,
+    High = 2,
+    Critical = 3,
+});
+
+impl FindingSeverity {
+    /// Unknown labels count as medium
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "low" | "minor" => Self::Low,
+            "high" | "major" => Self::High,
+            "critical" | "severe" => Self::Critical,
+            _ => Self::Medium,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ManuscriptCheck {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub requested_by: Option<Uuid>,
+    pub manuscript: String,
+    pub status: i16,
+    pub error: Option<String>,
+    pub total_errors: i32,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When a running check is considered lost
+    pub lease_until: Option<DateTime<Utc>>,
+}
+
+/// One error found in a manuscript. `location` points into the manuscript,
+/// e.g. a section, page or equation.
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ManuscriptFinding {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub check_id: Uuid,
+    pub error_type: AnalysisErrorType,
+    pub severity: FindingSeverity,
+    pub title: String,
+    pub description: String,
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ManuscriptCheckInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub manuscript: String,
+    pub status: AIErrorCheckStatus,
+    pub error: Option<String>,
+    pub statistics: ErrorStatistics,
+    pub findings: Vec<ManuscriptFinding>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ManuscriptCheck {
+    pub fn to_info(
+        &self,
+        statistics: ErrorStatistics,
+        findings: Vec<ManuscriptFinding>,
+    ) -> ManuscriptCheckInfo {
+        ManuscriptCheckInfo {
+            id: self.id,
+            project_id: self.project_id,
+            manuscript: self.manuscript.clone(),
+            status: AIErrorCheckStatus::from(self.status),
+            error: self.error.clone(),
+            statistics,
+            findings,
+            created_at: self.created_at,
+            completed_at: self.completed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_severity_labels() {
+        assert_eq!(FindingSeverity::from_label("low"), FindingSeverity::Low);
+        assert_eq!(FindingSeverity::from_label("Minor"), FindingSeverity::Low);
+        assert_eq!(FindingSeverity::from_label(" HIGH "), FindingSeverity::High);
+        assert_eq!(FindingSeverity::from_label("major"), FindingSeverity::High);
+        assert_eq!(
+            FindingSeverity::from_label("Critical"),
+            FindingSeverity::Critical
+        );
+        assert_eq!(
+            FindingSeverity::from_label("severe"),
+            FindingSeverity::Critical
+        );
+        assert_eq!(
+            FindingSeverity::from_label("medium"),
+            FindingSeverity::Medium
+        );
+    }
+
+    #[test]
+    fn unknown_severity_is_medium() {
+        for label in ["", "urgent", "very high"] {
+            assert_eq!(FindingSeverity::from_label(label), FindingSeverity::Medium);
+        }
+    }
+}
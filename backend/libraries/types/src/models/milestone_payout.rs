@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Where the funds of an approved milestone are on their way to the
+/// researcher. Only ever moves forward.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum MilestonePayoutStatus {
+    /// An admin sent the approval to the funding contract
+    ApprovedByAdmin,
+    /// The contract confirmed the approval, the researcher can claim
+    Claimable,
+    Claimed,
+}
+
+define_pg_enum!(MilestonePayoutStatus {
+    ApprovedByAdmin = 0,
+    Claimable = 1,
+    Claimed = 2,
+});
+
+/// Payout of a milestone with the project and milestone it belongs to
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestonePayoutInfo {
+    pub milestone_id: Uuid,
+    pub project_id: Uuid,
+    pub project_nerd_id: String,
+    pub project_title: Option<String>,
+    pub number: i16,
+    pub milestone_title: String,
+    pub funding_amount: Option<i32>,
+    pub status: MilestonePayoutStatus,
+    pub approval_tx_hash: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub claimable_at: Option<DateTime<Utc>>,
+    pub claim_tx_hash: Option<String>,
+    pub claimed_amount: Option<i32>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum MilestoneReminderKind {
+    DueSoon,
+    Overdue,
+}
+
+define_pg_enum!(MilestoneReminderKind {
+    DueSoon = 0,
+    Overdue = 1,
+});
+
+/// A milestone that a reminder was just recorded for
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct MilestoneReminder {
+    pub milestone_id: Uuid,
+    pub project_id: Uuid,
+    pub project_nerd_id: String,
+    pub project_title: Option<String>,
+    pub researcher_id: Uuid,
+    pub number: i16,
+    pub milestone_title: String,
+    pub due_at: DateTime<Utc>,
+    pub days_before: i32,
+}
+
+impl MilestoneReminder {
+    pub fn project_name(&self) -> String {
+        self.project_title
+            .clone()
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| self.project_nerd_id.clone())
+    }
+}
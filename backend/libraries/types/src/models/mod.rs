@@ -1,37 +1,71 @@
 mod affiliation;
 mod bounty;
 mod city_list;
+mod comment;
+mod cursor;
 mod dao;
 mod degree;
+mod editor_recommendation;
 mod employments;
+mod follow;
 mod hashtags;
+mod manuscript_check;
+mod milestone_payout;
+mod milestone_reminder;
 mod notification;
 mod paper;
 mod prediction;
 mod prediction_placement;
 mod project;
+mod project_budget;
+mod project_collaborator;
+mod project_review;
+mod project_revision;
+mod project_update;
+mod report;
+mod search;
+mod similarity;
 mod speech;
 mod temp_user;
+mod trending;
 mod user;
 mod user_history;
 mod values;
 mod wallpaper;
+mod webhook;
 
 pub use affiliation::*;
 pub use bounty::*;
 pub use city_list::*;
+pub use comment::*;
+pub use cursor::*;
 pub use dao::*;
 pub use degree::*;
+pub use editor_recommendation::*;
 pub use employments::*;
+pub use follow::*;
 pub use hashtags::*;
+pub use manuscript_check::*;
+pub use milestone_payout::*;
+pub use milestone_reminder::*;
 pub use notification::*;
 pub use paper::*;
 pub use prediction::*;
 pub use prediction_placement::*;
 pub use project::*;
+pub use project_budget::*;
+pub use project_collaborator::*;
+pub use project_review::*;
+pub use project_revision::*;
+pub use project_update::*;
+pub use report::*;
+pub use search::*;
+pub use similarity::*;
 pub use speech::*;
 pub use temp_user::*;
+pub use trending::*;
 pub use user::*;
 pub use user_history::*;
 pub use values::*;
 pub use wallpaper::*;
+pub use webhook::*;
@@ -80,8 +80,15 @@ impl NotificationTab {
         match self {
             NotificationTab::All => vec![],
             NotificationTab::Unread => vec![],
-            NotificationTab::Funding => vec![NotificationType::FundingUpdate],
-            NotificationTab::DAO => vec![NotificationType::NewDAO, NotificationType::DAOVote],
+            NotificationTab::Funding => vec![
+                NotificationType::FundingUpdate,
+                NotificationType::FundingRefunded,
+            ],
+            NotificationTab::DAO => vec![
+                NotificationType::NewDAO,
+                NotificationType::DAOVote,
+                NotificationType::DaoOutcome,
+            ],
             NotificationTab::Projects => vec![
                 NotificationType::InviteEditor,
                 NotificationType::CancelEditor,
@@ -90,6 +97,20 @@ impl NotificationTab {
                 NotificationType::NewProject,
                 NotificationType::ProjectMilestone,
                 NotificationType::ProjectComment,
+                NotificationType::ReviewRequest,
+                NotificationType::ReviewReminder,
+                NotificationType::ReviewOverdue,
+                NotificationType::ReviewSubmitted,
+                NotificationType::CollaboratorInvitation,
+                NotificationType::CollaboratorResponse,
+                NotificationType::ProjectUpdate,
+                NotificationType::ProjectUpdateComment,
+                NotificationType::MilestoneFinalized,
+                NotificationType::CommentMention,
+                NotificationType::CommentReply,
+                NotificationType::AIAnalysisReady,
+                NotificationType::MilestoneDueSoon,
+                NotificationType::MilestoneOverdue,
             ],
             NotificationTab::Predictions => vec![
                 NotificationType::NewPrediction,
@@ -98,6 +119,7 @@ impl NotificationTab {
             NotificationTab::Site => vec![
                 NotificationType::NewMessage,
                 NotificationType::SystemMessage,
+                NotificationType::ContentRemoved,
             ],
         }
     }
@@ -125,6 +147,24 @@ pub enum NotificationType {
     FundingUpdate,
     PredictionResult,
     SystemMessage,
+    ReviewRequest,
+    ReviewReminder,
+    ReviewOverdue,
+    ReviewSubmitted,
+    CollaboratorInvitation,
+    CollaboratorResponse,
+    ProjectUpdate,
+    ProjectUpdateComment,
+    MilestoneFinalized,
+    DaoOutcome,
+    BountyStatusChange,
+    CommentMention,
+    CommentReply,
+    ContentRemoved,
+    AIAnalysisReady,
+    MilestoneDueSoon,
+    MilestoneOverdue,
+    FundingRefunded,
 }
 
 impl From<NotificationType> for i32 {
@@ -149,6 +189,69 @@ impl From<NotificationType> for i32 {
             NotificationType::FundingUpdate => 16,
             NotificationType::PredictionResult => 17,
             NotificationType::SystemMessage => 18,
+            NotificationType::ReviewRequest => 19,
+            NotificationType::ReviewReminder => 20,
+            NotificationType::ReviewOverdue => 21,
+            NotificationType::ReviewSubmitted => 22,
+            NotificationType::CollaboratorInvitation => 23,
+            NotificationType::CollaboratorResponse => 24,
+            NotificationType::ProjectUpdate => 25,
+            NotificationType::ProjectUpdateComment => 26,
+            NotificationType::MilestoneFinalized => 27,
+            NotificationType::DaoOutcome => 28,
+            NotificationType::BountyStatusChange => 29,
+            NotificationType::CommentMention => 30,
+            NotificationType::CommentReply => 31,
+            NotificationType::ContentRemoved => 32,
+            NotificationType::AIAnalysisReady => 33,
+            NotificationType::MilestoneDueSoon => 34,
+            NotificationType::MilestoneOverdue => 35,
+            NotificationType::FundingRefunded => 36,
+        }
+    }
+}
+
+impl NotificationType {
+    /// Name of the localized title/message templates for this type.
+    pub fn template_name(&self) -> &'static str {
+        match self {
+            NotificationType::InviteEditor => "invite_editor",
+            NotificationType::CancelEditor => "cancel_editor",
+            NotificationType::AcceptEditor => "accept_editor",
+            NotificationType::DeclineEditor => "decline_editor",
+            NotificationType::NewBounty => "new_bounty",
+            NotificationType::NewDAO => "new_dao",
+            NotificationType::NewPrediction => "new_prediction",
+            NotificationType::NewMessage => "new_message",
+            NotificationType::ApprovedBid => "approved_bid",
+            NotificationType::RejectedBid => "rejected_bid",
+            NotificationType::BidReviewed => "bid_reviewed",
+            NotificationType::NewProject => "new_project",
+            NotificationType::ProjectMilestone => "project_milestone",
+            NotificationType::ProjectComment => "project_comment",
+            NotificationType::BountyComment => "bounty_comment",
+            NotificationType::DAOVote => "dao_vote",
+            NotificationType::FundingUpdate => "funding_update",
+            NotificationType::PredictionResult => "prediction_result",
+            NotificationType::SystemMessage => "system_message",
+            NotificationType::ReviewRequest => "review_request",
+            NotificationType::ReviewReminder => "review_reminder",
+            NotificationType::ReviewOverdue => "review_overdue",
+            NotificationType::ReviewSubmitted => "review_submitted",
+            NotificationType::CollaboratorInvitation => "collaborator_invitation",
+            NotificationType::CollaboratorResponse => "collaborator_response",
+            NotificationType::ProjectUpdate => "project_update",
+            NotificationType::ProjectUpdateComment => "project_update_comment",
+            NotificationType::MilestoneFinalized => "milestone_finalized",
+            NotificationType::DaoOutcome => "dao_outcome",
+            NotificationType::BountyStatusChange => "bounty_status_change",
+            NotificationType::CommentMention => "comment_mention",
+            NotificationType::CommentReply => "comment_reply",
+            NotificationType::ContentRemoved => "content_removed",
+            NotificationType::AIAnalysisReady => "ai_analysis_ready",
+            NotificationType::MilestoneDueSoon => "milestone_due_soon",
+            NotificationType::MilestoneOverdue => "milestone_overdue",
+            NotificationType::FundingRefunded => "funding_refunded",
         }
     }
 }
@@ -177,11 +280,35 @@ impl TryFrom<i32> for NotificationType {
             16 => Ok(Self::FundingUpdate),
             17 => Ok(Self::PredictionResult),
             18 => Ok(Self::SystemMessage),
+            19 => Ok(Self::ReviewRequest),
+            20 => Ok(Self::ReviewReminder),
+            21 => Ok(Self::ReviewOverdue),
+            22 => Ok(Self::ReviewSubmitted),
+            23 => Ok(Self::CollaboratorInvitation),
+            24 => Ok(Self::CollaboratorResponse),
+            25 => Ok(Self::ProjectUpdate),
+            26 => Ok(Self::ProjectUpdateComment),
+            27 => Ok(Self::MilestoneFinalized),
+            28 => Ok(Self::DaoOutcome),
+            29 => Ok(Self::BountyStatusChange),
+            30 => Ok(Self::CommentMention),
+            31 => Ok(Self::CommentReply),
+            32 => Ok(Self::ContentRemoved),
+            33 => Ok(Self::AIAnalysisReady),
+            34 => Ok(Self::MilestoneDueSoon),
+            35 => Ok(Self::MilestoneOverdue),
+            36 => Ok(Self::FundingRefunded),
             _ => Err(format!("Invalid value for NotificationType: {}", value)),
         }
     }
 }
 
+/// Latest actors of a collapsed notification that are returned to clients
+pub const MAX_NOTIFICATION_ACTORS: usize = 3;
+/// Latest actors of a collapsed notification that are stored. An actor that
+/// dropped off the list counts again when they come back.
+pub const MAX_STORED_NOTIFICATION_ACTORS: i32 = 50;
+
 #[derive(Clone, Debug, Deserialize, Serialize, FromRow)]
 pub struct Notification {
     pub id: i64,
@@ -191,6 +318,12 @@ pub struct Notification {
     pub message: String,
     pub data: Option<Value>,
     pub is_read: bool,
+    pub collapse_key: Option<String>,
+    /// Number of distinct actors when the notification is collapsed
+    pub group_count: i32,
+    /// Distinct actors of a collapsed notification, latest first, up to
+    /// `MAX_STORED_NOTIFICATION_ACTORS`
+    pub actors: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -203,6 +336,11 @@ pub struct CreateNotification {
     pub title: String,
     pub message: String,
     pub data: Option<Value>,
+    /// Unread notifications sharing a collapse key are merged into one entry
+    #[serde(default)]
+    pub collapse_key: Option<String>,
+    #[serde(default)]
+    pub actor: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -215,6 +353,9 @@ pub struct NotificationResponse {
     pub message: String,
     pub data: Option<Value>,
     pub is_read: bool,
+    pub collapse_key: Option<String>,
+    pub group_count: i32,
+    pub actors: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -230,6 +371,13 @@ impl From<Notification> for NotificationResponse {
             message: notification.message,
             data: notification.data,
             is_read: notification.is_read,
+            collapse_key: notification.collapse_key,
+            group_count: notification.group_count,
+            actors: notification
+                .actors
+                .into_iter()
+                .take(MAX_NOTIFICATION_ACTORS)
+                .collect(),
             created_at: notification.created_at,
             updated_at: notification.updated_at,
         }
@@ -240,3 +388,139 @@ impl From<Notification> for NotificationResponse {
 pub struct NotificationCount {
     pub count: i64,
 }
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default, PartialEq)]
+pub enum AnnouncementSegment {
+    #[default]
+    All,
+    Role,
+    Tier,
+    Interest,
+    ProjectFunders,
+}
+
+impl From<AnnouncementSegment> for i16 {
+    fn from(segment: AnnouncementSegment) -> i16 {
+        match segment {
+            AnnouncementSegment::All => 0,
+            AnnouncementSegment::Role => 1,
+            AnnouncementSegment::Tier => 2,
+            AnnouncementSegment::Interest => 3,
+            AnnouncementSegment::ProjectFunders => 4,
+        }
+    }
+}
+
+impl TryFrom<i16> for AnnouncementSegment {
+    type Error = String;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::All),
+            1 => Ok(Self::Role),
+            2 => Ok(Self::Tier),
+            3 => Ok(Self::Interest),
+            4 => Ok(Self::ProjectFunders),
+            _ => Err(format!("Invalid value for AnnouncementSegment: {}", value)),
+        }
+    }
+}
+
+impl AnnouncementSegment {
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            AnnouncementSegment::All => MessageType::All,
+            _ => MessageType::One,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default, PartialEq)]
+pub enum AnnouncementStatus {
+    #[default]
+    Scheduled,
+    /// Not written anymore, sending moves an announcement from scheduled to
+    /// sent in one transaction
+    Sending,
+    Sent,
+    Cancelled,
+}
+
+impl From<AnnouncementStatus> for i16 {
+    fn from(status: AnnouncementStatus) -> i16 {
+        match status {
+            AnnouncementStatus::Scheduled => 0,
+            AnnouncementStatus::Sending => 1,
+            AnnouncementStatus::Sent => 2,
+            AnnouncementStatus::Cancelled => 3,
+        }
+    }
+}
+
+impl TryFrom<i16> for AnnouncementStatus {
+    type Error = String;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Scheduled),
+            1 => Ok(Self::Sending),
+            2 => Ok(Self::Sent),
+            3 => Ok(Self::Cancelled),
+            _ => Err(format!("Invalid value for AnnouncementStatus: {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, FromRow)]
+pub struct Announcement {
+    pub id: Uuid,
+    /// None once the sending admin was deleted
+    pub sender_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub segment_type: i16,
+    pub segment_value: Option<String>,
+    pub status: i16,
+    pub recipient_count: i32,
+    pub scheduled_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementResponse {
+    pub id: Uuid,
+    pub sender_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub message_type: MessageType,
+    pub segment: AnnouncementSegment,
+    pub segment_value: Option<String>,
+    pub status: AnnouncementStatus,
+    pub recipient_count: i32,
+    pub scheduled_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Announcement> for AnnouncementResponse {
+    fn from(announcement: Announcement) -> Self {
+        let segment = AnnouncementSegment::try_from(announcement.segment_type).unwrap_or_default();
+        Self {
+            id: announcement.id,
+            sender_id: announcement.sender_id,
+            title: announcement.title,
+            message: announcement.message,
+            message_type: segment.message_type(),
+            segment,
+            segment_value: announcement.segment_value,
+            status: AnnouncementStatus::try_from(announcement.status).unwrap_or_default(),
+            recipient_count: announcement.recipient_count,
+            scheduled_at: announcement.scheduled_at,
+            sent_at: announcement.sent_at,
+            created_at: announcement.created_at,
+        }
+    }
+}
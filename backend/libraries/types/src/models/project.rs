@@ -1,8 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::UserInfo;
+use super::{CommentInfo, ProjectCollaboratorInfo, UserInfo};
+use crate::{FeedbackStatus, MileStoneStatus};
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
 pub struct Project {
@@ -77,6 +78,7 @@ pub struct ProjectInfo {
 
     pub team_members: Vec<TeamMember>,
     pub milestones: Vec<Milestone>,
+    pub has_overdue_milestones: bool,
 
     pub ai_analysis: Option<String>,
     pub ai_status: Option<i16>,
@@ -96,6 +98,37 @@ pub struct ProjectInfo {
     pub updated_at: DateTime<Utc>,
     pub dao_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
+
+    pub status_history: Vec<ProjectStatusHistoryInfo>,
+    pub collaborators: Vec<ProjectCollaboratorInfo>,
+
+    /// Whether the signed in user follows or bookmarked the project
+    pub is_following: bool,
+    pub is_bookmarked: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
+pub struct ProjectStatusHistory {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub from_status: i16,
+    pub to_status: i16,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatusHistoryInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// `None` for changes driven by on-chain events
+    pub actor: Option<UserInfo>,
+    pub from_status: i16,
+    pub to_status: i16,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
@@ -137,6 +170,11 @@ pub struct Milestone {
     pub arweave_tx_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// `days_after_start` days after the project started
+    #[sqlx(skip)]
+    pub due_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    pub is_overdue: bool,
 }
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
@@ -150,6 +188,30 @@ pub struct Funding {
     pub amount: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub refunded_amount: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FundingRefundStatus {
+    #[default]
+    NotRefunded,
+    PartiallyRefunded,
+    Refunded,
+}
+
+/// Part of a `Refunded` event of the funding contract, applied to one funding
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRefund {
+    pub id: Uuid,
+    pub funding_id: Uuid,
+    pub project_id: Uuid,
+    pub wallet: String,
+    pub amount: i32,
+    pub created_at: DateTime<Utc>,
+    /// The `Refunded` event it was applied from
+    pub tx_hash: Option<String>,
+    pub log_index: Option<i64>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
@@ -197,6 +259,12 @@ pub struct ProjectItem {
     pub updated_at: DateTime<Utc>,
     pub dao_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
+
+    /// Set when the listing was filtered by a search term
+    #[sqlx(default)]
+    pub search_rank: Option<f32>,
+    #[sqlx(default)]
+    pub search_snippet: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
@@ -225,6 +293,8 @@ pub struct ProjectItemInfo {
     pub updated_at: DateTime<Utc>,
     pub dao_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
+    pub search_rank: Option<f32>,
+    pub search_snippet: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
@@ -257,24 +327,19 @@ pub struct ProjectIds {
     pub nerd_id: String,
 }
 
+/// A submitted project claimed for AI review
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Default, Debug)]
-pub struct ProjectComment {
+pub struct AIReviewTask {
     pub id: Uuid,
-    pub user_id: Uuid,
-    pub project_id: Uuid,
     pub nerd_id: String,
-    pub comment: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
-
-#[derive(Clone, Deserialize, Serialize, Default, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct ProjectCommentInfo {
-    pub id: Uuid,
-    pub user: UserInfo,
-    pub comment: String,
-    pub updated_at: DateTime<Utc>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub details: Option<String>,
+    pub manuscript: Option<String>,
+    pub funding_goal: Option<i32>,
+    pub duration: Option<i32>,
+    pub tags: Vec<String>,
+    pub ai_attempts: i32,
 }
 
 impl Project {
@@ -285,6 +350,10 @@ impl Project {
         team_members: Vec<TeamMember>,
         milestones: Vec<Milestone>,
     ) -> ProjectInfo {
+        let milestones: Vec<Milestone> = milestones
+            .into_iter()
+            .map(|m| m.with_due_date(self.started_at))
+            .collect();
         ProjectInfo {
             id: self.id,
             nerd_id: self.nerd_id.clone(),
@@ -308,6 +377,7 @@ impl Project {
             other_cost: self.other_cost,
             tags: self.tags.clone(),
             team_members,
+            has_overdue_milestones: milestones.iter().any(|m| m.is_overdue),
             milestones,
             ai_analysis: self.ai_analysis.clone(),
             ai_status: self.ai_status,
@@ -324,6 +394,24 @@ impl Project {
             updated_at: self.updated_at,
             dao_at: self.dao_at,
             started_at: self.started_at,
+            status_history: Vec::new(),
+            collaborators: Vec::new(),
+            is_following: false,
+            is_bookmarked: false,
+        }
+    }
+}
+
+impl ProjectStatusHistory {
+    pub fn to_info(&self, actor: Option<UserInfo>) -> ProjectStatusHistoryInfo {
+        ProjectStatusHistoryInfo {
+            id: self.id,
+            project_id: self.project_id,
+            actor,
+            from_status: self.from_status,
+            to_status: self.to_status,
+            reason: self.reason.clone(),
+            created_at: self.created_at,
         }
     }
 }
@@ -355,16 +443,56 @@ impl ProjectItem {
             funding_amount: self.funding_amount,
             count_contributors: self.count_contributors,
             count_view: self.count_view,
-            milestones,
+            milestones: milestones
+                .into_iter()
+                .map(|m| m.with_due_date(self.started_at))
+                .collect(),
             funders,
             created_at: self.created_at,
             updated_at: self.updated_at,
             dao_at: self.dao_at,
             started_at: self.started_at,
+            search_rank: self.search_rank,
+            search_snippet: self.search_snippet.clone(),
+        }
+    }
+}
+
+impl Funding {
+    pub fn refund_status(&self) -> FundingRefundStatus {
+        if self.refunded_amount <= 0 {
+            FundingRefundStatus::NotRefunded
+        } else if self.refunded_amount < self.amount {
+            FundingRefundStatus::PartiallyRefunded
+        } else {
+            FundingRefundStatus::Refunded
         }
     }
 }
 
+impl Milestone {
+    /// Proof is expected while the milestone is pending or in process and
+    /// none was submitted, or it was rejected
+    pub fn awaits_proof(&self) -> bool {
+        [
+            MileStoneStatus::Pending.to_i16(),
+            MileStoneStatus::InProgress.to_i16(),
+        ]
+        .contains(&self.status)
+            && [
+                FeedbackStatus::Pending.to_i16(),
+                FeedbackStatus::Rejected.to_i16(),
+            ]
+            .contains(&self.proof_status)
+    }
+
+    pub fn with_due_date(mut self, started_at: Option<DateTime<Utc>>) -> Self {
+        self.due_at = started_at.map(|s| s + Duration::days(self.days_after_start as i64));
+        self.is_overdue = self.awaits_proof() && self.due_at.is_some_and(|d| d <= Utc::now());
+        self
+    }
+}
+
 impl ProjectEditor {
     pub fn to_info(&self, is_full: bool, user: UserInfo) -> ProjectEditorInfo {
         ProjectEditorInfo {
@@ -378,13 +506,132 @@ impl ProjectEditor {
     }
 }
 
-impl ProjectComment {
-    pub fn to_info(&self, user: UserInfo) -> ProjectCommentInfo {
-        ProjectCommentInfo {
-            id: self.id,
-            user,
-            comment: self.comment.clone(),
-            updated_at: self.updated_at,
+/// Flat comment as listed by `GET /project/:id/comment`. The threaded
+/// listing returns `CommentInfo`.
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectCommentInfo {
+    pub id: Uuid,
+    pub user: UserInfo,
+    pub comment: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CommentInfo> for ProjectCommentInfo {
+    fn from(comment: CommentInfo) -> Self {
+        Self {
+            id: comment.id,
+            user: comment.user,
+            comment: comment.comment,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funding(amount: i32, refunded_amount: i32) -> Funding {
+        Funding {
+            amount,
+            refunded_amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn refund_status_follows_refunded_amount() {
+        assert_eq!(
+            funding(100, 0).refund_status(),
+            FundingRefundStatus::NotRefunded
+        );
+        assert_eq!(
+            funding(100, 40).refund_status(),
+            FundingRefundStatus::PartiallyRefunded
+        );
+        assert_eq!(
+            funding(100, 99).refund_status(),
+            FundingRefundStatus::PartiallyRefunded
+        );
+        assert_eq!(
+            funding(100, 100).refund_status(),
+            FundingRefundStatus::Refunded
+        );
+    }
+
+    #[test]
+    fn refund_status_tolerates_odd_amounts() {
+        assert_eq!(
+            funding(100, -5).refund_status(),
+            FundingRefundStatus::NotRefunded
+        );
+        assert_eq!(
+            funding(100, 120).refund_status(),
+            FundingRefundStatus::Refunded
+        );
+        assert_eq!(
+            funding(0, 0).refund_status(),
+            FundingRefundStatus::NotRefunded
+        );
+    }
+
+    fn milestone(status: MileStoneStatus, proof_status: FeedbackStatus) -> Milestone {
+        Milestone {
+            status: status.to_i16(),
+            proof_status: proof_status.to_i16(),
+            days_after_start: 30,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn awaits_proof_while_open_without_proof() {
+        for status in [MileStoneStatus::Pending, MileStoneStatus::InProgress] {
+            assert!(milestone(status.clone(), FeedbackStatus::Pending).awaits_proof());
+            assert!(milestone(status.clone(), FeedbackStatus::Rejected).awaits_proof());
+            assert!(!milestone(status.clone(), FeedbackStatus::Accepted).awaits_proof());
+            assert!(!milestone(status, FeedbackStatus::RevisionRequired).awaits_proof());
+        }
+        for status in [
+            MileStoneStatus::Success,
+            MileStoneStatus::GiveUp,
+            MileStoneStatus::Failed,
+        ] {
+            assert!(!milestone(status, FeedbackStatus::Pending).awaits_proof());
         }
     }
+
+    #[test]
+    fn due_date_needs_a_start() {
+        let m = milestone(MileStoneStatus::Pending, FeedbackStatus::Pending).with_due_date(None);
+        assert_eq!(m.due_at, None);
+        assert!(!m.is_overdue);
+    }
+
+    #[test]
+    fn overdue_from_the_due_date_on() {
+        let started_at = Utc::now() - Duration::days(30);
+        let m = milestone(MileStoneStatus::InProgress, FeedbackStatus::Pending)
+            .with_due_date(Some(started_at));
+        assert_eq!(m.due_at, Some(started_at + Duration::days(30)));
+        assert!(m.is_overdue);
+
+        let started_at = Utc::now() - Duration::days(30) + Duration::minutes(1);
+        let m = milestone(MileStoneStatus::InProgress, FeedbackStatus::Pending)
+            .with_due_date(Some(started_at));
+        assert!(!m.is_overdue);
+    }
+
+    #[test]
+    fn submitted_proof_is_never_overdue() {
+        let started_at = Some(Utc::now() - Duration::days(60));
+        let m = milestone(MileStoneStatus::InProgress, FeedbackStatus::Accepted)
+            .with_due_date(started_at);
+        assert!(m.due_at.is_some());
+        assert!(!m.is_overdue);
+        let m =
+            milestone(MileStoneStatus::Success, FeedbackStatus::Pending).with_due_date(started_at);
+        assert!(!m.is_overdue);
+    }
 }
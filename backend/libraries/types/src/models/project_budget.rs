@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Milestone, Project};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum BudgetViolationCode {
+    MissingFundingGoal,
+    MissingDuration,
+    NegativeAmount,
+    /// Step 2 costs don't add up to the funding goal
+    CostsMismatch,
+    NoMilestones,
+    /// Milestone funding amounts don't add up to the funding goal
+    MilestoneFundingMismatch,
+    MilestoneDaysNotIncreasing,
+    MilestoneBeyondDuration,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetViolation {
+    pub code: BudgetViolationCode,
+    pub field: String,
+    pub message: String,
+    pub milestone_number: Option<i16>,
+    pub expected: Option<i64>,
+    pub actual: Option<i64>,
+}
+
+impl BudgetViolation {
+    fn new(code: BudgetViolationCode, field: &str, message: String) -> Self {
+        Self {
+            code,
+            field: field.to_string(),
+            message,
+            milestone_number: None,
+            expected: None,
+            actual: None,
+        }
+    }
+
+    fn amounts(mut self, expected: i64, actual: i64) -> Self {
+        self.expected = Some(expected);
+        self.actual = Some(actual);
+        self
+    }
+
+    fn milestone(mut self, number: i16) -> Self {
+        self.milestone_number = Some(number);
+        self
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneBudget {
+    pub number: i16,
+    pub funding_amount: i32,
+    pub days_after_start: i32,
+}
+
+/// The money and schedule parts of a project, as entered in steps 1 to 3
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBudget {
+    pub funding_goal: Option<i32>,
+    pub duration: Option<i32>,
+    pub personnel_cost: Option<i32>,
+    pub equipment_cost: Option<i32>,
+    pub materials_cost: Option<i32>,
+    pub overhead_cost: Option<i32>,
+    pub other_cost: Option<i32>,
+    pub milestones: Vec<MilestoneBudget>,
+}
+
+impl ProjectBudget {
+    pub fn from_project(project: &Project, milestones: &[Milestone]) -> Self {
+        let mut milestones: Vec<MilestoneBudget> = milestones
+            .iter()
+            .map(|m| MilestoneBudget {
+                number: m.number,
+                funding_amount: m.funding_amount,
+                days_after_start: m.days_after_start,
+            })
+            .collect();
+        milestones.sort_by_key(|m| m.number);
+        Self {
+            funding_goal: project.funding_goal,
+            duration: project.duration,
+            personnel_cost: project.personnel_cost,
+            equipment_cost: project.equipment_cost,
+            materials_cost: project.materials_cost,
+            overhead_cost: project.overhead_cost,
+            other_cost: project.other_cost,
+            milestones,
+        }
+    }
+
+    /// Checks costs and milestones against the funding goal and duration.
+    /// Milestones are expected in order of their number.
+    pub fn validate(&self) -> Vec<BudgetViolation> {
+        let mut violations = Vec::new();
+
+        let costs = [
+            ("personnelCost", self.personnel_cost),
+            ("equipmentCost", self.equipment_cost),
+            ("materialsCost", self.materials_cost),
+            ("overheadCost", self.overhead_cost),
+            ("otherCost", self.other_cost),
+        ];
+        for (field, cost) in costs {
+            if cost.unwrap_or_default() < 0 {
+                violations.push(BudgetViolation::new(
+                    BudgetViolationCode::NegativeAmount,
+                    field,
+                    format!("{} can't be negative", field),
+                ));
+            }
+        }
+        let total_costs: i64 = costs
+            .iter()
+            .map(|(_, cost)| cost.unwrap_or_default() as i64)
+            .sum();
+
+        match self.funding_goal {
+            Some(goal) if goal > 0 => {
+                if total_costs != goal as i64 {
+                    violations.push(
+                        BudgetViolation::new(
+                            BudgetViolationCode::CostsMismatch,
+                            "fundingGoal",
+                            format!(
+                                "Costs add up to {} but the funding goal is {}",
+                                total_costs, goal
+                            ),
+                        )
+                        .amounts(goal as i64, total_costs),
+                    );
+                }
+            }
+            _ => violations.push(BudgetViolation::new(
+                BudgetViolationCode::MissingFundingGoal,
+                "fundingGoal",
+                "Funding goal must be set".to_string(),
+            )),
+        }
+
+        let duration = match self.duration {
+            Some(duration) if duration > 0 => Some(duration),
+            _ => {
+                violations.push(BudgetViolation::new(
+                    BudgetViolationCode::MissingDuration,
+                    "duration",
+                    "Duration must be set".to_string(),
+                ));
+                None
+            }
+        };
+
+        if self.milestones.is_empty() {
+            violations.push(BudgetViolation::new(
+                BudgetViolationCode::NoMilestones,
+                "milestones",
+                "At least one milestone is required".to_string(),
+            ));
+            return violations;
+        }
+
+        let mut previous_days: Option<i32> = None;
+        for milestone in &self.milestones {
+            if milestone.funding_amount < 0 {
+                violations.push(
+                    BudgetViolation::new(
+                        BudgetViolationCode::NegativeAmount,
+                        "fundingAmount",
+                        format!(
+                            "Milestone {} funding amount can't be negative",
+                            milestone.number
+                        ),
+                    )
+                    .milestone(milestone.number),
+                );
+            }
+            if let Some(previous) = previous_days {
+                if milestone.days_after_start <= previous {
+                    violations.push(
+                        BudgetViolation::new(
+                            BudgetViolationCode::MilestoneDaysNotIncreasing,
+                            "daysAfterStart",
+                            format!(
+                                "Milestone {} must be due after day {}",
+                                milestone.number, previous
+                            ),
+                        )
+                        .milestone(milestone.number)
+                        .amounts(previous as i64 + 1, milestone.days_after_start as i64),
+                    );
+                }
+            }
+            if let Some(duration) = duration {
+                if milestone.days_after_start > duration {
+                    violations.push(
+                        BudgetViolation::new(
+                            BudgetViolationCode::MilestoneBeyondDuration,
+                            "daysAfterStart",
+                            format!(
+                                "Milestone {} is due after the project duration of {} days",
+                                milestone.number, duration
+                            ),
+                        )
+                        .milestone(milestone.number)
+                        .amounts(duration as i64, milestone.days_after_start as i64),
+                    );
+                }
+            }
+            previous_days = Some(milestone.days_after_start);
+        }
+
+        if let Some(goal) = self.funding_goal.filter(|goal| *goal > 0) {
+            let total_milestones: i64 = self
+                .milestones
+                .iter()
+                .map(|m| m.funding_amount as i64)
+                .sum();
+            if total_milestones != goal as i64 {
+                violations.push(
+                    BudgetViolation::new(
+                        BudgetViolationCode::MilestoneFundingMismatch,
+                        "milestones",
+                        format!(
+                            "Milestone funding adds up to {} but the funding goal is {}",
+                            total_milestones, goal
+                        ),
+                    )
+                    .amounts(goal as i64, total_milestones),
+                );
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone(number: i16, funding_amount: i32, days_after_start: i32) -> MilestoneBudget {
+        MilestoneBudget {
+            number,
+            funding_amount,
+            days_after_start,
+        }
+    }
+
+    fn budget(milestones: Vec<MilestoneBudget>) -> ProjectBudget {
+        ProjectBudget {
+            funding_goal: Some(1000),
+            duration: Some(90),
+            personnel_cost: Some(600),
+            equipment_cost: Some(200),
+            materials_cost: Some(100),
+            overhead_cost: Some(50),
+            other_cost: Some(50),
+            milestones,
+        }
+    }
+
+    fn codes(budget: &ProjectBudget) -> Vec<BudgetViolationCode> {
+        budget.validate().iter().map(|v| v.code).collect()
+    }
+
+    #[test]
+    fn accepts_a_balanced_budget() {
+        let budget = budget(vec![milestone(1, 400, 30), milestone(2, 600, 90)]);
+        assert!(budget.validate().is_empty());
+    }
+
+    #[test]
+    fn reports_costs_over_the_funding_goal() {
+        let mut budget = budget(vec![milestone(1, 1000, 90)]);
+        budget.equipment_cost = Some(300);
+        let violations = budget.validate();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, BudgetViolationCode::CostsMismatch);
+        assert_eq!(violations[0].expected, Some(1000));
+        assert_eq!(violations[0].actual, Some(1100));
+    }
+
+    #[test]
+    fn reports_milestone_funding_that_does_not_add_up() {
+        let over = budget(vec![milestone(1, 700, 30), milestone(2, 600, 90)]);
+        let violations = over.validate();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].code,
+            BudgetViolationCode::MilestoneFundingMismatch
+        );
+        assert_eq!(violations[0].actual, Some(1300));
+
+        let under = budget(vec![milestone(1, 400, 30)]);
+        assert_eq!(
+            codes(&under),
+            vec![BudgetViolationCode::MilestoneFundingMismatch]
+        );
+    }
+
+    #[test]
+    fn requires_at_least_one_milestone() {
+        assert_eq!(
+            codes(&budget(vec![])),
+            vec![BudgetViolationCode::NoMilestones]
+        );
+    }
+
+    #[test]
+    fn requires_a_funding_goal_and_duration() {
+        let mut budget = budget(vec![milestone(1, 1000, 90)]);
+        budget.funding_goal = None;
+        budget.duration = Some(0);
+        assert_eq!(
+            codes(&budget),
+            vec![
+                BudgetViolationCode::MissingFundingGoal,
+                BudgetViolationCode::MissingDuration,
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_milestone_schedule() {
+        let mut budget = budget(vec![
+            milestone(1, 300, 60),
+            milestone(2, 300, 60),
+            milestone(3, 400, 120),
+        ]);
+        budget.other_cost = Some(-50);
+        budget.personnel_cost = Some(700);
+        let violations = budget.validate();
+        assert_eq!(
+            violations.iter().map(|v| v.code).collect::<Vec<_>>(),
+            vec![
+                BudgetViolationCode::NegativeAmount,
+                BudgetViolationCode::MilestoneDaysNotIncreasing,
+                BudgetViolationCode::MilestoneBeyondDuration,
+            ]
+        );
+        assert_eq!(violations[1].milestone_number, Some(2));
+        assert_eq!(violations[2].milestone_number, Some(3));
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserInfo;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CollaboratorRole {
+    /// Can edit the project and submit milestone proofs
+    CoPi,
+    /// Can read drafts
+    #[default]
+    Viewer,
+}
+
+define_pg_enum!(CollaboratorRole {
+    CoPi = 0,
+    Viewer = 1,
+});
+
+impl CollaboratorRole {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CollaboratorRole::CoPi => "co-PI",
+            CollaboratorRole::Viewer => "viewer",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CollaboratorStatus {
+    #[default]
+    Invited,
+    Accepted,
+    Declined,
+}
+
+define_pg_enum!(CollaboratorStatus {
+    Invited = 0,
+    Accepted = 1,
+    Declined = 2,
+});
+
+/// What a user wants to do with a project, checked against ownership and
+/// collaborator role
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProjectPermission {
+    Read,
+    Edit,
+    /// Delete the project and manage its collaborators; owner only
+    Manage,
+}
+
+impl ProjectPermission {
+    pub fn allows(&self, role: CollaboratorRole) -> bool {
+        match self {
+            ProjectPermission::Read => true,
+            ProjectPermission::Edit => role == CollaboratorRole::CoPi,
+            ProjectPermission::Manage => false,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectCollaborator {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub role: CollaboratorRole,
+    pub status: CollaboratorStatus,
+    pub invited_by: Option<Uuid>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectCollaborator {
+    pub fn to_info(&self, user: UserInfo) -> ProjectCollaboratorInfo {
+        ProjectCollaboratorInfo {
+            id: self.id,
+            project_id: self.project_id,
+            user,
+            role: self.role,
+            status: self.status,
+            responded_at: self.responded_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectCollaboratorInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user: UserInfo,
+    pub role: CollaboratorRole,
+    pub status: CollaboratorStatus,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A pending invitation with the project it is for
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct CollaborationInvitation {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub project_title: Option<String>,
+    pub role: CollaboratorRole,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CollaborationInvitation {
+    pub fn to_info(&self, invited_by: Option<UserInfo>) -> CollaborationInvitationInfo {
+        CollaborationInvitationInfo {
+            id: self.id,
+            project_id: self.project_id,
+            project_title: self.project_title.clone(),
+            role: self.role,
+            invited_by,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CollaborationInvitationInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub project_title: Option<String>,
+    pub role: CollaboratorRole,
+    pub invited_by: Option<UserInfo>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn co_pis_can_read_and_edit() {
+        assert!(ProjectPermission::Read.allows(CollaboratorRole::CoPi));
+        assert!(ProjectPermission::Edit.allows(CollaboratorRole::CoPi));
+    }
+
+    #[test]
+    fn viewers_can_only_read() {
+        assert!(ProjectPermission::Read.allows(CollaboratorRole::Viewer));
+        assert!(!ProjectPermission::Edit.allows(CollaboratorRole::Viewer));
+    }
+
+    #[test]
+    fn only_the_owner_can_manage() {
+        assert!(!ProjectPermission::Manage.allows(CollaboratorRole::CoPi));
+        assert!(!ProjectPermission::Manage.allows(CollaboratorRole::Viewer));
+    }
+}
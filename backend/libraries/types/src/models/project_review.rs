@@ -0,0 +1,302 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{models::UserInfo, FeedbackStatus};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ProjectReviewStatus {
+    Pending,
+    Submitted,
+    Cancelled,
+}
+
+define_pg_enum!(ProjectReviewStatus {
+    Pending = 0,
+    Submitted = 1,
+    Cancelled = 2,
+});
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectReview {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub reviewer_id: Uuid,
+    pub invited_by: Option<Uuid>,
+    pub status: ProjectReviewStatus,
+    pub score_objectives: Option<i16>,
+    pub score_methodology: Option<i16>,
+    pub score_budget: Option<i16>,
+    pub score_expertise: Option<i16>,
+    pub score_innovation: Option<i16>,
+    pub recommendation: Option<i16>, // FeedbackStatus => 1: accepted, 2: request revision, 3: rejected
+    pub comments: Option<String>,
+    pub confidential_comments: Option<String>,
+    pub due_at: DateTime<Utc>,
+    pub reminded_at: Option<DateTime<Utc>>,
+    pub overdue_notified_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectReview {
+    pub fn to_info(&self, reviewer: UserInfo) -> ProjectReviewInfo {
+        ProjectReviewInfo {
+            id: self.id,
+            project_id: self.project_id,
+            reviewer,
+            status: self.status,
+            score_objectives: self.score_objectives,
+            score_methodology: self.score_methodology,
+            score_budget: self.score_budget,
+            score_expertise: self.score_expertise,
+            score_innovation: self.score_innovation,
+            recommendation: self.recommendation,
+            comments: self.comments.clone(),
+            confidential_comments: self.confidential_comments.clone(),
+            due_at: self.due_at,
+            is_overdue: self.is_overdue(),
+            submitted_at: self.submitted_at,
+            created_at: self.created_at,
+        }
+    }
+
+    pub fn is_overdue(&self) -> bool {
+        self.status == ProjectReviewStatus::Pending && self.due_at < Utc::now()
+    }
+
+    fn scores(&self) -> [Option<i16>; 5] {
+        [
+            self.score_objectives,
+            self.score_methodology,
+            self.score_budget,
+            self.score_expertise,
+            self.score_innovation,
+        ]
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReviewInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub reviewer: UserInfo,
+    pub status: ProjectReviewStatus,
+    pub score_objectives: Option<i16>,
+    pub score_methodology: Option<i16>,
+    pub score_budget: Option<i16>,
+    pub score_expertise: Option<i16>,
+    pub score_innovation: Option<i16>,
+    pub recommendation: Option<i16>,
+    pub comments: Option<String>,
+    pub confidential_comments: Option<String>,
+    pub due_at: DateTime<Utc>,
+    pub is_overdue: bool,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Aggregate of the submitted reviews the handling editor bases the
+/// consolidated decision on. Averages are `None` until a review is submitted.
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReviewSummary {
+    pub invited: i64,
+    pub submitted: i64,
+    pub pending: i64,
+    pub overdue: i64,
+    pub avg_objectives: Option<f64>,
+    pub avg_methodology: Option<f64>,
+    pub avg_budget: Option<f64>,
+    pub avg_expertise: Option<f64>,
+    pub avg_innovation: Option<f64>,
+    pub avg_overall: Option<f64>,
+    pub accepted: i64,
+    pub revision_required: i64,
+    pub rejected: i64,
+}
+
+impl ProjectReviewSummary {
+    pub fn from_reviews(reviews: &[ProjectReview]) -> Self {
+        let mut summary = ProjectReviewSummary::default();
+        let submitted: Vec<&ProjectReview> = reviews
+            .iter()
+            .filter(|r| r.status == ProjectReviewStatus::Submitted)
+            .collect();
+        for review in reviews {
+            match review.status {
+                ProjectReviewStatus::Cancelled => continue,
+                ProjectReviewStatus::Pending => summary.pending += 1,
+                ProjectReviewStatus::Submitted => summary.submitted += 1,
+            }
+            summary.invited += 1;
+            if review.is_overdue() {
+                summary.overdue += 1;
+            }
+        }
+        for review in &submitted {
+            match review.recommendation.map(FeedbackStatus::from) {
+                Some(FeedbackStatus::Accepted) => summary.accepted += 1,
+                Some(FeedbackStatus::RevisionRequired) => summary.revision_required += 1,
+                Some(FeedbackStatus::Rejected) => summary.rejected += 1,
+                _ => {}
+            }
+        }
+        let average = |index: usize| {
+            let scores: Vec<f64> = submitted
+                .iter()
+                .filter_map(|r| r.scores()[index])
+                .map(f64::from)
+                .collect();
+            (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64)
+        };
+        summary.avg_objectives = average(0);
+        summary.avg_methodology = average(1);
+        summary.avg_budget = average(2);
+        summary.avg_expertise = average(3);
+        summary.avg_innovation = average(4);
+        let averages: Vec<f64> = [
+            summary.avg_objectives,
+            summary.avg_methodology,
+            summary.avg_budget,
+            summary.avg_expertise,
+            summary.avg_innovation,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        summary.avg_overall =
+            (!averages.is_empty()).then(|| averages.iter().sum::<f64>() / averages.len() as f64);
+        summary
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReviewsResponse {
+    pub reviews: Vec<ProjectReviewInfo>,
+    /// Only returned to admins and the handling editor
+    pub summary: Option<ProjectReviewSummary>,
+}
+
+/// A pending review due for a reminder, with what the notification needs
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectReviewReminder {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub reviewer_id: Uuid,
+    pub reviewer_name: Option<String>,
+    pub project_title: Option<String>,
+    pub due_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn review(status: ProjectReviewStatus, due_in: Duration) -> ProjectReview {
+        let now = Utc::now();
+        ProjectReview {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            reviewer_id: Uuid::new_v4(),
+            invited_by: None,
+            status,
+            score_objectives: None,
+            score_methodology: None,
+            score_budget: None,
+            score_expertise: None,
+            score_innovation: None,
+            recommendation: None,
+            comments: None,
+            confidential_comments: None,
+            due_at: now + due_in,
+            reminded_at: None,
+            overdue_notified_at: None,
+            submitted_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn submitted(scores: [i16; 5], recommendation: FeedbackStatus) -> ProjectReview {
+        ProjectReview {
+            score_objectives: Some(scores[0]),
+            score_methodology: Some(scores[1]),
+            score_budget: Some(scores[2]),
+            score_expertise: Some(scores[3]),
+            score_innovation: Some(scores[4]),
+            recommendation: Some(recommendation.to_i16()),
+            submitted_at: Some(Utc::now()),
+            ..review(ProjectReviewStatus::Submitted, Duration::days(7))
+        }
+    }
+
+    #[test]
+    fn summary_of_no_reviews_is_empty() {
+        let summary = ProjectReviewSummary::from_reviews(&[]);
+        assert_eq!(summary.invited, 0);
+        assert_eq!(summary.avg_objectives, None);
+        assert_eq!(summary.avg_overall, None);
+    }
+
+    #[test]
+    fn counts_reviews_by_status_and_skips_cancelled() {
+        let summary = ProjectReviewSummary::from_reviews(&[
+            review(ProjectReviewStatus::Pending, Duration::days(3)),
+            review(ProjectReviewStatus::Pending, -Duration::days(1)),
+            review(ProjectReviewStatus::Cancelled, -Duration::days(1)),
+            submitted([4, 4, 4, 4, 4], FeedbackStatus::Accepted),
+        ]);
+        assert_eq!(summary.invited, 3);
+        assert_eq!(summary.pending, 2);
+        assert_eq!(summary.submitted, 1);
+        assert_eq!(summary.overdue, 1);
+    }
+
+    #[test]
+    fn averages_only_submitted_scores() {
+        let summary = ProjectReviewSummary::from_reviews(&[
+            submitted([5, 4, 3, 2, 1], FeedbackStatus::Accepted),
+            submitted([3, 4, 5, 2, 1], FeedbackStatus::Rejected),
+            submitted([1, 1, 1, 1, 1], FeedbackStatus::RevisionRequired),
+            ProjectReview {
+                score_objectives: Some(1),
+                ..review(ProjectReviewStatus::Pending, Duration::days(3))
+            },
+        ]);
+        assert_eq!(summary.avg_objectives, Some(3.0));
+        assert_eq!(summary.avg_methodology, Some(3.0));
+        assert_eq!(summary.avg_budget, Some(3.0));
+        assert_eq!(summary.avg_expertise, Some(5.0 / 3.0));
+        assert_eq!(summary.avg_innovation, Some(1.0));
+        assert_eq!(summary.avg_overall, Some((9.0 + 5.0 / 3.0 + 1.0) / 5.0));
+        assert_eq!(
+            (
+                summary.accepted,
+                summary.revision_required,
+                summary.rejected
+            ),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn overall_average_ignores_unscored_criteria() {
+        let summary = ProjectReviewSummary::from_reviews(&[ProjectReview {
+            score_objectives: Some(4),
+            score_budget: Some(2),
+            ..review(ProjectReviewStatus::Submitted, Duration::days(3))
+        }]);
+        assert_eq!(summary.avg_methodology, None);
+        assert_eq!(summary.avg_overall, Some(3.0));
+        assert_eq!(
+            summary.accepted + summary.revision_required + summary.rejected,
+            0
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+use super::{Milestone, Project, TeamMember};
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectRevision {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub version: i32,
+    pub from_status: i16,
+    pub submitted_by: Option<Uuid>,
+    pub snapshot: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ProjectRevision {
+    pub fn to_info(&self) -> ProjectRevisionInfo {
+        ProjectRevisionInfo {
+            id: self.id,
+            project_id: self.project_id,
+            version: self.version,
+            from_status: self.from_status,
+            submitted_by: self.submitted_by,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRevisionInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub version: i32,
+    /// Status the project was submitted from (Creating for the first version)
+    pub from_status: i16,
+    pub submitted_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Researcher-editable content of a project, captured at each submission
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSnapshot {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub manuscript: Option<String>,
+    pub upload_files: Vec<String>,
+    pub cover_photo: Option<String>,
+    pub youtube_link: Option<String>,
+    pub category: Vec<Uuid>,
+    pub funding_goal: Option<i32>,
+    pub duration: Option<i32>,
+    pub details: Option<String>,
+    pub personnel_cost: Option<i32>,
+    pub equipment_cost: Option<i32>,
+    pub materials_cost: Option<i32>,
+    pub overhead_cost: Option<i32>,
+    pub other_cost: Option<i32>,
+    pub tags: Vec<String>,
+    pub team_members: Vec<TeamMemberSnapshot>,
+    pub milestones: Vec<MilestoneSnapshot>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMemberSnapshot {
+    pub name: String,
+    pub role: String,
+    pub bio: String,
+    pub linkedin: String,
+    pub twitter: String,
+    pub github: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneSnapshot {
+    pub number: i16,
+    pub title: String,
+    pub description: String,
+    pub deliverables: Option<String>,
+    pub funding_amount: i32,
+    pub days_after_start: i32,
+    pub days_of_prediction: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FieldChangeType {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// e.g. `title`, `teamMembers[0].role`, `milestones[number=2].fundingAmount`
+    pub path: String,
+    pub change_type: FieldChangeType,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRevisionDiff {
+    pub project_id: Uuid,
+    pub from: ProjectRevisionInfo,
+    pub to: ProjectRevisionInfo,
+    pub changes: Vec<FieldChange>,
+}
+
+impl Project {
+    pub fn to_snapshot(
+        &self,
+        team_members: &[TeamMember],
+        milestones: &[Milestone],
+    ) -> ProjectSnapshot {
+        ProjectSnapshot {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            manuscript: self.manuscript.clone(),
+            upload_files: self.upload_files.clone(),
+            cover_photo: self.cover_photo.clone(),
+            youtube_link: self.youtube_link.clone(),
+            category: self.category.clone(),
+            funding_goal: self.funding_goal,
+            duration: self.duration,
+            details: self.details.clone(),
+            personnel_cost: self.personnel_cost,
+            equipment_cost: self.equipment_cost,
+            materials_cost: self.materials_cost,
+            overhead_cost: self.overhead_cost,
+            other_cost: self.other_cost,
+            tags: self.tags.clone(),
+            team_members: team_members
+                .iter()
+                .map(|m| TeamMemberSnapshot {
+                    name: m.name.clone(),
+                    role: m.role.clone(),
+                    bio: m.bio.clone(),
+                    linkedin: m.linkedin.clone(),
+                    twitter: m.twitter.clone(),
+                    github: m.github.clone(),
+                })
+                .collect(),
+            milestones: milestones
+                .iter()
+                .map(|m| MilestoneSnapshot {
+                    number: m.number,
+                    title: m.title.clone(),
+                    description: m.description.clone(),
+                    deliverables: m.deliverables.clone(),
+                    funding_amount: m.funding_amount,
+                    days_after_start: m.days_after_start,
+                    days_of_prediction: m.days_of_prediction,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Field-level diff of two snapshots. Objects and lists of objects (team
+/// members, milestones) are compared field by field, milestones by number and
+/// other objects by position; other lists such as tags are compared as a
+/// whole.
+pub fn diff_snapshots(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_values("", before, after, &mut changes);
+    changes
+}
+
+fn diff_values(path: &str, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(
+                    &path,
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b))
+            if a.len() + b.len() > 0 && a.iter().chain(b).all(|v| v["number"].is_i64()) =>
+        {
+            // Milestones are matched by number so that removing one does not
+            // show every later milestone as changed
+            let numbers: BTreeSet<i64> = a
+                .iter()
+                .chain(b)
+                .filter_map(|v| v["number"].as_i64())
+                .collect();
+            let find = |list: &[Value], number: i64| {
+                list.iter()
+                    .find(|v| v["number"].as_i64() == Some(number))
+                    .unwrap_or(&Value::Null)
+                    .clone()
+            };
+            for number in numbers {
+                diff_values(
+                    &format!("{}[number={}]", path, number),
+                    &find(a, number),
+                    &find(b, number),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.iter().chain(b).any(Value::is_object) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(
+                    &format!("{}[{}]", path, i),
+                    a.get(i).unwrap_or(&Value::Null),
+                    b.get(i).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(FieldChange {
+            path: path.to_string(),
+            change_type: match (before.is_null(), after.is_null()) {
+                (true, _) => FieldChangeType::Added,
+                (_, true) => FieldChangeType::Removed,
+                _ => FieldChangeType::Changed,
+            },
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn milestone(number: i16, title: &str, funding_amount: i32) -> MilestoneSnapshot {
+        MilestoneSnapshot {
+            number,
+            title: title.to_string(),
+            funding_amount,
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(milestones: Vec<MilestoneSnapshot>) -> Value {
+        serde_json::to_value(ProjectSnapshot {
+            title: Some("Protein folding".to_string()),
+            tags: vec!["biology".to_string()],
+            milestones,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn paths(changes: &[FieldChange]) -> Vec<(&str, FieldChangeType)> {
+        changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let a = snapshot(vec![milestone(1, "Setup", 100), milestone(2, "Trial", 200)]);
+        assert!(diff_snapshots(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn reports_changed_scalars_and_whole_lists() {
+        let a = snapshot(vec![]);
+        let mut b = a.clone();
+        b["title"] = json!("Protein design");
+        b["tags"] = json!(["biology", "chemistry"]);
+        b["details"] = json!("More details");
+        let changes = diff_snapshots(&a, &b);
+        assert_eq!(
+            paths(&changes),
+            vec![
+                ("details", FieldChangeType::Added),
+                ("tags", FieldChangeType::Changed),
+                ("title", FieldChangeType::Changed),
+            ]
+        );
+        assert_eq!(changes[2].before, json!("Protein folding"));
+        assert_eq!(changes[2].after, json!("Protein design"));
+    }
+
+    #[test]
+    fn matches_milestones_by_number() {
+        let a = snapshot(vec![
+            milestone(1, "Setup", 100),
+            milestone(2, "Trial", 200),
+            milestone(3, "Report", 300),
+        ]);
+        let b = snapshot(vec![
+            milestone(2, "Trial", 250),
+            milestone(3, "Report", 300),
+        ]);
+        let changes = diff_snapshots(&a, &b);
+        assert_eq!(
+            paths(&changes),
+            vec![
+                ("milestones[number=1]", FieldChangeType::Removed),
+                (
+                    "milestones[number=2].fundingAmount",
+                    FieldChangeType::Changed
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_added_milestones_regardless_of_order() {
+        let a = snapshot(vec![milestone(1, "Setup", 100)]);
+        let b = snapshot(vec![milestone(2, "Trial", 200), milestone(1, "Setup", 100)]);
+        let changes = diff_snapshots(&a, &b);
+        assert_eq!(
+            paths(&changes),
+            vec![("milestones[number=2]", FieldChangeType::Added)]
+        );
+        assert_eq!(changes[0].after["title"], json!("Trial"));
+    }
+
+    #[test]
+    fn compares_milestones_added_to_an_empty_list() {
+        let a = snapshot(vec![]);
+        let b = snapshot(vec![milestone(1, "Setup", 100)]);
+        assert_eq!(
+            paths(&diff_snapshots(&a, &b)),
+            vec![("milestones[number=1]", FieldChangeType::Added)]
+        );
+    }
+}
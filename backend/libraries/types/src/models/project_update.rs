@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserInfo;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ProjectUpdateVisibility {
+    Public,
+    FundersOnly,
+}
+
+define_pg_enum!(ProjectUpdateVisibility {
+    Public = 0,
+    FundersOnly = 1,
+});
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectUpdate {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub attachments: Vec<String>,
+    pub visibility: ProjectUpdateVisibility,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectUpdate {
+    pub fn to_info(&self, user: UserInfo, count_comments: i64) -> ProjectUpdateInfo {
+        ProjectUpdateInfo {
+            id: self.id,
+            project_id: self.project_id,
+            user,
+            title: self.title.clone(),
+            content: self.content.clone(),
+            attachments: self.attachments.clone(),
+            visibility: self.visibility,
+            count_comments,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectUpdateInfo {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user: UserInfo,
+    pub title: String,
+    pub content: String,
+    pub attachments: Vec<String>,
+    pub visibility: ProjectUpdateVisibility,
+    pub count_comments: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ProjectUpdateComment {
+    pub id: Uuid,
+    pub update_id: Uuid,
+    pub user_id: Uuid,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectUpdateComment {
+    pub fn to_info(&self, user: UserInfo) -> ProjectUpdateCommentInfo {
+        ProjectUpdateCommentInfo {
+            id: self.id,
+            update_id: self.update_id,
+            user,
+            comment: self.comment.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectUpdateCommentInfo {
+    pub id: Uuid,
+    pub update_id: Uuid,
+    pub user: UserInfo,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
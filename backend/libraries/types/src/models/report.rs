@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserInfo;
+
+/// Content that can be reported
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ReportTargetType {
+    ProjectComment,
+    BountyComment,
+    BountyChat,
+    Project,
+    Bounty,
+}
+
+define_pg_enum!(ReportTargetType {
+    ProjectComment = 0,
+    BountyComment = 1,
+    BountyChat = 2,
+    Project = 3,
+    Bounty = 4,
+});
+
+impl ReportTargetType {
+    /// How the content is called in notifications
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportTargetType::ProjectComment | ReportTargetType::BountyComment => "comment",
+            ReportTargetType::BountyChat => "message",
+            ReportTargetType::Project => "project",
+            ReportTargetType::Bounty => "bounty",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Misinformation,
+    Plagiarism,
+    Inappropriate,
+    Other,
+}
+
+define_pg_enum!(ReportReason {
+    Spam = 0,
+    Harassment = 1,
+    HateSpeech = 2,
+    Misinformation = 3,
+    Plagiarism = 4,
+    Inappropriate = 5,
+    Other = 6,
+});
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ReportStatus {
+    /// Waiting in the moderation queue
+    Open,
+    /// The content was hidden or removed
+    Resolved,
+    /// The content was restored
+    Dismissed,
+}
+
+define_pg_enum!(ReportStatus {
+    Open = 0,
+    Resolved = 1,
+    Dismissed = 2,
+});
+
+/// Whether reported content is shown. Hidden content can be restored;
+/// removed content can't.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ModerationStatus {
+    Visible,
+    Hidden,
+    Removed,
+}
+
+define_pg_enum!(ModerationStatus {
+    Visible = 0,
+    Hidden = 1,
+    Removed = 2,
+});
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ModerationAction {
+    Hide,
+    Restore,
+    Remove,
+    /// Hidden after reaching the report threshold
+    AutoHide,
+}
+
+define_pg_enum!(ModerationAction {
+    Hide = 0,
+    Restore = 1,
+    Remove = 2,
+    AutoHide = 3,
+});
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct Report {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportInfo {
+    pub id: Uuid,
+    pub reporter: UserInfo,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The author, current state and a short preview of reported content
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct ReportTarget {
+    pub id: Uuid,
+    pub author_id: Uuid,
+    /// The other side of a chat message
+    pub recipient_id: Option<Uuid>,
+    pub preview: Option<String>,
+    pub moderation_status: ModerationStatus,
+}
+
+/// One entry of the moderation queue: a piece of content with the reports
+/// filed against it
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationQueueItem {
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub preview: Option<String>,
+    pub moderation_status: Option<ModerationStatus>,
+    pub count_report: i64,
+    pub first_reported_at: DateTime<Utc>,
+    pub last_reported_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub reports: Vec<ReportInfo>,
+}
+
+impl Report {
+    pub fn to_info(&self, reporter: UserInfo) -> ReportInfo {
+        ReportInfo {
+            id: self.id,
+            reporter,
+            reason: self.reason,
+            details: self.details.clone(),
+            status: self.status,
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchEntityType {
+    Project,
+    Bounty,
+    Dao,
+    Prediction,
+    User,
+    Category,
+}
+
+impl SearchEntityType {
+    pub const ALL: [SearchEntityType; 6] = [
+        SearchEntityType::Project,
+        SearchEntityType::Bounty,
+        SearchEntityType::Dao,
+        SearchEntityType::Prediction,
+        SearchEntityType::User,
+        SearchEntityType::Category,
+    ];
+}
+
+impl FromStr for SearchEntityType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "project" => Ok(SearchEntityType::Project),
+            "bounty" => Ok(SearchEntityType::Bounty),
+            "dao" => Ok(SearchEntityType::Dao),
+            "prediction" => Ok(SearchEntityType::Prediction),
+            "user" => Ok(SearchEntityType::User),
+            "category" => Ok(SearchEntityType::Category),
+            _ => Err(format!("Unknown search type: {}", s)),
+        }
+    }
+}
+
+/// One hit of the global search. `subtitle` is the nerd id for projects,
+/// bounties and DAOs, the project title for predictions and the username
+/// for users.
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub id: Uuid,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub image: Option<String>,
+    pub snippet: Option<String>,
+    pub rank: f32,
+}
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    pub projects: Vec<SearchResult>,
+    pub bounties: Vec<SearchResult>,
+    pub daos: Vec<SearchResult>,
+    pub predictions: Vec<SearchResult>,
+    pub users: Vec<SearchResult>,
+    pub categories: Vec<SearchResult>,
+}
@@ -0,0 +1,221 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Words per shingle
+const SHINGLE_SIZE: usize = 3;
+pub const MINHASH_PERMUTATIONS: usize = 128;
+/// Signature values per LSH band. With 128 permutations this gives 32 bands,
+/// so texts sharing roughly 40% of their shingles are likely to be compared.
+const MINHASH_BAND_ROWS: usize = 4;
+const MINHASH_SEED: u64 = 0x6e65_7264_6e75_6767;
+const MERSENNE_PRIME: u64 = (1 << 61) - 1;
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SimilarityEntityType {
+    Project,
+    Bounty,
+}
+
+define_pg_enum!(SimilarityEntityType {
+    Project = 0,
+    Bounty = 1,
+});
+
+/// FNV-1a, used instead of `DefaultHasher` so stored signatures stay valid
+/// across Rust versions
+fn fnv1a(bytes: &[u8], hash: u64) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(FNV_PRIME))
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Hashes of the lowercased word shingles of `text`
+fn shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    if words.is_empty() {
+        return HashSet::new();
+    }
+    words
+        .windows(SHINGLE_SIZE.min(words.len()))
+        .map(|shingle| fnv1a(shingle.join(" ").as_bytes(), FNV_OFFSET))
+        .collect()
+}
+
+/// MinHash signature of a text. The share of equal values between two
+/// signatures estimates the Jaccard similarity of their shingle sets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinHash {
+    pub signature: Vec<i64>,
+}
+
+impl MinHash {
+    /// `None` when the text has no words
+    pub fn from_text(text: &str) -> Option<Self> {
+        let shingles = shingles(text);
+        if shingles.is_empty() {
+            return None;
+        }
+        let mut state = MINHASH_SEED;
+        let signature = (0..MINHASH_PERMUTATIONS)
+            .map(|_| {
+                let a = (splitmix64(&mut state) % (MERSENNE_PRIME - 1) + 1) as u128;
+                let b = (splitmix64(&mut state) % MERSENNE_PRIME) as u128;
+                shingles
+                    .iter()
+                    .map(|h| {
+                        ((a * (*h % MERSENNE_PRIME) as u128 + b) % MERSENNE_PRIME as u128) as i64
+                    })
+                    .min()
+                    .unwrap_or_default()
+            })
+            .collect();
+        Some(Self { signature })
+    }
+
+    /// Estimated Jaccard similarity between 0 and 1
+    pub fn similarity(&self, other: &[i64]) -> f64 {
+        if self.signature.is_empty() || self.signature.len() != other.len() {
+            return 0.0;
+        }
+        let equal = self
+            .signature
+            .iter()
+            .zip(other)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / self.signature.len() as f64
+    }
+
+    /// One hash per band of the signature. Texts sharing a band hash are
+    /// candidates for a match.
+    pub fn bands(&self) -> Vec<i64> {
+        self.signature
+            .chunks(MINHASH_BAND_ROWS)
+            .enumerate()
+            .map(|(i, rows)| {
+                let hash = fnv1a(&(i as u64).to_le_bytes(), FNV_OFFSET);
+                rows.iter().fold(hash, |h, v| fnv1a(&v.to_le_bytes(), h)) as i64
+            })
+            .collect()
+    }
+}
+
+/// Text of a project or bounty that gets fingerprinted
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct FingerprintSource {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub details: Option<String>,
+}
+
+impl FingerprintSource {
+    pub fn to_minhash(&self) -> Option<MinHash> {
+        let text = [&self.title, &self.description, &self.details]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        MinHash::from_text(&text)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct TextFingerprint {
+    pub entity_type: SimilarityEntityType,
+    pub entity_id: Uuid,
+    pub signature: Vec<i64>,
+}
+
+/// A project or bounty whose text resembles a submitted project
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarityMatchInfo {
+    pub matched_type: SimilarityEntityType,
+    pub matched_id: Uuid,
+    pub nerd_id: String,
+    pub title: Option<String>,
+    pub score: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First signature value and band hash of `ABSTRACT`
+    const SIGNATURE_0: i64 = 14069668587654119;
+    const BAND_0: i64 = 3682110797659797342;
+
+    const ABSTRACT: &str = "We map hydrothermal vent ecosystems along the Mid-Atlantic Ridge \
+        with autonomous underwater vehicles, sampling microbial mats at each site and \
+        sequencing their metagenomes to compare community structure across temperature \
+        gradients. The survey runs over three expeditions and publishes every dataset \
+        openly so that other groups can reuse the samples and the vehicle telemetry.";
+
+    #[test]
+    fn signature_is_stable() {
+        let minhash = MinHash::from_text(ABSTRACT).unwrap();
+        assert_eq!(minhash.signature.len(), MINHASH_PERMUTATIONS);
+        assert_eq!(minhash, MinHash::from_text(ABSTRACT).unwrap());
+        // Case and punctuation don't matter
+        let noisy = ABSTRACT.to_uppercase().replace(' ', " ,  ");
+        assert_eq!(minhash, MinHash::from_text(&noisy).unwrap());
+        // Stored signatures must keep matching new ones across releases
+        assert_eq!(minhash.signature[0], SIGNATURE_0);
+        assert_eq!(minhash.bands()[0], BAND_0);
+    }
+
+    #[test]
+    fn identical_texts_match_fully() {
+        let minhash = MinHash::from_text(ABSTRACT).unwrap();
+        assert_eq!(minhash.similarity(&minhash.signature), 1.0);
+    }
+
+    #[test]
+    fn disjoint_texts_barely_match() {
+        let minhash = MinHash::from_text(ABSTRACT).unwrap();
+        let other = MinHash::from_text(
+            "A community garden teaches children to grow vegetables, keep bees and \
+            cook seasonal meals, funded by a neighbourhood cooperative in Lisbon.",
+        )
+        .unwrap();
+        assert!(minhash.similarity(&other.signature) < 0.05);
+    }
+
+    #[test]
+    fn near_duplicates_share_a_band() {
+        let minhash = MinHash::from_text(ABSTRACT).unwrap();
+        let copy =
+            MinHash::from_text(&ABSTRACT.replace("three expeditions", "four cruises")).unwrap();
+        assert!(minhash.similarity(&copy.signature) > 0.7);
+        let bands: HashSet<i64> = minhash.bands().into_iter().collect();
+        assert_eq!(bands.len(), MINHASH_PERMUTATIONS / MINHASH_BAND_ROWS);
+        assert!(copy.bands().iter().any(|band| bands.contains(band)));
+    }
+
+    #[test]
+    fn needs_words_and_matching_lengths() {
+        assert!(MinHash::from_text(" .,- ").is_none());
+        let minhash = MinHash::from_text(ABSTRACT).unwrap();
+        assert_eq!(minhash.similarity(&minhash.signature[1..]), 0.0);
+    }
+}
@@ -0,0 +1,31 @@
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+
+/// Sort orders shared by the project, DAO, bounty and prediction listings.
+/// Listings fall back to their default order for sorts that don't apply.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ListingSort {
+    Updated,
+    Newest,
+    MostFunded,
+    ClosestToGoal,
+    EndingSoonest,
+    MostViewed,
+    /// Recent views, fundings, votes and comments, refreshed by the trending job
+    Trending,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum ViewEntityType {
+    Project,
+    Bounty,
+}
+
+define_pg_enum!(ViewEntityType {
+    Project = 0,
+    Bounty = 1,
+});
+
+/// Repeated views of the same item by the same viewer within this many
+/// minutes count once
+pub const VIEW_DEDUPE_MINUTES: i64 = 30;
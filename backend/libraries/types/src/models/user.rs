@@ -30,6 +30,7 @@ pub struct User {
     pub funding_updates: bool,
     pub dao_proposals: bool,
     pub prediction_markets: bool,
+    pub archive_notifications: bool,
     // privacy settings
     pub profile_visibility: bool,
     pub show_funding_history: bool,
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use postgres_macro::define_pg_enum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum WebhookEvent {
+    ProjectStatusChanged,
+    DaoFinished,
+    MilestoneFinalized,
+    BountyBidSubmitted,
+}
+
+define_pg_enum!(WebhookEvent {
+    ProjectStatusChanged = 0,
+    DaoFinished = 1,
+    MilestoneFinalized = 2,
+    BountyBidSubmitted = 3,
+});
+
+impl WebhookEvent {
+    /// Event name sent in the payload and the event header
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::ProjectStatusChanged => "project.status_changed",
+            WebhookEvent::DaoFinished => "dao.finished",
+            WebhookEvent::MilestoneFinalized => "milestone.finalized",
+            WebhookEvent::BountyBidSubmitted => "bounty.bid_submitted",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+define_pg_enum!(WebhookDeliveryStatus {
+    Pending = 0,
+    Succeeded = 1,
+    Failed = 2,
+});
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<i16>,
+    pub is_global: bool,
+    pub is_active: bool,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    pub fn to_info(&self) -> WebhookEndpointInfo {
+        WebhookEndpointInfo {
+            id: self.id,
+            user_id: self.user_id,
+            url: self.url.clone(),
+            events: self
+                .events
+                .iter()
+                .filter_map(|e| WebhookEvent::try_from(*e).ok())
+                .collect(),
+            is_global: self.is_global,
+            is_active: self.is_active,
+            description: self.description.clone(),
+            secret: None,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpointInfo {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub is_global: bool,
+    pub is_active: bool,
+    pub description: Option<String>,
+    /// Only returned when the endpoint is created or its secret is rotated
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event: WebhookEvent,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn to_info(&self) -> WebhookDeliveryInfo {
+        WebhookDeliveryInfo {
+            id: self.id,
+            endpoint_id: self.endpoint_id,
+            event: self.event,
+            payload: self.payload.clone(),
+            status: self.status,
+            attempts: self.attempts,
+            next_attempt_at: self.next_attempt_at,
+            last_status_code: self.last_status_code,
+            last_error: self.last_error.clone(),
+            delivered_at: self.delivered_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryInfo {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event: WebhookEvent,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A delivery claimed by the sender together with its endpoint
+#[derive(Clone, Deserialize, Serialize, sqlx::FromRow, Debug)]
+pub struct WebhookOutboxItem {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event: WebhookEvent,
+    pub payload: Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
@@ -15,6 +15,8 @@ pub struct ApiErrorResponse {
     message: Option<String>,
     #[serde(rename = "code")]
     status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 #[allow(dead_code)]
@@ -29,7 +31,26 @@ where
 
 impl ApiErrorResponse {
     pub(crate) fn send(status: u16, message: Option<String>) -> Response {
-        return ApiErrorResponse { message, status }.into_response();
+        ApiErrorResponse {
+            message,
+            status,
+            details: None,
+        }
+        .into_response()
+    }
+
+    /// Like `send`, with machine-readable details the client can act on
+    pub(crate) fn send_with_details(
+        status: u16,
+        message: Option<String>,
+        details: serde_json::Value,
+    ) -> Response {
+        ApiErrorResponse {
+            message,
+            status,
+            details: Some(details),
+        }
+        .into_response()
     }
 }
 
@@ -1,8 +1,9 @@
-use super::constants::FROM_EMAIL_ADDRESS;
+use super::{constants::FROM_EMAIL_ADDRESS, templates::Templates};
 use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message};
 use chrono::{DateTime, Utc};
 use email_address::EmailAddress;
-use rand::{thread_rng, Rng};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde_json::json;
 use std::{
     collections::HashSet,
     hash::{DefaultHasher, Hash, Hasher},
@@ -25,80 +26,27 @@ pub async fn send_auth_email(
     email: String,
     passkey: String,
     message_type: EmailVerifyType,
+    language: &str,
+    templates: &Templates,
     ses_client: &aws_sdk_sesv2::Client,
     reset_url: Option<String>,
 ) -> bool {
-    let (subject, content) = match message_type {
-        EmailVerifyType::VerifyEmail | EmailVerifyType::AddEmail => (
-            format!("Request To Verify Your Email Address(nerdnuggets.org)"),
-            format!(
-                r#"<div style="width: 100%; padding: 10 auto;">
-                    <div style="max-width: 1000px;">
-                        <div style="font-size: 40px; font-weight: bold;display: flex; justify-content: center; max-width: 1600px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;padding-top: 30px;">
-                            <span style="font-size: 40;">Request To Verify Your Email Address</span>
-                        </div>
-                        <div style="width: 100%; margin: 30px;">
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Hi</p>
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">A request to verify your email address has been detected on NOBLEBLOCKS. To proceed, please</p>
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">enter the verification code provided below:</p>
-                            <p style="font-size: 20px;font-weight: bold;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;">Verification Code: {}</p>
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">For your security, this code will expire in 3 minutes and is valid for only one use.</p>
-                            <br />
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Best regards</p>
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">NOBLEBLOCKS Team</p>
-                            <a href="https://www.nerdnuggets.org" style="margin-top: 20px; color: #1155cc">www.nerdnuggets.org</a>
-                            <p style="background: #888888; width: 100%; height: 2px;"></p>
-                            <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;"><i>Please do not reply to this email as it is automatically generated.</i></p>
-                            <p style="background: #888888; width: 100%; height: 2px;"></p>
-                        </div>
-                    </div>
-                </div>"#,
-                passkey
-            ),
-        ),
-        EmailVerifyType::ResetPassword => {
-            let reset_link = if let Some(url) = reset_url {
-                format!(
-                    r#"<p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Click the link below to reset your password:</p>
-                    <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">
-                        <a href="{}" style="color: #1155cc; text-decoration: underline;">Reset Password</a>
-                    </p>"#,
-                    url
-                )
-            } else {
-                format!(
-                    r#"<p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Enter the verification code provided below:</p>
-                    <p style="font-size: 20px;font-weight: bold;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;">Verification Code: {}</p>"#,
-                    passkey
-                )
-            };
-
-            (
-                format!("Request To Reset Your NERDNUGGETS Password(nerdnuggets.org)"),
-                format!(
-                    r#"<div style="width: 100%; padding: 10 auto;">
-                        <div style="max-width: 1000px;">
-                            <div style="font-size: 40px; font-weight: bold;display: flex; justify-content: center; max-width: 1600px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;padding-top: 30px;">
-                                <span style="font-size: 40;">Request To Reset Your NERDNUGGETS Password</span>
-                            </div>
-                            <div style="width: 100%; margin: 30px;">
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Hi</p>
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">A request to reset your password has been detected on NOBLEBLOCKS. To proceed, please</p>
-                                {}
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">For your security, this link will expire in 15 minutes and is valid for only one use.</p>
-                                <br />
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">Best regards</p>
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;">NOBLEBLOCKS Team</p>
-                                <a href="https://nerdnuggets.org" style="margin-top: 20px; color: #1155cc">www.nerdnuggets.org</a>
-                                <p style="background: #888888; width: 100%; height: 2px;"></p>
-                                <p style="font-size: 16px;font-family: Arial,'Helvetica Neue',Helvetica,sans-serif;color: black;"><i>Please do not reply to this email as it is automatically generated.</i></p>
-                                <p style="background: #888888; width: 100%; height: 2px;"></p>
-                            </div>
-                        </div>
-                    </div>"#,
-                    reset_link
-                ),
-            )
+    let template = match message_type {
+        EmailVerifyType::VerifyEmail | EmailVerifyType::AddEmail => "verify_email",
+        EmailVerifyType::ResetPassword => "reset_password",
+    };
+    let rendered = match templates.render_email(
+        template,
+        language,
+        &json!({
+            "passkey": passkey,
+            "reset_url": reset_url,
+        }),
+    ) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("Error rendering email: {:?}", e);
+            return false;
         }
     };
 
@@ -112,7 +60,7 @@ pub async fn send_auth_email(
                     Message::builder()
                         .subject(
                             Content::builder()
-                                .data(subject)
+                                .data(rendered.subject)
                                 .charset("UTF-8")
                                 .build()
                                 .expect("Subject Content"),
@@ -121,7 +69,14 @@ pub async fn send_auth_email(
                             Body::builder()
                                 .html(
                                     Content::builder()
-                                        .data(content)
+                                        .data(rendered.html)
+                                        .charset("UTF-8")
+                                        .build()
+                                        .expect("Message Content"),
+                                )
+                                .text(
+                                    Content::builder()
+                                        .data(rendered.text)
                                         .charset("UTF-8")
                                         .build()
                                         .expect("Message Content"),
@@ -193,6 +148,15 @@ pub fn generate_random_number(from: u32, to: u32) -> u32 {
     random_number
 }
 
+pub fn generate_webhook_secret() -> String {
+    let secret: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    format!("whsec_{}", secret)
+}
+
 pub fn generate_doi_identifier(now: Option<DateTime<Utc>>, article_id: &str) -> String {
     format!(
         "{}.{}",
@@ -310,3 +274,144 @@ pub fn generate_username(
     let timestamp = chrono::Utc::now().timestamp();
     format!("{}{}", base, timestamp)
 }
+
+/// Usernames mentioned in a text as `@username`, each listed once in the
+/// order they first appear. An `@` right after a letter or digit, as in an
+/// email address, is not a mention.
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '@' && !prev.is_some_and(|p| p.is_alphanumeric()) {
+            let start = i + 1;
+            let mut end = start;
+            while let Some(&(j, n)) = chars.peek() {
+                if n.is_alphanumeric() || n == '_' || n == '.' || n == '-' {
+                    end = j + n.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let username = text[start..end].trim_end_matches(['.', '-']);
+            if !username.is_empty() && !mentions.iter().any(|m| m == username) {
+                mentions.push(username.to_string());
+            }
+            prev = text[..end].chars().last();
+            continue;
+        }
+        prev = Some(c);
+    }
+    mentions
+}
+
+/// Whether `text` is made up only of emoji, including skin tones, keycaps,
+/// flags and sequences joined with U+200D, up to 16 code points. Plain text
+/// such as digits or punctuation is rejected.
+pub fn is_emoji(text: &str) -> bool {
+    let is_pictograph = |c: char| {
+        matches!(
+            c as u32,
+            0x1F000..=0x1FAFF
+                | 0x2190..=0x21FF
+                | 0x2300..=0x23FF
+                | 0x25A0..=0x27BF
+                | 0x2934..=0x2935
+                | 0x2B00..=0x2BFF
+                | 0x00A9
+                | 0x00AE
+                | 0x203C
+                | 0x2049
+                | 0x2122
+                | 0x2139
+                | 0x24C2
+                | 0x3030
+                | 0x303D
+                | 0x3297
+                | 0x3299
+        )
+    };
+    // Variation selectors, zero width joiner and the tags of subdivision flags
+    let is_modifier = |c: char| matches!(c as u32, 0xFE0E | 0xFE0F | 0x200D | 0xE0020..=0xE007F);
+    let is_keycap = text.chars().any(|c| c == '\u{20E3}');
+    let chars: Vec<char> = text.chars().collect();
+    !chars.is_empty()
+        && chars.len() <= 16
+        && chars.iter().any(|&c| is_pictograph(c) || is_keycap)
+        && chars.iter().enumerate().all(|(i, &c)| {
+            is_pictograph(c)
+                || is_modifier(c)
+                || (c == '\u{20E3}' && i > 0)
+                // A keycap starts with a digit, `#` or `*`
+                || (is_keycap && i == 0 && (c.is_ascii_digit() || c == '#' || c == '*'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_mentions_once_in_order() {
+        assert_eq!(
+            extract_mentions("@alice and @bob, then @alice again"),
+            vec!["alice", "bob"]
+        );
+        assert!(extract_mentions("no mentions here").is_empty());
+    }
+
+    #[test]
+    fn mentions_stop_at_punctuation() {
+        assert_eq!(
+            extract_mentions("Thanks @alice! (cc @bob.) @carol-, @dan_x:"),
+            vec!["alice", "bob", "carol", "dan_x"]
+        );
+        assert_eq!(extract_mentions("@john.doe-2 said"), vec!["john.doe-2"]);
+        assert!(extract_mentions("a lone @ sign, @@ and @.").is_empty());
+    }
+
+    #[test]
+    fn email_addresses_are_not_mentions() {
+        assert!(extract_mentions("mail me at alice@example.com").is_empty());
+        assert_eq!(extract_mentions("alice@example.com or (@bob)"), vec!["bob"]);
+    }
+
+    #[test]
+    fn mentions_keep_unicode_usernames() {
+        assert_eq!(
+            extract_mentions("merci @josé et @名前, @ßtraße"),
+            vec!["josé", "名前", "ßtraße"]
+        );
+    }
+
+    #[test]
+    fn accepts_emoji() {
+        for emoji in [
+            "👍",
+            "❤️",
+            "🎉",
+            "👍🏽",
+            "\u{1F469}\u{200D}\u{1F52C}",
+            "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+            "🇺🇸",
+            "1️⃣",
+            "#️⃣",
+            "⭐",
+            "✅",
+            "\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}",
+        ] {
+            assert!(is_emoji(emoji), "{}", emoji);
+        }
+    }
+
+    #[test]
+    fn rejects_text() {
+        for text in [
+            "", " ", "123", "<>", "a", "ok", "👍 ok", "👍 ", "1", "#", "\u{20E3}", "\u{200D}",
+        ] {
+            assert!(!is_emoji(text), "{:?}", text);
+        }
+        assert!(!is_emoji(&"👍".repeat(17)));
+    }
+}
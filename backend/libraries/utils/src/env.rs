@@ -18,11 +18,36 @@ pub struct Env {
     pub email_verify_limit: i16,
     pub email_region: String,
     pub frontend_url: String,
+    pub templates_dir: String,
     pub vapid_private_pem: String,
     pub production: bool,
     pub ai_backend_url: String,
     pub google_map_api_key: String,
     pub evm_job_schedule: String,
+    pub announcement_job_schedule: String,
+    pub notification_retention_job_schedule: String,
+    pub notification_read_retention: Duration,
+    pub notification_unread_retention: Duration,
+    pub notification_retention_batch_size: i64,
+    pub webhook_job_schedule: String,
+    pub webhook_max_attempts: i32,
+    pub webhook_retry_base: Duration,
+    pub webhook_batch_size: i64,
+    pub review_reminder_job_schedule: String,
+    pub review_deadline: Duration,
+    pub review_reminder_before: Duration,
+    pub editor_auto_assign: bool,
+    pub trending_job_schedule: String,
+    pub trending_window: Duration,
+    pub trending_half_life: Duration,
+    pub report_auto_hide_threshold: i64,
+    pub ai_review_job_schedule: String,
+    pub ai_review_max_attempts: i32,
+    pub ai_review_retry_base: Duration,
+    pub ai_review_batch_size: i64,
+    pub milestone_reminder_job_schedule: String,
+    /// Days before a milestone is due to remind the researcher
+    pub milestone_reminder_days: Vec<i32>,
     pub dao_contract_address: String,
     pub funding_contract_address: String,
     pub prediction_contract_address: String,
@@ -79,6 +104,8 @@ impl Env {
         let email_region = std::env::var("EMAIL_REGION").expect("EMAIL_REGION must be set");
 
         let frontend_url = std::env::var("FRONTEND_URL").expect("FRONTEND_URL must be set");
+        let templates_dir =
+            std::env::var("TEMPLATES_DIR").unwrap_or_else(|_| "templates".to_string());
 
         let vapid_private_pem =
             std::env::var("VAPID_PRIVATE_PEM").expect("VAPID_PRIVATE_PEM must be set");
@@ -95,6 +122,107 @@ impl Env {
 
         let evm_job_schedule =
             std::env::var("EVM_JOB_SCHEDULE").expect("EVM_JOB_SCHEDULE must be set");
+        let announcement_job_schedule = std::env::var("ANNOUNCEMENT_JOB_SCHEDULE")
+            .unwrap_or_else(|_| "0 * * * * *".to_string());
+        let notification_retention_job_schedule =
+            std::env::var("NOTIFICATION_RETENTION_JOB_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 3 * * *".to_string());
+        let notification_read_retention = Duration::days(
+            std::env::var("NOTIFICATION_READ_RETENTION_DAYS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(30),
+        );
+        let notification_unread_retention = Duration::days(
+            std::env::var("NOTIFICATION_UNREAD_RETENTION_DAYS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(180),
+        );
+        let notification_retention_batch_size = std::env::var("NOTIFICATION_RETENTION_BATCH_SIZE")
+            .ok()
+            .and_then(|p| p.parse::<i64>().ok())
+            .unwrap_or(1000)
+            .max(1);
+        let webhook_job_schedule =
+            std::env::var("WEBHOOK_JOB_SCHEDULE").unwrap_or_else(|_| "*/10 * * * * *".to_string());
+        let webhook_max_attempts = std::env::var("WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(8);
+        let webhook_retry_base = Duration::seconds(
+            std::env::var("WEBHOOK_RETRY_BASE_SECONDS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(30),
+        );
+        let webhook_batch_size = std::env::var("WEBHOOK_BATCH_SIZE")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(100);
+        let review_reminder_job_schedule = std::env::var("REVIEW_REMINDER_JOB_SCHEDULE")
+            .unwrap_or_else(|_| "0 0 * * * *".to_string());
+        let review_deadline = Duration::days(
+            std::env::var("REVIEW_DEADLINE_DAYS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(14),
+        );
+        let review_reminder_before = Duration::hours(
+            std::env::var("REVIEW_REMINDER_BEFORE_HOURS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(48),
+        );
+        let editor_auto_assign = std::env::var("EDITOR_AUTO_ASSIGN")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or_default();
+        let trending_job_schedule = std::env::var("TRENDING_JOB_SCHEDULE")
+            .unwrap_or_else(|_| "0 */15 * * * *".to_string());
+        let trending_window = Duration::days(
+            std::env::var("TRENDING_WINDOW_DAYS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(7),
+        );
+        let trending_half_life = Duration::hours(
+            std::env::var("TRENDING_HALF_LIFE_HOURS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(48),
+        );
+        let report_auto_hide_threshold = std::env::var("REPORT_AUTO_HIDE_THRESHOLD")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(5);
+        let ai_review_job_schedule = std::env::var("AI_REVIEW_JOB_SCHEDULE")
+            .unwrap_or_else(|_| "0 * * * * *".to_string());
+        let ai_review_max_attempts = std::env::var("AI_REVIEW_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(5);
+        let ai_review_retry_base = Duration::seconds(
+            std::env::var("AI_REVIEW_RETRY_BASE_SECONDS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(60),
+        );
+        let ai_review_batch_size = std::env::var("AI_REVIEW_BATCH_SIZE")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(10);
+        let milestone_reminder_job_schedule = std::env::var("MILESTONE_REMINDER_JOB_SCHEDULE")
+            .unwrap_or_else(|_| "0 0 * * * *".to_string());
+        let mut milestone_reminder_days: Vec<i32> = std::env::var("MILESTONE_REMINDER_DAYS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|d| d.trim().parse().ok())
+            .filter(|d| *d > 0)
+            .collect();
+        if milestone_reminder_days.is_empty() {
+            milestone_reminder_days = vec![7, 1];
+        }
         let dao_contract_address =
             std::env::var("DAO_CONTRACT_ADDRESS").expect("DAO_CONTRACT_ADDRESS must be set");
         let funding_contract_address = std::env::var("FUNDING_CONTRACT_ADDRESS")
@@ -136,11 +264,35 @@ impl Env {
             email_verify_limit,
             email_region,
             frontend_url,
+            templates_dir,
             vapid_private_pem,
             production,
             ai_backend_url,
             google_map_api_key,
             evm_job_schedule,
+            announcement_job_schedule,
+            notification_retention_job_schedule,
+            notification_read_retention,
+            notification_unread_retention,
+            notification_retention_batch_size,
+            webhook_job_schedule,
+            webhook_max_attempts,
+            webhook_retry_base,
+            webhook_batch_size,
+            review_reminder_job_schedule,
+            review_deadline,
+            review_reminder_before,
+            editor_auto_assign,
+            trending_job_schedule,
+            trending_window,
+            trending_half_life,
+            report_auto_hide_threshold,
+            ai_review_job_schedule,
+            ai_review_max_attempts,
+            ai_review_retry_base,
+            ai_review_batch_size,
+            milestone_reminder_job_schedule,
+            milestone_reminder_days,
             dao_contract_address,
             funding_contract_address,
             prediction_contract_address,
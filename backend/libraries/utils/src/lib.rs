@@ -1,3 +1,4 @@
 pub mod commons;
 pub mod constants;
 pub mod env;
+pub mod templates;
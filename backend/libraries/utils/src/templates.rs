@@ -0,0 +1,232 @@
+use serde::Serialize;
+use std::{path::Path, sync::Arc};
+use tera::{Context, Tera};
+use types::models::NotificationType;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+#[derive(Clone, Debug)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderedNotification {
+    pub title: String,
+    pub message: String,
+}
+
+/// Notification and email templates compiled once at startup.
+///
+/// Templates live under `<dir>/<locale>/notification/<type>.{title,message}.txt`
+/// and `<dir>/<locale>/email/<name>.{subject.txt,html,txt}`, with the shared
+/// email layouts under `<dir>/layouts`.
+#[derive(Clone)]
+pub struct Templates {
+    tera: Arc<Tera>,
+}
+
+impl Templates {
+    pub fn init(dir: &str) -> Self {
+        Self::load(dir).unwrap_or_else(|e| panic!("Template error: {e}"))
+    }
+
+    pub fn load(dir: &str) -> Result<Self, tera::Error> {
+        let glob = Path::new(dir).join("**").join("*");
+        let mut tera = Tera::new(&glob.to_string_lossy())?;
+        tera.autoescape_on(vec![".html"]);
+        Ok(Self {
+            tera: Arc::new(tera),
+        })
+    }
+
+    /// Maps a `User.language` value ("English", "Spanish", "es", ...) to the
+    /// locale directory holding its templates, falling back to the default.
+    pub fn locale(&self, language: &str) -> &'static str {
+        match language.trim().to_lowercase().as_str() {
+            "es" | "spanish" | "español" | "espanol" => "es",
+            _ => DEFAULT_LOCALE,
+        }
+    }
+
+    pub fn render_notification<T: Serialize>(
+        &self,
+        notification_type: &NotificationType,
+        language: &str,
+        data: &T,
+    ) -> Result<RenderedNotification, tera::Error> {
+        let context = Context::from_serialize(data)?;
+        let name = notification_type.template_name();
+        Ok(RenderedNotification {
+            title: self.render(
+                language,
+                &format!("notification/{name}.title.txt"),
+                &context,
+            )?,
+            message: self.render(
+                language,
+                &format!("notification/{name}.message.txt"),
+                &context,
+            )?,
+        })
+    }
+
+    pub fn render_email<T: Serialize>(
+        &self,
+        name: &str,
+        language: &str,
+        data: &T,
+    ) -> Result<RenderedEmail, tera::Error> {
+        let context = Context::from_serialize(data)?;
+        Ok(RenderedEmail {
+            subject: self.render(language, &format!("email/{name}.subject.txt"), &context)?,
+            html: self.render(language, &format!("email/{name}.html"), &context)?,
+            text: self.render(language, &format!("email/{name}.txt"), &context)?,
+        })
+    }
+
+    fn render(&self, language: &str, name: &str, context: &Context) -> Result<String, tera::Error> {
+        let localized = format!("{}/{}", self.locale(language), name);
+        let template = if self.tera.get_template_names().any(|n| n == localized) {
+            localized
+        } else {
+            format!("{}/{}", DEFAULT_LOCALE, name)
+        };
+        Ok(self.tera.render(&template, context)?.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const LOCALES: [&str; 2] = ["English", "Spanish"];
+
+    fn templates() -> Templates {
+        Templates::init(concat!(env!("CARGO_MANIFEST_DIR"), "/../../../templates"))
+    }
+
+    fn notification_data() -> serde_json::Value {
+        json!({
+            "project_name": "Deep Sea Survey",
+            "admin_name": "Alice",
+            "user_name": "Bob",
+            "creator_name": "Carol",
+            "bounty_title": "Protein Folding",
+            "dao_name": "Ocean DAO",
+            "prediction_title": "Reef Recovery",
+            "milestone_name": "Sampling",
+            "commenter_name": "Dave",
+            "sender_name": "Erin",
+            "editor_name": "Frank",
+            "reviewer_name": "Grace",
+            "due_date": "2025-10-01",
+            "role": "co-PI",
+            "update_title": "First samples",
+            "proposal_title": "Extend Survey",
+            "funding_amount": "250 NERD",
+            "result": "Yes",
+            "title": "Maintenance",
+            "message": "Scheduled downtime tonight",
+            "success": true,
+            "passed": true,
+            "status": "InProgress",
+            "entity_type": "bounty",
+            "entity_title": "Protein Folding",
+            "content_type": "comment",
+            "content_title": "Great work!",
+            "note": "Off-topic",
+        })
+    }
+
+    #[test]
+    fn renders_every_notification_type() {
+        let templates = templates();
+        for language in LOCALES {
+            for notification_type in (0..).map_while(|v| NotificationType::try_from(v).ok()) {
+                let rendered = templates
+                    .render_notification(&notification_type, language, &notification_data())
+                    .unwrap_or_else(|e| panic!("{:?} ({language}): {e:?}", notification_type));
+                assert!(!rendered.title.is_empty());
+                assert!(!rendered.message.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn renders_verify_email() {
+        let templates = templates();
+        for language in LOCALES {
+            let email = templates
+                .render_email("verify_email", language, &json!({ "passkey": "123456" }))
+                .unwrap();
+            assert!(!email.subject.is_empty());
+            assert!(email.html.contains("123456"));
+            assert!(email.text.contains("123456"));
+            assert!(!email.html.contains("NOBLEBLOCKS"));
+        }
+    }
+
+    #[test]
+    fn renders_reset_password_email() {
+        let templates = templates();
+        for language in LOCALES {
+            let email = templates
+                .render_email(
+                    "reset_password",
+                    language,
+                    &json!({ "passkey": "654321", "reset_url": "https://nerdnuggets.org/reset?t=a&b" }),
+                )
+                .unwrap();
+            assert!(email.html.contains("reset?t=a&amp;b"));
+            assert!(email.text.contains("https://nerdnuggets.org/reset?t=a&b"));
+
+            let email = templates
+                .render_email("reset_password", language, &json!({ "passkey": "654321" }))
+                .unwrap();
+            assert!(email.html.contains("654321"));
+            assert!(email.text.contains("654321"));
+        }
+    }
+
+    #[test]
+    fn falls_back_to_default_locale() {
+        let templates = templates();
+        assert_eq!(templates.locale("English"), "en");
+        assert_eq!(templates.locale("Español"), "es");
+        assert_eq!(templates.locale("Klingon"), DEFAULT_LOCALE);
+        let rendered = templates
+            .render_notification(
+                &NotificationType::NewMessage,
+                "Klingon",
+                &notification_data(),
+            )
+            .unwrap();
+        assert!(rendered.message.contains("Erin"));
+    }
+
+    #[test]
+    fn renders_grouped_comment_notifications() {
+        let templates = templates();
+        let mut data = notification_data();
+        data["count"] = json!(4);
+        let rendered = templates
+            .render_notification(&NotificationType::ProjectComment, "English", &data)
+            .unwrap();
+        assert_eq!(
+            rendered.message,
+            "Dave and 3 others commented on project 'Deep Sea Survey'"
+        );
+        data["count"] = json!(2);
+        let rendered = templates
+            .render_notification(&NotificationType::BountyComment, "English", &data)
+            .unwrap();
+        assert_eq!(
+            rendered.message,
+            "Dave and 1 other person commented on bounty 'Protein Folding'"
+        );
+    }
+}
@@ -19,7 +19,7 @@ use std::{
 use tokio::sync::mpsc;
 use tracing::info;
 use types::error::Error;
-use utils::env::Env;
+use utils::{env::Env, templates::Templates};
 use uuid::Uuid;
 
 #[tokio::main]
@@ -37,7 +37,8 @@ async fn main() -> Result<(), Error> {
         .await
         .unwrap_or_else(|e| panic!("Database error: {e}"));
     let db = Arc::new(connection);
-    let service = AppService::init(&db, &env);
+    let templates = Templates::init(&env.templates_dir);
+    let service = AppService::init(&db, &env, &templates);
 
     let user_sockets = Arc::new(Mutex::new(HashMap::new()));
 
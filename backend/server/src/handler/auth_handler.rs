@@ -17,7 +17,7 @@ use types::{
 };
 use utils::{
     commons::{is_valid_email, send_auth_email},
-    constants::EMAIL_SEND_AGAIN_IN_SECONDS,
+    constants::{DEFAULT_LANGUAGE, EMAIL_SEND_AGAIN_IN_SECONDS},
 };
 
 pub async fn login_with_email(
@@ -461,6 +461,8 @@ pub async fn register_with_email(
         payload.email,
         passkey,
         EmailVerifyType::VerifyEmail,
+        DEFAULT_LANGUAGE,
+        &state.templates,
         &state.ses_client,
         None,
     )
@@ -501,6 +503,8 @@ pub async fn resend_verification_email(
             payload.email.to_owned(),
             passkey.to_owned(),
             EmailVerifyType::VerifyEmail,
+            DEFAULT_LANGUAGE,
+            &state.templates,
             &state.ses_client,
             None,
         )
@@ -632,6 +636,8 @@ pub async fn forgot_password(
         payload.email,
         reset_token,
         EmailVerifyType::ResetPassword,
+        &user.language,
+        &state.templates,
         &state.ses_client,
         Some(reset_url),
     )
@@ -1,30 +1,61 @@
+use crate::handler::{comment_handler::notify_comment_audience, viewer_key};
 use crate::state::AppState;
 use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
 use axum::{Extension, Json};
 
 use third_party_api::arweave::upload_bounty_creation;
 use types::dto::{
     BountyAction, BountyActionRequest, BountyCreateRequest, BountyUpdateRequest,
     CancelBountyRequest, GetBidsOption, GetBountyChatNumbersResponse, GetBountyChatsOption,
-    GetBountysOption, GetMyBidsOption, GetMyBountyStatsResponse, GetSimilarBountiesOption,
-    OffsetAndLimitOption, RejectBidMilestoneRequest, ReviewBidMilestoneSubmissionRequest,
-    ReviewBountyRequest, ReviewBountyWorkSubmissionRequest, SendBountyChatRequest,
-    SubmitBidMilestoneWorkRequest, SubmitBidRequest, SubmitBountyCommentRequest,
-    SubmitBountyWorkRequest,
+    GetBountyCommentsOption, GetBountysOption, GetMyBidsOption, GetMyBountyStatsResponse,
+    GetSimilarBountiesOption, Paginated, RejectBidMilestoneRequest,
+    ReviewBidMilestoneSubmissionRequest, ReviewBountyRequest, ReviewBountyWorkSubmissionRequest,
+    SendBountyChatRequest, SubmitBidMilestoneWorkRequest, SubmitBidRequest,
+    SubmitBountyCommentRequest, SubmitBountyWorkRequest,
 };
 use types::error::{ApiError, DbError, ValidatedRequest};
 use types::models::{
-    BidInfo, BidMilestoneSubmission, BidMilestoneSubmissionStatus, BountyChatInfo,
-    BountyCommentInfo, BountyInfo, BountyWorkSubmissionInfo, User,
+    BidInfo, BidMilestoneSubmission, BidMilestoneSubmissionStatus, Bounty, BountyChatInfo,
+    BountyCommentInfo, BountyInfo, BountyWorkSubmissionInfo, CommentEntityType, CommentInfo,
+    FollowEntityType, PageRequest, ReportTargetType, User,
 };
 use types::UserRoleType;
 use utils::commons::uuid_from_str;
+use uuid::Uuid;
 
 pub async fn get_bounty_by_id(
+    Extension(user): Extension<Option<User>>,
+    Extension(role): Extension<Option<String>>,
+    headers: HeaderMap,
     Path(id): Path<String>,
     State(state): State<AppState>,
 ) -> Result<Json<BountyInfo>, ApiError> {
-    let bounty = state.service.bounty.get_bounty_info_by_id(&id).await?;
+    let mut bounty = state
+        .service
+        .bounty
+        .get_bounty_info_by_id_without_increment(&id)
+        .await?;
+    let is_admin = role == Some(UserRoleType::Admin.to_string());
+    state
+        .service
+        .report
+        .ensure_visible(
+            ReportTargetType::Bounty,
+            bounty.id,
+            user.as_ref().map(|u| u.id),
+            is_admin,
+        )
+        .await?;
+    let viewer = viewer_key(user.as_ref(), &headers);
+    state
+        .service
+        .bounty
+        .record_view(bounty.id, viewer.as_deref())
+        .await;
+    if let Some(user) = user {
+        set_saved_flags(&state, user.id, std::slice::from_mut(&mut bounty)).await;
+    }
     Ok(Json(bounty))
 }
 
@@ -78,6 +109,7 @@ pub async fn create_bounty(
             // println!("Failed to upload bounty to Arweave: {:?}", e);
         }
     }
+    let _ = state.service.similarity.index_bounty(bounty.id).await;
 
     Ok(Json(bounty))
 }
@@ -92,6 +124,13 @@ pub async fn update_bounty(
         return Err(DbError::Str("You are not an admin.".to_string()).into());
     }
     let res = state.service.bounty.update_bounty(&id, payload).await?;
+    if res {
+        let _ = state
+            .service
+            .similarity
+            .index_bounty(uuid_from_str(&id)?)
+            .await;
+    }
     Ok(Json(res))
 }
 
@@ -109,8 +148,9 @@ pub async fn get_bounties(
     Extension(role): Extension<Option<String>>,
     Query(opts): Query<GetBountysOption>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<BountyInfo>>, ApiError> {
-    let res = state
+) -> Result<Json<Paginated<BountyInfo>>, ApiError> {
+    let user_id = user.map(|u| u.id);
+    let mut res = state
         .service
         .bounty
         .get_bounties(
@@ -119,15 +159,33 @@ pub async fn get_bounties(
             opts.category_id,
             opts.difficulty,
             role,
-            user.map(|u| u.id),
+            user_id,
             opts.is_mine,
-            opts.offset,
+            opts.sort,
+            PageRequest::new(opts.offset, opts.cursor.as_deref())?,
             opts.limit,
         )
         .await?;
+    if let Some(user_id) = user_id {
+        set_saved_flags(&state, user_id, res.items_mut()).await;
+    }
     Ok(Json(res))
 }
 
+/// Marks the bounties the user follows or bookmarked
+async fn set_saved_flags(state: &AppState, user_id: Uuid, bounties: &mut [BountyInfo]) {
+    let ids: Vec<Uuid> = bounties.iter().map(|b| b.id).collect();
+    let (followed, bookmarked) = state
+        .service
+        .follow
+        .get_saved_ids(user_id, FollowEntityType::Bounty, &ids)
+        .await;
+    for bounty in bounties {
+        bounty.is_following = followed.contains(&bounty.id);
+        bounty.is_bookmarked = bookmarked.contains(&bounty.id);
+    }
+}
+
 pub async fn get_bids(
     Path(id): Path<String>,
     State(state): State<AppState>,
@@ -193,16 +251,49 @@ pub async fn reject_bid(
     Ok(Json(res))
 }
 
+/// Flat listing kept for existing clients; see `get_bounty_comment_threads`
 pub async fn get_bounty_comments(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Query(opts): Query<GetBountyCommentsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Paginated<BountyCommentInfo>>, ApiError> {
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
+    let comments = state
+        .service
+        .comment
+        .get_comments(
+            CommentEntityType::Bounty,
+            &id,
+            Some(user.id),
+            page,
+            opts.limit,
+        )
+        .await?;
+    Ok(Json(comments.flat_map(|c| {
+        c.flatten().into_iter().map(BountyCommentInfo::from)
+    })))
+}
+
+/// Comments with their replies, reactions and pins
+pub async fn get_bounty_comment_threads(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
-    Query(opts): Query<OffsetAndLimitOption>,
+    Query(opts): Query<GetBountyCommentsOption>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<BountyCommentInfo>>, ApiError> {
+) -> Result<Json<Paginated<CommentInfo>>, ApiError> {
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
     Ok(Json(
         state
             .service
-            .bounty
-            .get_bounty_comments(&id, opts.offset, opts.limit)
+            .comment
+            .get_comments(
+                CommentEntityType::Bounty,
+                &id,
+                Some(user.id),
+                page,
+                opts.limit,
+            )
             .await?,
     ))
 }
@@ -213,13 +304,32 @@ pub async fn submit_bounty_comment(
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<SubmitBountyCommentRequest>,
 ) -> Result<Json<bool>, ApiError> {
-    Ok(Json(
-        state
+    let posted = state
+        .service
+        .comment
+        .create_comment(
+            CommentEntityType::Bounty,
+            &id,
+            user.id,
+            &payload.comment,
+            payload.parent_id,
+        )
+        .await?;
+    let notified = notify_comment_audience(&state, CommentEntityType::Bounty, &posted, &user).await;
+    let owner_id = posted.target.user_id;
+    if owner_id != user.id && !notified.contains(&owner_id) {
+        let _ = state
             .service
-            .bounty
-            .submit_bounty_comment(&id, user.id, &payload.comment)
-            .await?,
-    ))
+            .notification
+            .notify_bounty_comment(
+                owner_id,
+                posted.target.id,
+                &posted.target.title.clone().unwrap_or_default(),
+                &user.name.clone().unwrap_or_default(),
+            )
+            .await;
+    }
+    Ok(Json(true))
 }
 
 pub async fn review_bounty(
@@ -231,11 +341,17 @@ pub async fn review_bounty(
     if role != UserRoleType::Admin.to_string() {
         return Err(DbError::Str("You are not an admin.".to_string()).into());
     }
+    let before = state
+        .service
+        .bounty
+        .get_bounty_by_id_or_nerd_id(&id)
+        .await?;
     let res = state
         .service
         .bounty
         .review_bounty(&id, payload.status, payload.admin_notes)
         .await?;
+    notify_status_change(&state, before, None).await;
     Ok(Json(res))
 }
 
@@ -254,11 +370,12 @@ pub async fn get_my_bounty_stats(
 pub async fn get_bounty_chats(
     Query(opts): Query<GetBountyChatsOption>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<BountyChatInfo>>, ApiError> {
+) -> Result<Json<Paginated<BountyChatInfo>>, ApiError> {
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
     let chats = state
         .service
         .bounty
-        .get_bounty_chats(&opts.chat_number, opts.offset, opts.limit)
+        .get_bounty_chats(&opts.chat_number, page, opts.limit)
         .await?;
     Ok(Json(chats))
 }
@@ -336,7 +453,11 @@ pub async fn create_bidder_chat(
     State(state): State<AppState>,
 ) -> Result<Json<String>, ApiError> {
     // Verify that the current user is the bounty creator (funder)
-    let bounty = state.service.bounty.get_bounty_info_by_id(&id).await?;
+    let bounty = state
+        .service
+        .bounty
+        .get_bounty_info_by_id(&id, Some(&user.id.to_string()))
+        .await?;
     if bounty.user.id != user.id {
         return Err(DbError::Str(
             "Only the bounty creator can initiate chats with bidders".to_string(),
@@ -515,11 +636,17 @@ pub async fn handle_bounty_action(
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<BountyActionRequest>,
 ) -> Result<Json<bool>, ApiError> {
+    let before = state
+        .service
+        .bounty
+        .get_bounty_by_id_or_nerd_id(&bounty_id)
+        .await?;
     let success = state
         .service
         .bounty
         .handle_bounty_action(&bounty_id, user.id, payload.action, payload.admin_notes)
         .await?;
+    notify_status_change(&state, before, Some(user.id)).await;
     Ok(Json(success))
 }
 
@@ -529,10 +656,46 @@ pub async fn cancel_bounty(
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<CancelBountyRequest>,
 ) -> Result<Json<bool>, ApiError> {
+    let before = state
+        .service
+        .bounty
+        .get_bounty_by_id_or_nerd_id(&bounty_id)
+        .await?;
     let success = state
         .service
         .bounty
         .handle_bounty_action(&bounty_id, user.id, BountyAction::Cancel, payload.reason)
         .await?;
+    notify_status_change(&state, before, Some(user.id)).await;
     Ok(Json(success))
 }
+
+/// Lets the bounty's followers know when its status moved away from
+/// `before.status`. The user who made the change isn't notified.
+async fn notify_status_change(state: &AppState, before: Bounty, actor_id: Option<Uuid>) {
+    let Ok(bounty) = state
+        .service
+        .bounty
+        .get_bounty_by_id_or_nerd_id(&before.id.to_string())
+        .await
+    else {
+        return;
+    };
+    if bounty.status == before.status {
+        return;
+    }
+    let Ok(mut followers) = state
+        .service
+        .follow
+        .get_follower_ids(&[(FollowEntityType::Bounty, bounty.id)])
+        .await
+    else {
+        return;
+    };
+    followers.retain(|id| Some(*id) != actor_id);
+    let _ = state
+        .service
+        .notification
+        .notify_bounty_status_change(followers, bounty.id, &bounty.title, bounty.status)
+        .await;
+}
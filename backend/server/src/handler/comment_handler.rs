@@ -0,0 +1,143 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use types::{
+    dto::{EditCommentRequest, PinCommentRequest, ReactCommentRequest},
+    error::{ApiError, ValidatedRequest},
+    models::{CommentEdit, CommentEntityType, PostedComment, User},
+    UserRoleType,
+};
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Notifies the users mentioned in a comment and the author of the comment
+/// it replies to. Returns who was notified.
+pub(crate) async fn notify_comment_audience(
+    state: &AppState,
+    entity_type: CommentEntityType,
+    posted: &PostedComment,
+    user: &User,
+) -> Vec<Uuid> {
+    let commenter_name = user.name.clone().unwrap_or_default();
+    let title = posted.target.title.clone().unwrap_or_default();
+    let mut notified = posted.mentioned_user_ids.clone();
+    if !notified.is_empty() {
+        let _ = state
+            .service
+            .notification
+            .notify_comment_mention(
+                notified.clone(),
+                entity_type,
+                posted.target.id,
+                &title,
+                posted.comment.id,
+                &commenter_name,
+            )
+            .await;
+    }
+    if let (Some(parent_user_id), Some(parent_id)) =
+        (posted.parent_user_id, posted.comment.parent_id)
+    {
+        if !notified.contains(&parent_user_id) {
+            let _ = state
+                .service
+                .notification
+                .notify_comment_reply(
+                    parent_user_id,
+                    entity_type,
+                    posted.target.id,
+                    &title,
+                    parent_id,
+                    &commenter_name,
+                )
+                .await;
+            notified.push(parent_user_id);
+        }
+    }
+    notified
+}
+
+pub async fn edit_comment(
+    Extension(user): Extension<User>,
+    Path((entity_type, comment_id)): Path<(CommentEntityType, String)>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<EditCommentRequest>,
+) -> Result<Json<bool>, ApiError> {
+    let posted = state
+        .service
+        .comment
+        .edit_comment(entity_type, &comment_id, user.id, &payload.comment)
+        .await?;
+    notify_comment_audience(&state, entity_type, &posted, &user).await;
+    Ok(Json(true))
+}
+
+pub async fn delete_comment(
+    Extension(user): Extension<User>,
+    Path((entity_type, comment_id)): Path<(CommentEntityType, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .comment
+            .delete_comment(entity_type, &comment_id, user.id)
+            .await?,
+    ))
+}
+
+pub async fn get_comment_history(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path((entity_type, comment_id)): Path<(CommentEntityType, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CommentEdit>>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .comment
+            .get_comment_history(entity_type, &comment_id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn react_comment(
+    Extension(user): Extension<User>,
+    Path((entity_type, comment_id)): Path<(CommentEntityType, String)>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<ReactCommentRequest>,
+) -> Result<Json<bool>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .comment
+            .toggle_reaction(entity_type, &comment_id, user.id, &payload.emoji)
+            .await?,
+    ))
+}
+
+pub async fn pin_comment(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path((entity_type, comment_id)): Path<(CommentEntityType, String)>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<PinCommentRequest>,
+) -> Result<Json<bool>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .comment
+            .pin_comment(
+                entity_type,
+                &comment_id,
+                user.id,
+                is_admin,
+                payload.is_pinned,
+            )
+            .await?,
+    ))
+}
@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use types::{
+    dto::GetSavedItemsOption,
+    error::ApiError,
+    models::{FollowEntityType, SavedItem, User},
+};
+use utils::commons::uuid_from_str;
+
+use crate::state::AppState;
+
+pub async fn toggle_follow(
+    Extension(user): Extension<User>,
+    Path((entity_type, id)): Path<(FollowEntityType, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    let entity_id = uuid_from_str(&id)?;
+    Ok(Json(
+        state
+            .service
+            .follow
+            .toggle_follow(user.id, entity_type, entity_id)
+            .await?,
+    ))
+}
+
+pub async fn toggle_bookmark(
+    Extension(user): Extension<User>,
+    Path((entity_type, id)): Path<(FollowEntityType, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    let entity_id = uuid_from_str(&id)?;
+    Ok(Json(
+        state
+            .service
+            .follow
+            .toggle_bookmark(user.id, entity_type, entity_id)
+            .await?,
+    ))
+}
+
+pub async fn get_follows(
+    Extension(user): Extension<User>,
+    Query(opts): Query<GetSavedItemsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SavedItem>>, ApiError> {
+    Ok(Json(state.service.follow.get_follows(user.id, opts).await?))
+}
+
+pub async fn get_bookmarks(
+    Extension(user): Extension<User>,
+    Query(opts): Query<GetSavedItemsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SavedItem>>, ApiError> {
+    Ok(Json(
+        state.service.follow.get_bookmarks(user.id, opts).await?,
+    ))
+}
@@ -1,7 +1,31 @@
+use axum::http::HeaderMap;
+use types::models::User;
+
 pub mod auth_handler;
 pub mod bounty_handler;
+pub mod comment_handler;
+pub mod follow_handler;
 pub mod notification_handler;
 pub mod prediction_handler;
 pub mod project_handler;
+pub mod report_handler;
+pub mod search_handler;
 pub mod user_handler;
 pub mod util_handler;
+pub mod webhook_handler;
+
+/// Identifies who viewed an item so repeated views count once: the user id,
+/// or the client address forwarded by the proxy for anonymous requests
+pub(crate) fn viewer_key(user: Option<&User>, headers: &HeaderMap) -> Option<String> {
+    if let Some(user) = user {
+        return Some(user.id.to_string());
+    }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .or_else(|| headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| format!("ip:{}", v))
+}
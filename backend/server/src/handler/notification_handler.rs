@@ -2,11 +2,16 @@ use axum::{
     extract::{Path, Query, State},
     Extension, Json,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use types::{
+    dto::Paginated,
     error::{ApiError, UserError},
-    models::{CreateNotification, NotificationResponse, NotificationTab, NotificationType, User},
+    models::{
+        AnnouncementResponse, AnnouncementSegment, CreateNotification, NotificationResponse,
+        NotificationTab, NotificationType, PageRequest, User,
+    },
     UserRoleType,
 };
 use uuid::Uuid;
@@ -17,6 +22,7 @@ use crate::state::AppState;
 pub struct NotificationQuery {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    pub cursor: Option<String>,
     pub tab: Option<NotificationTab>,
 }
 
@@ -35,6 +41,23 @@ pub async fn get_notifications(
     Extension(user): Extension<User>,
     Query(query): Query<NotificationQuery>,
     State(state): State<AppState>,
+) -> Result<Json<Paginated<NotificationResponse>>, ApiError> {
+    let limit = query.limit.unwrap_or(20).min(100);
+    let page = PageRequest::new(query.offset, query.cursor.as_deref())?;
+
+    Ok(Json(
+        state
+            .service
+            .notification
+            .get_user_notifications_by_tab(user.id, limit, page, query.tab)
+            .await?,
+    ))
+}
+
+pub async fn get_archived_notifications(
+    Extension(user): Extension<User>,
+    Query(query): Query<NotificationQuery>,
+    State(state): State<AppState>,
 ) -> Result<Json<Vec<NotificationResponse>>, ApiError> {
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
@@ -43,7 +66,7 @@ pub async fn get_notifications(
         state
             .service
             .notification
-            .get_user_notifications_by_tab(user.id, limit, offset, query.tab)
+            .get_archived_notifications(user.id, limit, offset)
             .await?,
     ))
 }
@@ -131,6 +154,7 @@ pub struct CreateNotificationRequest {
     pub title: String,
     pub message: String,
     pub data: Option<serde_json::Value>,
+    pub collapse_key: Option<String>,
 }
 
 pub async fn create_notification(
@@ -151,6 +175,8 @@ pub async fn create_notification(
         title: request.title,
         message: request.message,
         data: request.data,
+        collapse_key: request.collapse_key,
+        actor: None,
     };
 
     Ok(Json(NotificationResponse::from(
@@ -161,3 +187,87 @@ pub async fn create_notification(
             .await?,
     )))
 }
+
+// Admin endpoints for announcements
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAnnouncementRequest {
+    pub title: String,
+    pub message: String,
+    #[serde(default)]
+    pub segment: AnnouncementSegment,
+    pub segment_value: Option<String>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnnouncementQuery {
+    pub offset: Option<i32>,
+    pub limit: Option<i32>,
+}
+
+pub async fn create_announcement(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    State(state): State<AppState>,
+    Json(request): Json<CreateAnnouncementRequest>,
+) -> Result<Json<AnnouncementResponse>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(ApiError::UserError(UserError::Str(
+            "You are not authorized to send announcements".to_string(),
+        )));
+    }
+
+    Ok(Json(
+        state
+            .service
+            .notification
+            .create_announcement(
+                user.id,
+                &request.title,
+                &request.message,
+                request.segment,
+                request.segment_value,
+                request.scheduled_at,
+            )
+            .await?,
+    ))
+}
+
+pub async fn get_announcements(
+    Extension(role): Extension<String>,
+    Query(query): Query<AnnouncementQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AnnouncementResponse>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(ApiError::UserError(UserError::Str(
+            "You are not authorized to view announcements".to_string(),
+        )));
+    }
+    let limit = query.limit.unwrap_or(20).min(100);
+    let offset = query.offset.unwrap_or(0);
+
+    Ok(Json(
+        state
+            .service
+            .notification
+            .get_announcements(offset, limit)
+            .await?,
+    ))
+}
+
+pub async fn cancel_announcement(
+    Extension(role): Extension<String>,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AnnouncementResponse>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(ApiError::UserError(UserError::Str(
+            "You are not authorized to cancel announcements".to_string(),
+        )));
+    }
+
+    Ok(Json(
+        state.service.notification.cancel_announcement(id).await?,
+    ))
+}
@@ -59,6 +59,7 @@ pub async fn get_predictions(
             opts.status,
             opts.category_id,
             opts.is_mine,
+            opts.sort,
             opts.offset,
             opts.limit,
         )
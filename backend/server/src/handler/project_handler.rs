@@ -1,29 +1,75 @@
+use crate::handler::{comment_handler::notify_comment_audience, viewer_key};
 use crate::state::AppState;
 use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
 use axum::{Extension, Json};
 use third_party_api::arweave::upload_project_submission;
 use types::dto::{
-    AdminProjectDashboardCounts, AssignEditorRequest, DaoStatisticsResponse, DaoVoteTab,
-    EditorDashboardCounts, GetDaosOption, GetProjectCommentsOption, GetProjectFundersOption,
-    GetProjectsOption, GetSimilarProjectsOption, GetUserDaoVotesOption, MakeDecisionRequest,
-    MilestoneApprovalRequest, ProjectCountsResponse, ProjectFundersResponse,
+    AdminProjectDashboardCounts, AssignEditorRequest, CreateProjectUpdateRequest,
+    DaoStatisticsResponse, DaoVoteTab, EditProjectUpdateRequest, EditorDashboardCounts,
+    GetAssignedReviewsOption, GetDaosOption, GetEditorRecommendationsOption,
+    GetMilestonePayoutsOption, GetProjectCommentsOption, GetProjectFundersOption,
+    GetProjectRevisionDiffOption, GetProjectStatusHistoryOption, GetProjectUpdatesOption,
+    GetProjectsOption, GetSimilarProjectsOption, GetUserDaoVotesOption, InviteCollaboratorRequest,
+    InviteReviewerRequest, MakeDecisionRequest, MilestoneApprovalRequest, Paginated,
+    ProjectBudgetValidationResponse, ProjectCountsResponse, ProjectFundersResponse,
     ProjectUpdateStep1Request, ProjectUpdateStep2Request, ProjectUpdateStep3Request,
-    ResearchProjectDashboardResponse, SubmitDaoVoteRequest, SubmitProjectCommentRequest,
-    UpdateMilestoneRequest, UserDaoVotingStats,
+    ResearchProjectDashboardResponse, SimilarityReindexCursor, SimilarityReindexResponse,
+    SubmitDaoVoteRequest, SubmitProjectCommentRequest, SubmitProjectReviewRequest,
+    SubmitProjectUpdateCommentRequest, UpdateMilestoneRequest, UserDaoVotingStats,
+    ValidateProjectBudgetRequest,
 };
 use types::error::{ApiError, UserError, ValidatedRequest};
 use types::models::{
-    DaoDetailInfo, DaoInfo, DaoVote, Milestone, ProjectCommentInfo, ProjectIds, ProjectInfo,
-    ProjectItemInfo, User,
+    CollaborationInvitationInfo, CommentEntityType, CommentInfo, DaoDetailInfo, DaoInfo, DaoVote,
+    EditorRecommendation, FollowEntityType, ManuscriptCheckInfo, Milestone, MilestonePayoutInfo,
+    PageRequest, ProjectCollaboratorInfo, ProjectCommentInfo, ProjectIds, ProjectInfo,
+    ProjectItemInfo, ProjectReviewInfo, ProjectReviewsResponse, ProjectRevisionDiff,
+    ProjectRevisionInfo, ProjectStatusHistoryInfo, ProjectUpdateCommentInfo, ProjectUpdateInfo,
+    ProjectUpdateVisibility, ReportTargetType, SimilarityMatchInfo, User,
 };
-use types::{FeedbackStatus, UserRoleType};
+use types::{FeedbackStatus, Statistics, UserRoleType};
 use utils::commons::uuid_from_str;
+use uuid::Uuid;
 
 pub async fn get_project_by_id(
+    Extension(user): Extension<Option<User>>,
+    Extension(role): Extension<Option<String>>,
+    headers: HeaderMap,
     Path(id): Path<String>,
     State(state): State<AppState>,
 ) -> Result<Json<ProjectInfo>, ApiError> {
-    let project = state.service.project.get_project_by_id(&id).await?;
+    let mut project = state
+        .service
+        .project
+        .get_project_by_id_without_increment(&id)
+        .await?;
+    let is_admin = role == Some(UserRoleType::Admin.to_string());
+    state
+        .service
+        .report
+        .ensure_visible(
+            ReportTargetType::Project,
+            project.id,
+            user.as_ref().map(|u| u.id),
+            is_admin,
+        )
+        .await?;
+    let viewer = viewer_key(user.as_ref(), &headers);
+    state
+        .service
+        .project
+        .record_view(project.id, viewer.as_deref())
+        .await;
+    if let Some(user) = user {
+        let (followed, bookmarked) = state
+            .service
+            .follow
+            .get_saved_ids(user.id, FollowEntityType::Project, &[project.id])
+            .await;
+        project.is_following = !followed.is_empty();
+        project.is_bookmarked = !bookmarked.is_empty();
+    }
     Ok(Json(project))
 }
 
@@ -45,6 +91,7 @@ pub async fn delete_project(
 }
 
 pub async fn update_project_step_1(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<ProjectUpdateStep1Request>,
@@ -52,12 +99,13 @@ pub async fn update_project_step_1(
     let res = state
         .service
         .project
-        .update_project_step_1(&id, payload)
+        .update_project_step_1(&id, user.id, payload)
         .await?;
     Ok(Json(res))
 }
 
 pub async fn update_project_step_2(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<ProjectUpdateStep2Request>,
@@ -65,12 +113,13 @@ pub async fn update_project_step_2(
     let res = state
         .service
         .project
-        .update_project_step_2(&id, payload)
+        .update_project_step_2(&id, user.id, payload)
         .await?;
     Ok(Json(res))
 }
 
 pub async fn update_project_step_3(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<ProjectUpdateStep3Request>,
@@ -78,7 +127,7 @@ pub async fn update_project_step_3(
     let res = state
         .service
         .project
-        .update_project_step_3(&id, payload)
+        .update_project_step_3(&id, user.id, payload)
         .await?;
     Ok(Json(res))
 }
@@ -92,7 +141,7 @@ pub async fn submit_project(
     // if user.wallet_address.filter(|w| !w.is_empty()).is_none() {
     //     return Err(UserError::Str("Wallet address is not set".to_string()).into());
     // }
-    let res = state.service.project.submit_project(&id).await?;
+    let res = state.service.project.submit_project(&id, user.id).await?;
 
     let project_info = state
         .service
@@ -100,6 +149,17 @@ pub async fn submit_project(
         .get_project_by_id_without_increment(&id)
         .await?;
     if res {
+        if let Err(e) = state
+            .service
+            .similarity
+            .check_project(project_info.id)
+            .await
+        {
+            println!(
+                "Failed to check similarity of project {}: {:?}",
+                project_info.id, e
+            );
+        }
         // Upload project submission metadata to Arweave
         let project_data = serde_json::json!(project_info);
 
@@ -125,6 +185,309 @@ pub async fn submit_project(
     Ok(Json(res))
 }
 
+pub async fn validate_project_budget(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<ValidateProjectBudgetRequest>,
+) -> Result<Json<ProjectBudgetValidationResponse>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .validate_project_budget(&id, user.id, payload)
+            .await?,
+    ))
+}
+
+pub async fn get_project_draft(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<ProjectInfo>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_project_draft(&id, user.id)
+            .await?,
+    ))
+}
+
+pub async fn get_collaborators(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectCollaboratorInfo>>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_collaborators(&id, user.id)
+            .await?,
+    ))
+}
+
+pub async fn invite_collaborator(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<InviteCollaboratorRequest>,
+) -> Result<Json<ProjectCollaboratorInfo>, ApiError> {
+    let collaborator = state
+        .service
+        .project
+        .invite_collaborator(&id, user.id, payload.user_id, payload.role)
+        .await?;
+    if let Ok(project) = state
+        .service
+        .project
+        .get_project_by_id_without_increment(&id)
+        .await
+    {
+        let _ = state
+            .service
+            .notification
+            .notify_collaborator_invitation(
+                payload.user_id,
+                project.id,
+                &project.title.unwrap_or_default(),
+                &user.name.clone().unwrap_or_default(),
+                payload.role.label(),
+            )
+            .await;
+    }
+    Ok(Json(collaborator))
+}
+
+pub async fn remove_collaborator(
+    Extension(user): Extension<User>,
+    Path((id, user_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    let user_id = uuid_from_str(&user_id)?;
+    Ok(Json(
+        state
+            .service
+            .project
+            .remove_collaborator(&id, user.id, user_id)
+            .await?,
+    ))
+}
+
+pub async fn accept_collaboration(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    respond_collaboration(user, id, state, true).await
+}
+
+pub async fn decline_collaboration(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    respond_collaboration(user, id, state, false).await
+}
+
+async fn respond_collaboration(
+    user: User,
+    id: String,
+    state: AppState,
+    accept: bool,
+) -> Result<Json<bool>, ApiError> {
+    let res = state
+        .service
+        .project
+        .respond_collaboration(&id, user.id, accept)
+        .await?;
+    if let Ok(project) = state
+        .service
+        .project
+        .get_project_by_id_without_increment(&id)
+        .await
+    {
+        let _ = state
+            .service
+            .notification
+            .notify_collaborator_response(
+                project.user.id,
+                project.id,
+                &project.title.unwrap_or_default(),
+                &user.name.clone().unwrap_or_default(),
+                accept,
+            )
+            .await;
+    }
+    Ok(Json(res))
+}
+
+pub async fn get_collaboration_invitations(
+    Extension(user): Extension<User>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CollaborationInvitationInfo>>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_collaboration_invitations(user.id)
+            .await?,
+    ))
+}
+
+pub async fn create_project_update(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<CreateProjectUpdateRequest>,
+) -> Result<Json<ProjectUpdateInfo>, ApiError> {
+    let update = state
+        .service
+        .project
+        .create_project_update(&id, user.id, payload)
+        .await?;
+    if let Ok(project) = state
+        .service
+        .project
+        .get_project_by_id_without_increment(&id)
+        .await
+    {
+        let mut user_ids: Vec<Uuid> = state
+            .service
+            .project
+            .get_funder_ids(project.id)
+            .await
+            .unwrap_or_default();
+        if update.visibility == ProjectUpdateVisibility::Public {
+            user_ids.extend(
+                state
+                    .service
+                    .follow
+                    .get_follower_ids(&[(FollowEntityType::Project, project.id)])
+                    .await
+                    .unwrap_or_default(),
+            );
+        }
+        user_ids.sort();
+        user_ids.dedup();
+        user_ids.retain(|id| *id != user.id);
+        if !user_ids.is_empty() {
+            let _ = state
+                .service
+                .notification
+                .notify_project_update(
+                    user_ids,
+                    project.id,
+                    update.id,
+                    &project.title.unwrap_or_default(),
+                    &update.title,
+                    &user.name.clone().unwrap_or_default(),
+                )
+                .await;
+        }
+    }
+    Ok(Json(update))
+}
+
+pub async fn edit_project_update(
+    Extension(user): Extension<User>,
+    Path(update_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<EditProjectUpdateRequest>,
+) -> Result<Json<ProjectUpdateInfo>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .edit_project_update(&update_id, user.id, payload)
+            .await?,
+    ))
+}
+
+pub async fn delete_project_update(
+    Extension(user): Extension<User>,
+    Path(update_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .project
+            .delete_project_update(&update_id, user.id)
+            .await?,
+    ))
+}
+
+pub async fn get_project_updates(
+    Extension(user): Extension<Option<User>>,
+    Extension(role): Extension<Option<String>>,
+    Path(id): Path<String>,
+    Query(opts): Query<GetProjectUpdatesOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectUpdateInfo>>, ApiError> {
+    let is_admin = role == Some(UserRoleType::Admin.to_string());
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_project_updates(&id, user.map(|u| u.id), is_admin, opts.offset, opts.limit)
+            .await?,
+    ))
+}
+
+pub async fn get_project_update_comments(
+    Extension(user): Extension<Option<User>>,
+    Extension(role): Extension<Option<String>>,
+    Path(update_id): Path<String>,
+    Query(opts): Query<GetProjectUpdatesOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectUpdateCommentInfo>>, ApiError> {
+    let is_admin = role == Some(UserRoleType::Admin.to_string());
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_project_update_comments(
+                &update_id,
+                user.map(|u| u.id),
+                is_admin,
+                opts.offset,
+                opts.limit,
+            )
+            .await?,
+    ))
+}
+
+pub async fn submit_project_update_comment(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(update_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<SubmitProjectUpdateCommentRequest>,
+) -> Result<Json<ProjectUpdateCommentInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    let (comment, update) = state
+        .service
+        .project
+        .submit_project_update_comment(&update_id, &user, is_admin, &payload.comment)
+        .await?;
+    if update.user_id != user.id {
+        let _ = state
+            .service
+            .notification
+            .notify_project_update_comment(
+                update.user_id,
+                update.project_id,
+                update.id,
+                &update.title,
+                &user.name.clone().unwrap_or_default(),
+            )
+            .await;
+    }
+    Ok(Json(comment))
+}
+
 pub async fn get_project_ids(
     State(state): State<AppState>,
 ) -> Result<Json<Vec<ProjectIds>>, ApiError> {
@@ -137,7 +500,7 @@ pub async fn get_projects(
     Extension(role): Extension<Option<String>>,
     Query(opts): Query<GetProjectsOption>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<ProjectItemInfo>>, ApiError> {
+) -> Result<Json<Paginated<ProjectItemInfo>>, ApiError> {
     let res = state
         .service
         .project
@@ -149,7 +512,8 @@ pub async fn get_projects(
             user.map(|u| u.id),
             opts.is_mine,
             opts.is_public,
-            opts.offset,
+            opts.sort,
+            PageRequest::new(opts.offset, opts.cursor.as_deref())?,
             opts.limit,
         )
         .await?;
@@ -157,6 +521,7 @@ pub async fn get_projects(
 }
 
 pub async fn assign_editor(
+    Extension(user): Extension<User>,
     Extension(role): Extension<String>,
     Path(id): Path<String>,
     State(state): State<AppState>,
@@ -169,7 +534,25 @@ pub async fn assign_editor(
         state
             .service
             .project
-            .assign_editor(&id, payload.editor_id)
+            .assign_editor(&id, payload.editor_id, user.id)
+            .await?,
+    ))
+}
+
+pub async fn get_editor_recommendations(
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    Query(opts): Query<GetEditorRecommendationsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<EditorRecommendation>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_editor_recommendations(&id, opts.limit)
             .await?,
     ))
 }
@@ -204,6 +587,7 @@ pub async fn make_decision(
                     FeedbackStatus::from(payload.status),
                     payload.feedback,
                     payload.to_dao.unwrap_or_default(),
+                    user.id,
                     &state.evm,
                 )
                 .await?,
@@ -212,13 +596,114 @@ pub async fn make_decision(
     Err(UserError::RoleNotAllowed)?
 }
 
+pub async fn invite_reviewer(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<InviteReviewerRequest>,
+) -> Result<Json<ProjectReviewInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    if !is_admin && role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .review
+            .invite_reviewer(&id, payload.reviewer_id, payload.due_at, &user, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn cancel_reviewer(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path((id, reviewer_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    if !is_admin && role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    let reviewer_id = uuid_from_str(&reviewer_id)?;
+    Ok(Json(
+        state
+            .service
+            .review
+            .cancel_reviewer(&id, reviewer_id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn get_project_reviews(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<ProjectReviewsResponse>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    if !is_admin && role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .review
+            .get_project_reviews(&id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn submit_project_review(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<SubmitProjectReviewRequest>,
+) -> Result<Json<ProjectReviewInfo>, ApiError> {
+    if role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .review
+            .submit_review(&id, &user, payload)
+            .await?,
+    ))
+}
+
+pub async fn get_assigned_reviews(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Query(opts): Query<GetAssignedReviewsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectReviewInfo>>, ApiError> {
+    if role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .review
+            .get_assigned_reviews(user.id, opts.status, opts.offset, opts.limit)
+            .await?,
+    ))
+}
+
 pub async fn update_milestone(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<UpdateMilestoneRequest>,
 ) -> Result<Json<bool>, ApiError> {
     Ok(Json(
-        state.service.project.update_milestone(&id, payload).await?,
+        state
+            .service
+            .project
+            .update_milestone(&id, user.id, payload)
+            .await?,
     ))
 }
 
@@ -249,16 +734,49 @@ pub async fn get_milestones(
     Ok(Json(state.service.project.get_milestones(&id).await?))
 }
 
+/// Flat listing kept for existing clients; see `get_project_comment_threads`
 pub async fn get_project_comments(
+    Extension(user): Extension<User>,
     Path(id): Path<String>,
     Query(opts): Query<GetProjectCommentsOption>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<ProjectCommentInfo>>, ApiError> {
+) -> Result<Json<Paginated<ProjectCommentInfo>>, ApiError> {
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
+    let comments = state
+        .service
+        .comment
+        .get_comments(
+            CommentEntityType::Project,
+            &id,
+            Some(user.id),
+            page,
+            opts.limit,
+        )
+        .await?;
+    Ok(Json(comments.flat_map(|c| {
+        c.flatten().into_iter().map(ProjectCommentInfo::from)
+    })))
+}
+
+/// Comments with their replies, reactions and pins
+pub async fn get_project_comment_threads(
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Query(opts): Query<GetProjectCommentsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Paginated<CommentInfo>>, ApiError> {
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
     Ok(Json(
         state
             .service
-            .project
-            .get_project_comments(&id, opts.offset, opts.limit)
+            .comment
+            .get_comments(
+                CommentEntityType::Project,
+                &id,
+                Some(user.id),
+                page,
+                opts.limit,
+            )
             .await?,
     ))
 }
@@ -269,13 +787,33 @@ pub async fn submit_project_comment(
     State(state): State<AppState>,
     ValidatedRequest(payload): ValidatedRequest<SubmitProjectCommentRequest>,
 ) -> Result<Json<bool>, ApiError> {
-    Ok(Json(
-        state
+    let posted = state
+        .service
+        .comment
+        .create_comment(
+            CommentEntityType::Project,
+            &id,
+            user.id,
+            &payload.comment,
+            payload.parent_id,
+        )
+        .await?;
+    let notified =
+        notify_comment_audience(&state, CommentEntityType::Project, &posted, &user).await;
+    let owner_id = posted.target.user_id;
+    if owner_id != user.id && !notified.contains(&owner_id) {
+        let _ = state
             .service
-            .project
-            .submit_project_comment(&id, user.id, &payload.comment)
-            .await?,
-    ))
+            .notification
+            .notify_project_comment(
+                owner_id,
+                posted.target.id,
+                &posted.target.title.clone().unwrap_or_default(),
+                &user.name.clone().unwrap_or_default(),
+            )
+            .await;
+    }
+    Ok(Json(true))
 }
 
 pub async fn get_daos(
@@ -292,6 +830,7 @@ pub async fn get_daos(
                 opts.status,
                 user.map(|u| u.id),
                 opts.is_mine,
+                opts.sort,
                 opts.offset,
                 opts.limit,
             )
@@ -364,6 +903,143 @@ pub async fn get_project_counts_by_status(
     Ok(Json(counts))
 }
 
+pub async fn get_project_revisions(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectRevisionInfo>>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_project_revisions(&id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn get_project_revision_diff(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    Query(opts): Query<GetProjectRevisionDiffOption>,
+    State(state): State<AppState>,
+) -> Result<Json<ProjectRevisionDiff>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_project_revision_diff(&id, user.id, is_admin, opts.from, opts.to)
+            .await?,
+    ))
+}
+
+pub async fn get_project_similarity(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SimilarityMatchInfo>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() && role != UserRoleType::Editor.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .similarity
+            .get_project_matches(&id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn reindex_similarity(
+    Extension(role): Extension<String>,
+    Query(cursor): Query<SimilarityReindexCursor>,
+    State(state): State<AppState>,
+) -> Result<Json<SimilarityReindexResponse>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(state.service.similarity.reindex(cursor).await?))
+}
+
+pub async fn run_error_check(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<ManuscriptCheckInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .manuscript_check
+            .run_error_check(&id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn get_error_check(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Option<ManuscriptCheckInfo>>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .manuscript_check
+            .get_error_check(&id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn get_error_check_statistics(
+    State(state): State<AppState>,
+) -> Result<Json<Statistics>, ApiError> {
+    Ok(Json(
+        state
+            .service
+            .manuscript_check
+            .get_error_statistics()
+            .await?,
+    ))
+}
+
+pub async fn get_project_status_history(
+    Extension(role): Extension<String>,
+    Query(opts): Query<GetProjectStatusHistoryOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectStatusHistoryInfo>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state
+            .service
+            .project
+            .get_status_history(opts.project_id, opts.offset, opts.limit)
+            .await?,
+    ))
+}
+
+pub async fn get_milestone_payouts(
+    Extension(role): Extension<String>,
+    Query(opts): Query<GetMilestonePayoutsOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<MilestonePayoutInfo>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(
+        state.service.project.get_milestone_payouts(opts).await?,
+    ))
+}
+
 pub async fn get_admin_project_dashboard_counts(
     Extension(role): Extension<String>,
     State(state): State<AppState>,
@@ -409,10 +1085,11 @@ pub async fn get_project_funders(
     State(state): State<AppState>,
 ) -> Result<Json<ProjectFundersResponse>, ApiError> {
     let project_id = uuid_from_str(&id)?;
+    let page = PageRequest::new(opts.offset, opts.cursor.as_deref())?;
     let funders = state
         .service
         .project
-        .get_project_funders_full(project_id, opts.offset, opts.limit)
+        .get_project_funders_full(project_id, page, opts.limit)
         .await?;
     Ok(Json(funders))
 }
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use types::{
+    dto::{
+        CreateReportRequest, GetModerationQueueOption, ModerateContentAction,
+        ModerateContentRequest,
+    },
+    error::{ApiError, UserError, ValidatedRequest},
+    models::{ModerationQueueItem, Report, User},
+    UserRoleType,
+};
+
+use crate::state::AppState;
+
+/// How much of a removed comment or message is quoted back to its author
+const REMOVED_PREVIEW_CHARS: usize = 80;
+
+pub async fn create_report(
+    Extension(user): Extension<User>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<CreateReportRequest>,
+) -> Result<Json<Report>, ApiError> {
+    Ok(Json(
+        state.service.report.create_report(user.id, payload).await?,
+    ))
+}
+
+pub async fn get_moderation_queue(
+    Extension(role): Extension<String>,
+    Query(opts): Query<GetModerationQueueOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ModerationQueueItem>>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    Ok(Json(state.service.report.get_moderation_queue(opts).await?))
+}
+
+pub async fn moderate_content(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<ModerateContentRequest>,
+) -> Result<Json<bool>, ApiError> {
+    if role != UserRoleType::Admin.to_string() {
+        return Err(UserError::RoleNotAllowed)?;
+    }
+    let target_type = payload.target_type;
+    let action = payload.action;
+    let note = payload.note.clone();
+    let target = state
+        .service
+        .report
+        .moderate_content(user.id, payload)
+        .await?;
+    if action == ModerateContentAction::Remove {
+        let preview = target.preview.unwrap_or_default();
+        let title = match preview.char_indices().nth(REMOVED_PREVIEW_CHARS) {
+            Some((i, _)) => format!("{}...", &preview[..i]),
+            None => preview,
+        };
+        let _ = state
+            .service
+            .notification
+            .notify_content_removed(
+                target.author_id,
+                target_type.label(),
+                target.id,
+                &title,
+                note.as_deref(),
+            )
+            .await;
+    }
+    Ok(Json(true))
+}
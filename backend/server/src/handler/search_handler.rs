@@ -0,0 +1,13 @@
+use crate::state::AppState;
+use axum::extract::{Query, State};
+use axum::Json;
+use types::dto::SearchOption;
+use types::error::ApiError;
+use types::models::SearchResponse;
+
+pub async fn search(
+    Query(opts): Query<SearchOption>,
+    State(state): State<AppState>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    Ok(Json(state.service.search.search(opts).await?))
+}
@@ -1292,6 +1292,13 @@ pub async fn update_notification_settings(
         .user
         .update_notification_settings(user.id, payload)
         .await?;
+    if user.archive_notifications && !result.archive_notifications {
+        state
+            .service
+            .notification
+            .delete_archived_notifications(user.id)
+            .await?;
+    }
     Ok(Json(result))
 }
 
@@ -0,0 +1,125 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use types::{
+    dto::{CreateWebhookEndpointRequest, GetWebhookDeliveriesOption, UpdateWebhookEndpointRequest},
+    error::{ApiError, ValidatedRequest},
+    models::{User, WebhookDeliveryInfo, WebhookEndpointInfo},
+    UserRoleType,
+};
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+pub async fn create_webhook_endpoint(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<CreateWebhookEndpointRequest>,
+) -> Result<Json<WebhookEndpointInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .create_endpoint(user.id, is_admin, payload)
+            .await?,
+    ))
+}
+
+pub async fn get_webhook_endpoints(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WebhookEndpointInfo>>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .get_endpoints(user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn update_webhook_endpoint(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    ValidatedRequest(payload): ValidatedRequest<UpdateWebhookEndpointRequest>,
+) -> Result<Json<WebhookEndpointInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .update_endpoint(id, user.id, is_admin, payload)
+            .await?,
+    ))
+}
+
+pub async fn rotate_webhook_secret(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<WebhookEndpointInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .rotate_endpoint_secret(id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn delete_webhook_endpoint(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<bool>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .delete_endpoint(id, user.id, is_admin)
+            .await?,
+    ))
+}
+
+pub async fn get_webhook_deliveries(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Query(opts): Query<GetWebhookDeliveriesOption>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WebhookDeliveryInfo>>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .get_deliveries(user.id, is_admin, opts)
+            .await?,
+    ))
+}
+
+pub async fn retry_webhook_delivery(
+    Extension(user): Extension<User>,
+    Extension(role): Extension<String>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<WebhookDeliveryInfo>, ApiError> {
+    let is_admin = role == UserRoleType::Admin.to_string();
+    Ok(Json(
+        state
+            .service
+            .webhook
+            .retry_delivery(id, user.id, is_admin)
+            .await?,
+    ))
+}
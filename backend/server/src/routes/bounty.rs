@@ -2,11 +2,12 @@ use crate::{
     handler::bounty_handler::{
         cancel_bounty, create_bidder_chat, create_bounty, delete_bounty,
         finalize_bounty_work_submission, get_bid_milestone_submissions, get_bounty_chat_numbers,
-        get_bounty_chats, get_bounty_comments, get_bounty_work_submission, get_my_bids,
-        get_my_bounty_stats, get_winning_bid_milestones, handle_bounty_action, mark_chat_as_read,
-        reject_bid, reject_bid_milestone, review_bid_milestone_submission, review_bounty,
-        review_bounty_work_submission, save_bounty_work, select_as_winner, send_bounty_chat,
-        submit_bid, submit_bid_milestone_work, submit_bounty_comment, update_bounty,
+        get_bounty_chats, get_bounty_comment_threads, get_bounty_comments,
+        get_bounty_work_submission, get_my_bids, get_my_bounty_stats, get_winning_bid_milestones,
+        handle_bounty_action, mark_chat_as_read, reject_bid, reject_bid_milestone,
+        review_bid_milestone_submission, review_bounty, review_bounty_work_submission,
+        save_bounty_work, select_as_winner, send_bounty_chat, submit_bid,
+        submit_bid_milestone_work, submit_bounty_comment, update_bounty,
     },
     state::AppState,
 };
@@ -26,6 +27,10 @@ pub fn routes() -> Router<AppState> {
             get(get_winning_bid_milestones),
         )
         .route("/bounty/:id/comment", get(get_bounty_comments))
+        .route(
+            "/bounty/:id/comment/thread",
+            get(get_bounty_comment_threads),
+        )
         .route("/bounty/:id/comment", post(submit_bounty_comment))
         .route("/bounty/:id/review", post(review_bounty))
         .route("/bounty/:id/action", post(handle_bounty_action))
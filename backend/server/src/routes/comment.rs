@@ -0,0 +1,20 @@
+use crate::{handler::comment_handler::*, state::AppState};
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/comment/:entity_type/:comment_id", put(edit_comment))
+        .route("/comment/:entity_type/:comment_id", delete(delete_comment))
+        .route(
+            "/comment/:entity_type/:comment_id/history",
+            get(get_comment_history),
+        )
+        .route(
+            "/comment/:entity_type/:comment_id/reaction",
+            post(react_comment),
+        )
+        .route("/comment/:entity_type/:comment_id/pin", put(pin_comment))
+}
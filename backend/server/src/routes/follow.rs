@@ -0,0 +1,13 @@
+use crate::{handler::follow_handler::*, state::AppState};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/follow", get(get_follows))
+        .route("/follow/:entity_type/:id", post(toggle_follow))
+        .route("/bookmark", get(get_bookmarks))
+        .route("/bookmark/:entity_type/:id", post(toggle_bookmark))
+}
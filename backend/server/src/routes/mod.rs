@@ -1,10 +1,14 @@
 mod auth;
 mod bounty;
+mod comment;
+mod follow;
 mod notification;
 mod project;
+mod report;
 mod public;
 mod user;
 mod util;
+mod webhook;
 
 use crate::{
     middleware::{auth as auth_middleware, public as public_middleware},
@@ -53,10 +57,14 @@ pub async fn routes(db_conn: Arc<DatabasePool>, env: Env) -> IntoMakeService<Rou
         let app_state = AppState::init(&db_conn, env, s3_client, ses_client);
         Router::new()
             .merge(bounty::routes())
+            .merge(comment::routes())
+            .merge(follow::routes())
             .merge(notification::routes())
             .merge(project::routes())
+            .merge(report::routes())
             .merge(user::routes())
             .merge(util::routes())
+            .merge(webhook::routes())
             .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
                 app_state.clone(),
                 auth_middleware,
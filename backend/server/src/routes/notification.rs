@@ -8,8 +8,15 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/notification", get(get_notifications))
         .route("/notification/unread", get(get_unread_notifications))
+        .route("/notification/archived", get(get_archived_notifications))
         .route("/notification/count", get(get_notification_count))
         .route("/notification", post(create_notification))
+        .route("/notification/announcement", get(get_announcements))
+        .route("/notification/announcement", post(create_announcement))
+        .route(
+            "/notification/announcement/:id/cancel",
+            put(cancel_announcement),
+        )
         .route(
             "/notification/read-all",
             put(mark_all_notifications_as_read),
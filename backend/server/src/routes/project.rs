@@ -2,18 +2,26 @@ use crate::{
     handler::{
         prediction_handler::get_my_prediction_stats,
         project_handler::{
-            approve_reject_milestone, assign_editor, create_project, delete_project,
-            get_admin_project_dashboard_counts, get_editor_dashboard_counts, get_milestones,
-            get_my_dao_vote, get_project_comments, get_research_projects_dashboard,
-            get_user_dao_votes, get_user_dao_voting_stats, make_decision, submit_dao_vote,
-            submit_project, submit_project_comment, update_milestone, update_project_step_1,
-            update_project_step_2, update_project_step_3,
+            accept_collaboration, approve_reject_milestone, assign_editor, cancel_reviewer,
+            create_project, create_project_update, decline_collaboration, delete_project,
+            delete_project_update, edit_project_update, get_admin_project_dashboard_counts,
+            get_assigned_reviews, get_collaboration_invitations, get_collaborators,
+            get_editor_dashboard_counts, get_editor_recommendations, get_error_check,
+            get_milestone_payouts, get_milestones, get_my_dao_vote, get_project_comment_threads,
+            get_project_comments, get_project_draft, get_project_reviews,
+            get_project_revision_diff, get_project_revisions, get_project_similarity,
+            get_project_status_history, get_research_projects_dashboard, get_user_dao_votes,
+            get_user_dao_voting_stats, invite_collaborator, invite_reviewer, make_decision,
+            reindex_similarity, remove_collaborator, run_error_check, submit_dao_vote,
+            submit_project, submit_project_comment, submit_project_review,
+            submit_project_update_comment, update_milestone, update_project_step_1,
+            update_project_step_2, update_project_step_3, validate_project_budget,
         },
     },
     state::AppState,
 };
 use axum::{
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
@@ -25,13 +33,62 @@ pub fn routes() -> Router<AppState> {
         .route("/project/:id/2", patch(update_project_step_2))
         .route("/project/:id/3", patch(update_project_step_3))
         .route("/project/:id/submit", post(submit_project))
+        .route("/project/:id/validate", post(validate_project_budget))
+        .route("/project/:id/draft", get(get_project_draft))
+        .route("/project/:id/collaborator", get(get_collaborators))
+        .route("/project/:id/collaborator", post(invite_collaborator))
+        .route(
+            "/project/:id/collaborator/:user_id",
+            delete(remove_collaborator),
+        )
+        .route(
+            "/project/:id/collaborator/accept",
+            post(accept_collaboration),
+        )
+        .route(
+            "/project/:id/collaborator/decline",
+            post(decline_collaboration),
+        )
+        .route(
+            "/project/collaboration/invitation",
+            get(get_collaboration_invitations),
+        )
         .route("/project/:id/editor", post(assign_editor))
+        .route(
+            "/project/:id/editor/recommendation",
+            get(get_editor_recommendations),
+        )
         .route("/project/:id/decide", patch(make_decision))
+        .route("/project/:id/reviewer", post(invite_reviewer))
+        .route(
+            "/project/:id/reviewer/:reviewer_id",
+            delete(cancel_reviewer),
+        )
+        .route("/project/:id/review", get(get_project_reviews))
+        .route("/project/:id/review", post(submit_project_review))
+        .route("/project/review/assigned", get(get_assigned_reviews))
         .route("/milestone/:id", patch(update_milestone))
         .route("/milestone/:id/approve", patch(approve_reject_milestone))
         .route("/project/:id/milestone", get(get_milestones))
+        .route("/project/:id/revision", get(get_project_revisions))
+        .route("/project/:id/revision/diff", get(get_project_revision_diff))
+        .route("/project/:id/error-check", get(get_error_check))
+        .route("/project/:id/similarity", get(get_project_similarity))
+        .route("/similarity/reindex", post(reindex_similarity))
+        .route("/project/:id/error-check", post(run_error_check))
         .route("/project/:id/comment", get(get_project_comments))
+        .route(
+            "/project/:id/comment/thread",
+            get(get_project_comment_threads),
+        )
         .route("/project/:id/comment", post(submit_project_comment))
+        .route("/project/:id/update", post(create_project_update))
+        .route("/project/update/:update_id", put(edit_project_update))
+        .route("/project/update/:update_id", delete(delete_project_update))
+        .route(
+            "/project/update/:update_id/comment",
+            post(submit_project_update_comment),
+        )
         .route("/dao/:id/vote", post(submit_dao_vote))
         .route("/dao/:id/vote", get(get_my_dao_vote))
         .route("/dao/stats", get(get_user_dao_voting_stats))
@@ -41,6 +98,11 @@ pub fn routes() -> Router<AppState> {
             "/project/admin/counts",
             get(get_admin_project_dashboard_counts),
         )
+        .route("/project/admin/payouts", get(get_milestone_payouts))
+        .route(
+            "/project/admin/status-history",
+            get(get_project_status_history),
+        )
         .route("/project/editor/counts", get(get_editor_dashboard_counts))
         .route(
             "/project/research/dashboard",
@@ -3,10 +3,11 @@ use crate::{
         bounty_handler::{get_bids, get_bounties, get_bounty_by_id, get_similar_bounties},
         prediction_handler::{get_prediction_by_id, get_predictions, get_top_predictors},
         project_handler::{
-            get_dao_by_id, get_dao_statistics, get_daos, get_project_by_id,
-            get_project_counts_by_status, get_project_funders, get_project_ids, get_projects,
-            get_similar_projects,
+            get_dao_by_id, get_dao_statistics, get_daos, get_error_check_statistics,
+            get_project_by_id, get_project_counts_by_status, get_project_funders, get_project_ids,
+            get_project_update_comments, get_project_updates, get_projects, get_similar_projects,
         },
+        search_handler::search,
         user_handler::get_user_profile_by_username,
         util_handler::{get_categories, get_category_by_id, get_institutions},
     },
@@ -26,9 +27,15 @@ pub fn routes() -> Router<AppState> {
         .route("/project/ids", get(get_project_ids))
         .route("/project/:id/funders", get(get_project_funders))
         .route("/project/:id/similar", get(get_similar_projects))
+        .route("/project/:id/update", get(get_project_updates))
+        .route(
+            "/project/update/:update_id/comment",
+            get(get_project_update_comments),
+        )
         .route("/dao", get(get_daos))
         .route("/dao/:id", get(get_dao_by_id))
         .route("/dao/statistics", get(get_dao_statistics))
+        .route("/error-check/statistics", get(get_error_check_statistics))
         .route("/bounty", get(get_bounties))
         .route("/bounty/:id", get(get_bounty_by_id))
         .route("/bounty/:id/bid", get(get_bids))
@@ -36,4 +43,5 @@ pub fn routes() -> Router<AppState> {
         .route("/prediction", get(get_predictions))
         .route("/prediction/:id", get(get_prediction_by_id))
         .route("/prediction/top", get(get_top_predictors))
+        .route("/search", get(search))
 }
@@ -0,0 +1,12 @@
+use crate::{handler::report_handler::*, state::AppState};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/report", post(create_report))
+        .route("/moderation/queue", get(get_moderation_queue))
+        .route("/moderation/action", post(moderate_content))
+}
@@ -0,0 +1,16 @@
+use crate::{handler::webhook_handler::*, state::AppState};
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/webhook", get(get_webhook_endpoints))
+        .route("/webhook", post(create_webhook_endpoint))
+        .route("/webhook/delivery", get(get_webhook_deliveries))
+        .route("/webhook/delivery/:id/retry", put(retry_webhook_delivery))
+        .route("/webhook/:id", put(update_webhook_endpoint))
+        .route("/webhook/:id/secret", put(rotate_webhook_secret))
+        .route("/webhook/:id", delete(delete_webhook_endpoint))
+}
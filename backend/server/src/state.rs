@@ -4,7 +4,7 @@ use evm::EVMClient;
 use std::{collections::HashMap, sync::Arc};
 use twitter_v2::{authorization::Oauth2Client, oauth2::PkceCodeVerifier};
 use types::NerdNuggetsOAuth2AppName;
-use utils::env::Env;
+use utils::{env::Env, templates::Templates};
 use uuid::Uuid;
 
 #[allow(dead_code)]
@@ -93,6 +93,7 @@ pub struct AppState {
     pub service: AppService,
     pub s3_client: aws_sdk_s3::Client,
     pub ses_client: aws_sdk_sesv2::Client,
+    pub templates: Templates,
 }
 
 impl AppState {
@@ -110,12 +111,14 @@ impl AppState {
             &env.rpc_url,
             env.chain_id,
         );
+        let templates = Templates::init(&env.templates_dir);
         Self {
-            service: AppService::init(db, &env),
+            service: AppService::init(db, &env, &templates),
             env,
             evm,
             s3_client,
             ses_client,
+            templates,
         }
     }
 }
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_announcements_status_scheduled_at;
+DROP INDEX IF EXISTS idx_announcements_created_at;
+DROP TABLE IF EXISTS announcements;
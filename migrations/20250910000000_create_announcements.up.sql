@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS announcements (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        sender_id UUID NOT NULL,
+        title VARCHAR(255) NOT NULL,
+        message TEXT NOT NULL,
+        segment_type SMALLINT NOT NULL DEFAULT 0,
+        segment_value VARCHAR(255),
+        status SMALLINT NOT NULL DEFAULT 0,
+        recipient_count INTEGER NOT NULL DEFAULT 0,
+        scheduled_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        sent_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT now(),
+        updated_at TIMESTAMPTZ DEFAULT now(),
+        FOREIGN KEY (sender_id) REFERENCES users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_announcements_status_scheduled_at ON announcements(status, scheduled_at);
+CREATE INDEX IF NOT EXISTS idx_announcements_created_at ON announcements(created_at);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_notifications_user_collapse_key;
+
+ALTER TABLE notifications
+DROP COLUMN IF EXISTS collapse_key,
+DROP COLUMN IF EXISTS group_count,
+DROP COLUMN IF EXISTS actors;
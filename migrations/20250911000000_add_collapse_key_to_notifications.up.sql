@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+ALTER TABLE notifications
+ADD COLUMN IF NOT EXISTS collapse_key VARCHAR(255),
+ADD COLUMN IF NOT EXISTS group_count INTEGER NOT NULL DEFAULT 1,
+ADD COLUMN IF NOT EXISTS actors TEXT[] NOT NULL DEFAULT '{}';
+
+-- Only one unread notification per collapse key, so new ones merge into it
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notifications_user_collapse_key
+ON notifications(user_id, collapse_key)
+WHERE collapse_key IS NOT NULL AND is_read = false;
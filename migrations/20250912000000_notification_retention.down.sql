@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+ALTER TABLE users DROP COLUMN IF EXISTS archive_notifications;
+
+DROP INDEX IF EXISTS idx_notifications_archive_user_id_created_at;
+DROP TABLE IF EXISTS notifications_archive;
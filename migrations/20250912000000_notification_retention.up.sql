@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS notifications_archive (
+        id BIGINT PRIMARY KEY,
+        user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        notification_type INTEGER NOT NULL,
+        title VARCHAR(255) NOT NULL,
+        message TEXT NOT NULL,
+        data JSONB,
+        is_read BOOLEAN DEFAULT false,
+        collapse_key VARCHAR(255),
+        group_count INTEGER NOT NULL DEFAULT 1,
+        actors TEXT[] NOT NULL DEFAULT '{}',
+        created_at TIMESTAMPTZ,
+        updated_at TIMESTAMPTZ,
+        archived_at TIMESTAMPTZ DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_notifications_archive_user_id_created_at ON notifications_archive(user_id, created_at);
+
+-- Users who opt out have expired notifications deleted instead of archived
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS archive_notifications BOOLEAN NOT NULL DEFAULT true;
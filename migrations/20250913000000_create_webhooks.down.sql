@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_webhook_deliveries_status_next_attempt_at;
+DROP INDEX IF EXISTS idx_webhook_deliveries_endpoint_id_created_at;
+DROP TABLE IF EXISTS webhook_deliveries;
+DROP INDEX IF EXISTS idx_webhook_endpoints_user_id;
+DROP TABLE IF EXISTS webhook_endpoints;
@@ -0,0 +1,38 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS webhook_endpoints (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        user_id UUID NOT NULL,
+        url VARCHAR(2048) NOT NULL,
+        secret VARCHAR(255) NOT NULL,
+        events SMALLINT[] NOT NULL DEFAULT '{}',
+        is_global BOOLEAN NOT NULL DEFAULT false,
+        is_active BOOLEAN NOT NULL DEFAULT true,
+        description TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_user_id ON webhook_endpoints(user_id);
+
+CREATE TABLE
+    IF NOT EXISTS webhook_deliveries (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        endpoint_id UUID NOT NULL,
+        event SMALLINT NOT NULL,
+        payload JSONB NOT NULL,
+        status SMALLINT NOT NULL DEFAULT 0,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        last_status_code INTEGER,
+        last_error TEXT,
+        delivered_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (endpoint_id) REFERENCES webhook_endpoints(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status_next_attempt_at ON webhook_deliveries(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_endpoint_id_created_at ON webhook_deliveries(endpoint_id, created_at);
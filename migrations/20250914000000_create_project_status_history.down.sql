@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_project_status_history_project_id_created_at;
+DROP INDEX IF EXISTS idx_project_status_history_created_at;
+DROP TABLE IF EXISTS project_status_history;
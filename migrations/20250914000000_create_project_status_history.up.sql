@@ -0,0 +1,17 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS project_status_history (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL,
+        actor_id UUID,
+        from_status SMALLINT NOT NULL,
+        to_status SMALLINT NOT NULL,
+        reason TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE,
+        FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_status_history_project_id_created_at ON project_status_history(project_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_project_status_history_created_at ON project_status_history(created_at);
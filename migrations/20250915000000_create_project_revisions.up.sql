@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS project_revisions (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL,
+        version INTEGER NOT NULL,
+        from_status SMALLINT NOT NULL,
+        submitted_by UUID,
+        snapshot JSONB NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE,
+        FOREIGN KEY (submitted_by) REFERENCES users(id) ON DELETE SET NULL,
+        UNIQUE (project_id, version)
+    );
@@ -0,0 +1,31 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS project_reviews (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL,
+        reviewer_id UUID NOT NULL,
+        invited_by UUID,
+        status SMALLINT NOT NULL DEFAULT 0,
+        score_objectives SMALLINT,
+        score_methodology SMALLINT,
+        score_budget SMALLINT,
+        score_expertise SMALLINT,
+        score_innovation SMALLINT,
+        recommendation SMALLINT,
+        comments TEXT,
+        confidential_comments TEXT,
+        due_at TIMESTAMPTZ NOT NULL,
+        reminded_at TIMESTAMPTZ,
+        overdue_notified_at TIMESTAMPTZ,
+        submitted_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE,
+        FOREIGN KEY (reviewer_id) REFERENCES users(id) ON DELETE CASCADE,
+        FOREIGN KEY (invited_by) REFERENCES users(id) ON DELETE SET NULL,
+        UNIQUE (project_id, reviewer_id)
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_reviews_reviewer_id ON project_reviews(reviewer_id);
+CREATE INDEX IF NOT EXISTS idx_project_reviews_pending_due_at ON project_reviews(due_at) WHERE status = 0;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS project_collaborators (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        role SMALLINT NOT NULL,
+        status SMALLINT NOT NULL DEFAULT 0,
+        invited_by UUID,
+        responded_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+        FOREIGN KEY (invited_by) REFERENCES users(id) ON DELETE SET NULL,
+        UNIQUE (project_id, user_id)
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_collaborators_user_id ON project_collaborators(user_id);
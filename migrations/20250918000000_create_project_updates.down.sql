@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS project_update_comments;
+DROP TABLE IF EXISTS project_updates;
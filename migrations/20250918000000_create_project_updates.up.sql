@@ -0,0 +1,32 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS project_updates (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        title VARCHAR(255) NOT NULL,
+        content TEXT NOT NULL,
+        attachments TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[],
+        visibility SMALLINT NOT NULL DEFAULT 0,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_updates_project_id ON project_updates(project_id, created_at DESC);
+
+CREATE TABLE
+    IF NOT EXISTS project_update_comments (
+        id UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+        update_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        comment TEXT NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        FOREIGN KEY (update_id) REFERENCES project_updates(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_update_comments_update_id ON project_update_comments(update_id);
@@ -0,0 +1,19 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS project_search_vector_update ON project;
+DROP TRIGGER IF EXISTS team_member_search_vector_update ON team_member;
+DROP TRIGGER IF EXISTS bounty_search_vector_update ON bounty;
+DROP TRIGGER IF EXISTS dao_search_vector_update ON dao;
+
+DROP FUNCTION IF EXISTS update_project_search_vector();
+DROP FUNCTION IF EXISTS update_project_search_vector_from_team();
+DROP FUNCTION IF EXISTS project_search_vector(UUID, TEXT, TEXT, TEXT, TEXT[]);
+DROP FUNCTION IF EXISTS update_bounty_search_vector();
+DROP FUNCTION IF EXISTS update_dao_search_vector();
+
+DROP INDEX IF EXISTS idx_project_title_trgm;
+DROP INDEX IF EXISTS idx_bounty_title_trgm;
+DROP INDEX IF EXISTS idx_dao_title_trgm;
+
+ALTER TABLE project DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE bounty DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE dao DROP COLUMN IF EXISTS search_vector;
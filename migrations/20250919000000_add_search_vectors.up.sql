@@ -0,0 +1,95 @@
+-- Add up migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE project ADD COLUMN search_vector TSVECTOR;
+ALTER TABLE bounty ADD COLUMN search_vector TSVECTOR;
+ALTER TABLE dao ADD COLUMN search_vector TSVECTOR;
+
+-- Title weighs most, then tags and description, then details, then the team
+CREATE OR REPLACE FUNCTION project_search_vector(
+    p_id UUID, p_title TEXT, p_description TEXT, p_details TEXT, p_tags TEXT[]
+)
+RETURNS TSVECTOR AS $$
+    SELECT setweight(to_tsvector('english', COALESCE(p_title, '')), 'A')
+        || setweight(to_tsvector('english', array_to_string(COALESCE(p_tags, '{}'), ' ')), 'B')
+        || setweight(to_tsvector('english', COALESCE(p_description, '')), 'B')
+        || setweight(to_tsvector('english', COALESCE(p_details, '')), 'C')
+        || setweight(to_tsvector('english', COALESCE(
+            (SELECT string_agg(name || ' ' || role, ' ') FROM team_member WHERE project_id = p_id),
+            ''
+        )), 'D');
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION update_project_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = project_search_vector(NEW.id, NEW.title, NEW.description, NEW.details, NEW.tags);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER project_search_vector_update
+    BEFORE INSERT OR UPDATE OF title, description, details, tags ON project
+    FOR EACH ROW
+    EXECUTE FUNCTION update_project_search_vector();
+
+CREATE OR REPLACE FUNCTION update_project_search_vector_from_team()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE project p
+    SET search_vector = project_search_vector(p.id, p.title, p.description, p.details, p.tags)
+    WHERE p.id = COALESCE(NEW.project_id, OLD.project_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER team_member_search_vector_update
+    AFTER INSERT OR UPDATE OR DELETE ON team_member
+    FOR EACH ROW
+    EXECUTE FUNCTION update_project_search_vector_from_team();
+
+CREATE OR REPLACE FUNCTION update_bounty_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = setweight(to_tsvector('english', COALESCE(NEW.title, '')), 'A')
+        || setweight(to_tsvector('english', array_to_string(COALESCE(NEW.tags, '{}'), ' ')), 'B')
+        || setweight(to_tsvector('english', COALESCE(NEW.description, '')), 'B')
+        || setweight(to_tsvector('english', array_to_string(
+            COALESCE(NEW.requirements, '{}') || COALESCE(NEW.deliverables, '{}'), ' '
+        )), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER bounty_search_vector_update
+    BEFORE INSERT OR UPDATE OF title, description, tags, requirements, deliverables ON bounty
+    FOR EACH ROW
+    EXECUTE FUNCTION update_bounty_search_vector();
+
+CREATE OR REPLACE FUNCTION update_dao_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = setweight(to_tsvector('english', COALESCE(NEW.title, '')), 'A')
+        || setweight(to_tsvector('english', COALESCE(NEW.description, '')), 'B')
+        || setweight(to_tsvector('english', COALESCE(NEW.details, '')), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER dao_search_vector_update
+    BEFORE INSERT OR UPDATE OF title, description, details ON dao
+    FOR EACH ROW
+    EXECUTE FUNCTION update_dao_search_vector();
+
+-- Backfill existing rows through the triggers
+UPDATE project SET title = title;
+UPDATE bounty SET title = title;
+UPDATE dao SET title = title;
+
+CREATE INDEX idx_project_search_vector ON project USING GIN (search_vector);
+CREATE INDEX idx_bounty_search_vector ON bounty USING GIN (search_vector);
+CREATE INDEX idx_dao_search_vector ON dao USING GIN (search_vector);
+
+CREATE INDEX idx_project_title_trgm ON project USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_bounty_title_trgm ON bounty USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_dao_title_trgm ON dao USING GIN (title gin_trgm_ops);
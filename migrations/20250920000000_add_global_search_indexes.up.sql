@@ -0,0 +1,6 @@
+-- Add up migration script here
+CREATE INDEX idx_prediction_title_trgm ON prediction USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_prediction_project_title_trgm ON prediction USING GIN (project_title gin_trgm_ops);
+CREATE INDEX idx_users_name_trgm ON users USING GIN (name gin_trgm_ops);
+CREATE INDEX idx_users_username_trgm ON users USING GIN (username gin_trgm_ops);
+CREATE INDEX idx_category_name_trgm ON category USING GIN (name gin_trgm_ops);
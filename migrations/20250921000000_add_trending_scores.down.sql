@@ -0,0 +1,12 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_project_trending_score;
+DROP INDEX IF EXISTS idx_bounty_trending_score;
+DROP INDEX IF EXISTS idx_dao_trending_score;
+DROP INDEX IF EXISTS idx_prediction_trending_score;
+
+ALTER TABLE project DROP COLUMN IF EXISTS trending_score;
+ALTER TABLE bounty DROP COLUMN IF EXISTS trending_score;
+ALTER TABLE dao DROP COLUMN IF EXISTS trending_score;
+ALTER TABLE prediction DROP COLUMN IF EXISTS trending_score;
+
+DROP TABLE IF EXISTS view_events;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+CREATE TABLE view_events (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type SMALLINT NOT NULL,
+    entity_id UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_view_events_entity ON view_events(entity_type, entity_id, created_at);
+CREATE INDEX idx_view_events_created_at ON view_events(created_at);
+
+ALTER TABLE project ADD COLUMN trending_score REAL NOT NULL DEFAULT 0;
+ALTER TABLE bounty ADD COLUMN trending_score REAL NOT NULL DEFAULT 0;
+ALTER TABLE dao ADD COLUMN trending_score REAL NOT NULL DEFAULT 0;
+ALTER TABLE prediction ADD COLUMN trending_score REAL NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_project_trending_score ON project(trending_score DESC);
+CREATE INDEX idx_bounty_trending_score ON bounty(trending_score DESC);
+CREATE INDEX idx_dao_trending_score ON dao(trending_score DESC);
+CREATE INDEX idx_prediction_trending_score ON prediction(trending_score DESC);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_bounty_comment_bounty_created_at_id;
+DROP INDEX IF EXISTS idx_project_comment_project_created_at_id;
+DROP INDEX IF EXISTS idx_funding_project_created_at_id;
+DROP INDEX IF EXISTS idx_bounty_chat_chat_number_created_at_id;
+DROP INDEX IF EXISTS idx_notifications_user_created_at_id;
+DROP INDEX IF EXISTS idx_bounty_updated_at_id;
+DROP INDEX IF EXISTS idx_project_updated_at_id;
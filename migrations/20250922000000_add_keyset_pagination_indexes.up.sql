@@ -0,0 +1,8 @@
+-- Add up migration script here
+CREATE INDEX IF NOT EXISTS idx_project_updated_at_id ON project (updated_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_bounty_updated_at_id ON bounty (updated_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created_at_id ON notifications (user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_bounty_chat_chat_number_created_at_id ON bounty_chat (chat_number, created_at, id);
+CREATE INDEX IF NOT EXISTS idx_funding_project_created_at_id ON funding (project_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_project_comment_project_created_at_id ON project_comment (project_id, created_at, id);
+CREATE INDEX IF NOT EXISTS idx_bounty_comment_bounty_created_at_id ON bounty_comment (bounty_id, created_at, id);
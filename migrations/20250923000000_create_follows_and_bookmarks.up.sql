@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS follows (
+        user_id UUID NOT NULL,
+        entity_type SMALLINT NOT NULL,
+        entity_id UUID NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (user_id, entity_type, entity_id),
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idx_follows_entity ON follows(entity_type, entity_id);
+
+CREATE TABLE
+    IF NOT EXISTS bookmarks (
+        user_id UUID NOT NULL,
+        entity_type SMALLINT NOT NULL,
+        entity_id UUID NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (user_id, entity_type, entity_id),
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+    );
@@ -0,0 +1,21 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS bounty_comment_reaction;
+DROP TABLE IF EXISTS project_comment_reaction;
+DROP TABLE IF EXISTS bounty_comment_edit;
+DROP TABLE IF EXISTS project_comment_edit;
+
+DROP INDEX IF EXISTS idx_bounty_comment_parent_created_at_id;
+DROP INDEX IF EXISTS idx_project_comment_parent_created_at_id;
+
+ALTER TABLE bounty_comment
+    DROP COLUMN IF EXISTS deleted_at,
+    DROP COLUMN IF EXISTS edited_at,
+    DROP COLUMN IF EXISTS is_pinned,
+    DROP COLUMN IF EXISTS parent_id;
+
+ALTER TABLE project_comment
+    DROP COLUMN IF EXISTS deleted_at,
+    DROP COLUMN IF EXISTS edited_at,
+    DROP COLUMN IF EXISTS is_pinned,
+    DROP COLUMN IF EXISTS parent_id;
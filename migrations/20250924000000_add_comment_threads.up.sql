@@ -0,0 +1,55 @@
+-- Add up migration script here
+
+ALTER TABLE project_comment
+    ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES project_comment(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS is_pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS edited_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+ALTER TABLE bounty_comment
+    ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES bounty_comment(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS is_pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS edited_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_project_comment_parent_created_at_id ON project_comment (parent_id, created_at, id) WHERE parent_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_bounty_comment_parent_created_at_id ON bounty_comment (parent_id, created_at, id) WHERE parent_id IS NOT NULL;
+
+-- Previous text of a comment, one row per edit
+CREATE TABLE
+    IF NOT EXISTS project_comment_edit (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        comment_id UUID NOT NULL REFERENCES project_comment(id) ON DELETE CASCADE,
+        comment TEXT NOT NULL,
+        edited_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_project_comment_edit_comment ON project_comment_edit (comment_id, edited_at);
+
+CREATE TABLE
+    IF NOT EXISTS bounty_comment_edit (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        comment_id UUID NOT NULL REFERENCES bounty_comment(id) ON DELETE CASCADE,
+        comment TEXT NOT NULL,
+        edited_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_bounty_comment_edit_comment ON bounty_comment_edit (comment_id, edited_at);
+
+CREATE TABLE
+    IF NOT EXISTS project_comment_reaction (
+        comment_id UUID NOT NULL REFERENCES project_comment(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        emoji VARCHAR(64) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (comment_id, user_id, emoji)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS bounty_comment_reaction (
+        comment_id UUID NOT NULL REFERENCES bounty_comment(id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        emoji VARCHAR(64) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (comment_id, user_id, emoji)
+    );
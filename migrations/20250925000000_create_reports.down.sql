@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS moderation_actions;
+DROP TABLE IF EXISTS reports;
+
+ALTER TABLE bounty_chat DROP COLUMN IF EXISTS moderation_status;
+ALTER TABLE bounty_comment DROP COLUMN IF EXISTS moderation_status;
+ALTER TABLE project_comment DROP COLUMN IF EXISTS moderation_status;
+ALTER TABLE bounty DROP COLUMN IF EXISTS moderation_status;
+ALTER TABLE project DROP COLUMN IF EXISTS moderation_status;
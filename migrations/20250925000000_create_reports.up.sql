@@ -0,0 +1,40 @@
+-- Add up migration script here
+
+-- 0: visible, 1: hidden, 2: removed
+ALTER TABLE project ADD COLUMN IF NOT EXISTS moderation_status SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE bounty ADD COLUMN IF NOT EXISTS moderation_status SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE project_comment ADD COLUMN IF NOT EXISTS moderation_status SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE bounty_comment ADD COLUMN IF NOT EXISTS moderation_status SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE bounty_chat ADD COLUMN IF NOT EXISTS moderation_status SMALLINT NOT NULL DEFAULT 0;
+
+CREATE TABLE
+    IF NOT EXISTS reports (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        reporter_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        target_type SMALLINT NOT NULL,
+        target_id UUID NOT NULL,
+        reason SMALLINT NOT NULL,
+        details TEXT,
+        status SMALLINT NOT NULL DEFAULT 0,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        resolved_at TIMESTAMPTZ,
+        resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+        UNIQUE (reporter_id, target_type, target_id)
+    );
+
+CREATE INDEX IF NOT EXISTS idx_reports_target ON reports(target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_reports_status_created_at ON reports(status, created_at);
+
+-- What was done to reported content, and by whom. Automatic hides have no admin.
+CREATE TABLE
+    IF NOT EXISTS moderation_actions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        target_type SMALLINT NOT NULL,
+        target_id UUID NOT NULL,
+        admin_id UUID REFERENCES users(id) ON DELETE SET NULL,
+        action SMALLINT NOT NULL,
+        note TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_moderation_actions_target ON moderation_actions(target_type, target_id, created_at);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_project_ai_next_attempt_at;
+
+ALTER TABLE project DROP COLUMN IF EXISTS ai_analyzed_at;
+ALTER TABLE project DROP COLUMN IF EXISTS ai_error;
+ALTER TABLE project DROP COLUMN IF EXISTS ai_next_attempt_at;
+ALTER TABLE project DROP COLUMN IF EXISTS ai_attempts;
@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+-- ai_status follows AIErrorCheckStatus (0: not yet, 1: in process, 2: done, 3: error)
+ALTER TABLE project ADD COLUMN IF NOT EXISTS ai_attempts INT NOT NULL DEFAULT 0;
+ALTER TABLE project ADD COLUMN IF NOT EXISTS ai_next_attempt_at TIMESTAMPTZ;
+ALTER TABLE project ADD COLUMN IF NOT EXISTS ai_error TEXT;
+ALTER TABLE project ADD COLUMN IF NOT EXISTS ai_analyzed_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_project_ai_next_attempt_at ON project(ai_next_attempt_at) WHERE ai_next_attempt_at IS NOT NULL;
+
+-- Queue projects already waiting for an editor
+UPDATE project SET ai_status = 0, ai_next_attempt_at = now()
+WHERE status IN (1, 2) AND ai_status IS NULL;
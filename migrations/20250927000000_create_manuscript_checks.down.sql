@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS manuscript_findings;
+DROP TABLE IF EXISTS manuscript_checks;
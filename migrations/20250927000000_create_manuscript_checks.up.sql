@@ -0,0 +1,32 @@
+-- Add up migration script here
+
+-- status follows AIErrorCheckStatus (0: not yet, 1: in process, 2: done, 3: error)
+CREATE TABLE
+    IF NOT EXISTS manuscript_checks (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+        requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+        manuscript VARCHAR(255) NOT NULL,
+        status SMALLINT NOT NULL DEFAULT 1,
+        error TEXT,
+        total_errors INT NOT NULL DEFAULT 0,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        completed_at TIMESTAMPTZ
+    );
+
+CREATE INDEX IF NOT EXISTS idx_manuscript_checks_project_created_at ON manuscript_checks(project_id, created_at DESC);
+
+-- error_type follows AnalysisErrorType, severity 0: low, 1: medium, 2: high, 3: critical
+CREATE TABLE
+    IF NOT EXISTS manuscript_findings (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        check_id UUID NOT NULL REFERENCES manuscript_checks(id) ON DELETE CASCADE,
+        error_type SMALLINT NOT NULL,
+        severity SMALLINT NOT NULL DEFAULT 1,
+        title TEXT NOT NULL DEFAULT '',
+        description TEXT NOT NULL DEFAULT '',
+        location TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_manuscript_findings_check_id ON manuscript_findings(check_id);
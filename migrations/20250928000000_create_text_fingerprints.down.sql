@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS project_similarity_matches;
+
+DROP TABLE IF EXISTS text_fingerprints;
@@ -0,0 +1,26 @@
+-- Add up migration script here
+
+-- entity_type follows SimilarityEntityType (0: project, 1: bounty). bands holds
+-- the LSH band hashes of the MinHash signature, used to find candidates.
+CREATE TABLE
+    IF NOT EXISTS text_fingerprints (
+        entity_type SMALLINT NOT NULL,
+        entity_id UUID NOT NULL,
+        signature BIGINT[] NOT NULL,
+        bands BIGINT[] NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (entity_type, entity_id)
+    );
+
+CREATE INDEX IF NOT EXISTS idx_text_fingerprints_bands ON text_fingerprints USING GIN (bands);
+
+CREATE TABLE
+    IF NOT EXISTS project_similarity_matches (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        project_id UUID NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+        matched_type SMALLINT NOT NULL,
+        matched_id UUID NOT NULL,
+        score DOUBLE PRECISION NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        UNIQUE (project_id, matched_type, matched_id)
+    );
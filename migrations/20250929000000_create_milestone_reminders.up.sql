@@ -0,0 +1,27 @@
+-- Add up migration script here
+
+-- Milestone due dates count from started_at, which wasn't set when a DAO vote
+-- passed. Take it from the status history of projects that went to funding (7).
+UPDATE project p
+SET
+    started_at = h.created_at
+FROM
+    (
+        SELECT project_id, MIN(created_at) AS created_at
+        FROM project_status_history
+        WHERE to_status = 7
+        GROUP BY project_id
+    ) h
+WHERE
+    p.id = h.project_id
+    AND p.started_at IS NULL;
+
+-- kind follows MilestoneReminderKind (0: due soon, 1: overdue)
+CREATE TABLE
+    IF NOT EXISTS milestone_reminders (
+        milestone_id UUID NOT NULL REFERENCES milestone(id) ON DELETE CASCADE,
+        kind SMALLINT NOT NULL,
+        days_before INT NOT NULL DEFAULT 0,
+        sent_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (milestone_id, kind, days_before)
+    );
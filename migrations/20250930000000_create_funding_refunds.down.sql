@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS funding_refunds;
+
+ALTER TABLE funding DROP COLUMN IF EXISTS refunded_amount;
@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+ALTER TABLE funding ADD COLUMN IF NOT EXISTS refunded_amount INT NOT NULL DEFAULT 0;
+
+CREATE TABLE
+    IF NOT EXISTS funding_refunds (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        funding_id UUID NOT NULL REFERENCES funding(id) ON DELETE CASCADE,
+        project_id UUID NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+        wallet VARCHAR(255) NOT NULL,
+        amount INT NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_funding_refunds_funding_id ON funding_refunds(funding_id);
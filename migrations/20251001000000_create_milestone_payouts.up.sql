@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+CREATE TABLE
+    IF NOT EXISTS milestone_payouts (
+        milestone_id UUID PRIMARY KEY REFERENCES milestone(id) ON DELETE CASCADE,
+        project_id UUID NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+        status SMALLINT NOT NULL DEFAULT 0,
+        approval_tx_hash VARCHAR(255),
+        approved_at TIMESTAMPTZ,
+        claimable_at TIMESTAMPTZ,
+        claim_tx_hash VARCHAR(255),
+        claimed_amount INT,
+        claimed_at TIMESTAMPTZ,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+
+CREATE INDEX IF NOT EXISTS idx_milestone_payouts_project_id ON milestone_payouts(project_id);
+CREATE INDEX IF NOT EXISTS idx_milestone_payouts_status ON milestone_payouts(status);
@@ -0,0 +1,19 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS team_member_search_vector_insert ON team_member;
+DROP TRIGGER IF EXISTS team_member_search_vector_update ON team_member;
+DROP TRIGGER IF EXISTS team_member_search_vector_delete ON team_member;
+
+CREATE OR REPLACE FUNCTION update_project_search_vector_from_team()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE project p
+    SET search_vector = project_search_vector(p.id, p.title, p.description, p.details, p.tags)
+    WHERE p.id = COALESCE(NEW.project_id, OLD.project_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER team_member_search_vector_update
+    AFTER INSERT OR UPDATE OR DELETE ON team_member
+    FOR EACH ROW
+    EXECUTE FUNCTION update_project_search_vector_from_team();
@@ -0,0 +1,43 @@
+-- Add up migration script here
+-- Refresh project search vectors once per statement instead of once per
+-- team member row, so replacing a team updates each project a single time
+DROP TRIGGER IF EXISTS team_member_search_vector_update ON team_member;
+DROP FUNCTION IF EXISTS update_project_search_vector_from_team();
+
+CREATE OR REPLACE FUNCTION update_project_search_vector_from_team()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE project p
+        SET search_vector = project_search_vector(p.id, p.title, p.description, p.details, p.tags)
+        WHERE p.id IN (SELECT project_id FROM new_rows);
+    ELSIF TG_OP = 'UPDATE' THEN
+        UPDATE project p
+        SET search_vector = project_search_vector(p.id, p.title, p.description, p.details, p.tags)
+        WHERE p.id IN (SELECT project_id FROM new_rows UNION SELECT project_id FROM old_rows);
+    ELSE
+        UPDATE project p
+        SET search_vector = project_search_vector(p.id, p.title, p.description, p.details, p.tags)
+        WHERE p.id IN (SELECT project_id FROM old_rows);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER team_member_search_vector_insert
+    AFTER INSERT ON team_member
+    REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION update_project_search_vector_from_team();
+
+CREATE TRIGGER team_member_search_vector_update
+    AFTER UPDATE ON team_member
+    REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION update_project_search_vector_from_team();
+
+CREATE TRIGGER team_member_search_vector_delete
+    AFTER DELETE ON team_member
+    REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION update_project_search_vector_from_team();
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_view_events_viewer;
+
+ALTER TABLE view_events DROP COLUMN IF EXISTS viewer;
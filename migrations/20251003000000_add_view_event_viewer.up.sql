@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- User id or client address, used to count repeated views once
+ALTER TABLE view_events ADD COLUMN viewer TEXT;
+
+CREATE INDEX idx_view_events_viewer ON view_events(entity_type, entity_id, viewer, created_at);
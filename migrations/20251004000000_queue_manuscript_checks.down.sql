@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_manuscript_checks_open;
+
+ALTER TABLE manuscript_checks ALTER COLUMN status SET DEFAULT 1;
+
+ALTER TABLE manuscript_checks DROP COLUMN IF EXISTS lease_until;
@@ -0,0 +1,13 @@
+-- Add up migration script here
+-- Checks are queued as not yet (0) and run by the AI review job, which holds
+-- them in process (1) until lease_until
+ALTER TABLE manuscript_checks ADD COLUMN lease_until TIMESTAMPTZ;
+
+ALTER TABLE manuscript_checks ALTER COLUMN status SET DEFAULT 0;
+
+-- Checks left in process by an interrupted request would never finish
+UPDATE manuscript_checks SET status = 3, error = 'Interrupted', completed_at = now()
+WHERE status IN (0, 1);
+
+-- At most one queued or running check per project
+CREATE UNIQUE INDEX idx_manuscript_checks_open ON manuscript_checks(project_id) WHERE status IN (0, 1);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE funding_refunds DROP CONSTRAINT IF EXISTS funding_refunds_event_key;
+
+ALTER TABLE funding_refunds DROP COLUMN IF EXISTS log_index;
+
+ALTER TABLE funding_refunds DROP COLUMN IF EXISTS tx_hash;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+-- The Refunded event a refund was applied from. One event can be spread over
+-- several fundings, so the funding is part of the key.
+ALTER TABLE funding_refunds ADD COLUMN tx_hash VARCHAR(255);
+
+ALTER TABLE funding_refunds ADD COLUMN log_index BIGINT;
+
+ALTER TABLE funding_refunds ADD CONSTRAINT funding_refunds_event_key UNIQUE (tx_hash, log_index, funding_id);
@@ -0,0 +1,9 @@
+-- Add down migration script here
+DELETE FROM announcements WHERE sender_id IS NULL;
+
+ALTER TABLE announcements DROP CONSTRAINT IF EXISTS announcements_sender_id_fkey;
+
+ALTER TABLE announcements ADD CONSTRAINT announcements_sender_id_fkey
+    FOREIGN KEY (sender_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE announcements ALTER COLUMN sender_id SET NOT NULL;
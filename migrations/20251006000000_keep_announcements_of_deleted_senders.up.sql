@@ -0,0 +1,8 @@
+-- Add up migration script here
+-- Announcements stay on record when the sending admin is deleted
+ALTER TABLE announcements ALTER COLUMN sender_id DROP NOT NULL;
+
+ALTER TABLE announcements DROP CONSTRAINT IF EXISTS announcements_sender_id_fkey;
+
+ALTER TABLE announcements ADD CONSTRAINT announcements_sender_id_fkey
+    FOREIGN KEY (sender_id) REFERENCES users(id) ON DELETE SET NULL;